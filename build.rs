@@ -0,0 +1,485 @@
+//! Generates code from the source files, for the features that need it.
+//!
+//! * `introspection`: the table is built by scanning the source files for
+//!   public functions and reading the same `Intrinsic` / `Assembly` doc lines
+//!   (and the `doc(cfg(..))` attribute) that rustdoc shows, so it can't drift
+//!   out of date with the docs.
+//! * `runtime`: the `dynamic` module gets a copy of each CPU feature module,
+//!   with each function turned into an `unsafe` `#[target_feature]` function,
+//!   along with a token method that calls it. Copying the source means that
+//!   the two versions can't drift apart either.
+
+use std::{env, fmt::Write as _, fs, path::Path};
+
+fn main() {
+  println!("cargo:rerun-if-changed=build.rs");
+  println!("cargo:rerun-if-changed=src");
+  if env::var_os("CARGO_FEATURE_INTROSPECTION").is_some() {
+    write_introspection_table();
+  }
+  if env::var_os("CARGO_FEATURE_RUNTIME").is_some() {
+    write_dynamic_modules();
+  }
+}
+
+/// Writes `intrinsic_table.rs`, an expression of the whole table.
+fn write_introspection_table() {
+  let mut paths: Vec<_> = fs::read_dir("src/x86_x64").unwrap().map(|entry| entry.unwrap().path()).collect();
+  paths.sort();
+  paths.insert(0, Path::new("src/lib.rs").to_path_buf());
+
+  let mut out = String::from("&[\n");
+  for path in &paths {
+    let text = fs::read_to_string(path).unwrap();
+    scan_file(&text, &mut out);
+  }
+  out.push_str("]\n");
+
+  let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("intrinsic_table.rs");
+  fs::write(dest, out).unwrap();
+}
+
+/// Appends one `IntrinsicInfo` expression per public function in the file.
+fn scan_file(text: &str, out: &mut String) {
+  let lines: Vec<&str> = text.lines().collect();
+  let file_features = lines.iter().find(|l| l.starts_with("#![cfg(")).map(|l| target_features(l)).unwrap_or_default();
+
+  for (i, line) in lines.iter().enumerate() {
+    let trimmed = line.trim_start();
+    let name = match fn_name(trimmed) {
+      Some(name) => name,
+      None => continue,
+    };
+    // Only free functions: skip anything indented inside an `impl` block.
+    let indent = line.len() - trimmed.len();
+    let in_lib_block = indent == 2 && text.contains("submodule!(pub x86_x64 {");
+    if indent != 0 && !in_lib_block {
+      continue;
+    }
+
+    // Walk back over the attributes and doc comment of this function.
+    let mut start = i;
+    while start > 0 {
+      let prev = lines[start - 1].trim_start();
+      if prev.starts_with("///") || prev.starts_with("#[") {
+        start -= 1;
+      } else {
+        break;
+      }
+    }
+    let header = &lines[start..i];
+
+    let mut intrinsic = String::new();
+    let mut instructions = Vec::new();
+    let mut features = Vec::new();
+    let mut collecting_alternatives = false;
+    for h in header {
+      let h = h.trim_start();
+      if let Some(rest) = h.strip_prefix("/// * **Intrinsic:**") {
+        intrinsic = between_backticks(rest).unwrap_or_default();
+        collecting_alternatives = false;
+      } else if let Some(rest) = h.strip_prefix("/// * **Assembly:**") {
+        let rest = rest.trim();
+        if rest == "one of" {
+          collecting_alternatives = true;
+        } else if let Some(asm) = between_backticks(rest) {
+          instructions.push(asm);
+        } else if !rest.is_empty() {
+          instructions.push(rest.to_string());
+        }
+      } else if collecting_alternatives && h.starts_with("///   * ") {
+        if let Some(asm) = between_backticks(h) {
+          instructions.push(asm);
+        }
+      } else if h.starts_with("#[cfg_attr(docsrs, doc(cfg(") {
+        features = target_features(h);
+      }
+    }
+    if features.is_empty() {
+      features = file_features.clone();
+    }
+    if intrinsic.is_empty() {
+      intrinsic = body_intrinsic(&lines[i..]).unwrap_or_default();
+    }
+
+    let _ = writeln!(out, "  IntrinsicInfo {{ function: {:?}, intrinsic: {:?}, target_features: &{:?}, instructions: &{:?} }},", name, intrinsic, features, instructions);
+  }
+}
+
+/// The function name, if this line starts a `pub fn` (or `pub const fn`).
+fn fn_name(line: &str) -> Option<&str> {
+  let rest = line.strip_prefix("pub fn ").or_else(|| line.strip_prefix("pub const fn "))?;
+  let end = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))?;
+  Some(&rest[..end])
+}
+
+/// All the `target_feature = "..."` values named in an attribute line.
+fn target_features(line: &str) -> Vec<String> {
+  line.split("target_feature = \"").skip(1).filter_map(|s| s.split('"').next()).map(String::from).collect()
+}
+
+/// The text between the first pair of backticks, if it's not empty.
+fn between_backticks(s: &str) -> Option<String> {
+  let mut parts = s.split('`');
+  parts.next()?;
+  let inner = parts.next()?.trim();
+  if inner.is_empty() {
+    None
+  } else {
+    Some(inner.to_string())
+  }
+}
+
+/// The first intrinsic called within an `unsafe` block in the function body.
+fn body_intrinsic(lines: &[&str]) -> Option<String> {
+  for line in lines.iter().skip(1) {
+    if *line == "}" || *line == "  }" {
+      break;
+    }
+    if let Some(pos) = line.find("unsafe { _") {
+      let rest = &line[pos + "unsafe { ".len()..];
+      let end = rest.find('(')?;
+      return Some(rest[..end].to_string());
+    }
+  }
+  None
+}
+
+/// Each CPU feature module that `dynamic` has a copy of: the module, the
+/// `target_feature` to enable, the token type, and the safety requirement.
+#[rustfmt::skip]
+const DYNAMIC_MODULES: &[(&str, &str, &str, &str)] = &[
+  ("sse", "sse", "SseToken", "The CPU must support `sse`."),
+  ("sse2", "sse2", "Sse2Token", "The CPU must support `sse2`."),
+  ("sse3", "sse3", "Sse3Token", "The CPU must support `sse3`."),
+  ("ssse3", "ssse3", "Ssse3Token", "The CPU must support `ssse3`."),
+  ("sse4_1", "sse4.1", "Sse41Token", "The CPU must support `sse4.1`."),
+  ("sse4_2", "sse4.2", "Sse42Token", "The CPU must support `sse4.2`."),
+  ("avx", "avx", "AvxToken", "The CPU must support `avx`, and the OS must have enabled the `ymm` register state."),
+  ("avx2", "avx2", "Avx2Token", "The CPU must support `avx2`, and the OS must have enabled the `ymm` register state."),
+  ("adx", "adx", "AdxToken", "The CPU must support `adx`."),
+  ("aes", "aes", "AesToken", "The CPU must support `aes` and `sse2`."),
+  ("bmi1", "bmi1", "Bmi1Token", "The CPU must support `bmi1`."),
+  ("bmi2", "bmi2", "Bmi2Token", "The CPU must support `bmi2`."),
+  ("f16c", "f16c", "F16cToken", "The CPU must support `f16c` and `avx`, and the OS must have enabled the `ymm` register state."),
+  ("fma", "fma", "FmaToken", "The CPU must support `fma` and `avx`, and the OS must have enabled the `ymm` register state."),
+  ("lzcnt", "lzcnt", "LzcntToken", "The CPU must support `lzcnt`."),
+  ("pclmulqdq", "pclmulqdq", "PclmulqdqToken", "The CPU must support `pclmulqdq` and `sse2`."),
+  ("popcnt", "popcnt", "PopcntToken", "The CPU must support `popcnt`."),
+  ("rdrand", "rdrand", "RdrandToken", "The CPU must support `rdrand`."),
+  ("rdseed", "rdseed", "RdseedToken", "The CPU must support `rdseed`."),
+];
+
+/// Writes `dynamic_<module>.rs` for each module in [`DYNAMIC_MODULES`].
+fn write_dynamic_modules() {
+  let out_dir = env::var("OUT_DIR").unwrap();
+  let texts: Vec<String> = DYNAMIC_MODULES.iter().map(|(module, ..)| fs::read_to_string(format!("src/x86_x64/{}.rs", module)).unwrap()).collect();
+
+  // Types with methods (such as `StreamingStores`) stay in the `cfg` modules,
+  // so functions that use one are left out. Enums only exist for their
+  // immediate values, so those are copied.
+  let mut structs = Vec::new();
+  for text in &texts {
+    for item in parse_items(text) {
+      if let Some(name) = item.code[0].strip_prefix("pub struct ") {
+        structs.push(ident_prefix(name).to_string());
+      }
+    }
+  }
+
+  for ((module, feature, token, safety), text) in DYNAMIC_MODULES.iter().zip(&texts) {
+    let items = parse_items(text);
+    let enums: Vec<&str> = items.iter().filter_map(|item| item.code[0].strip_prefix("pub enum ")).map(ident_prefix).collect();
+
+    let mut out = format!("// Generated by `build.rs` from `src/x86_x64/{}.rs`.\n\n", module);
+    let mut methods = String::new();
+    for item in &items {
+      let first = item.code[0];
+      let is_pub = first.starts_with("pub ");
+      let kind = first.trim_start_matches("pub(crate) ").trim_start_matches("pub ");
+      if kind.starts_with("fn ") {
+        let sig = Signature::parse(&item.code);
+        if item.attrs.iter().any(|a| a.starts_with("#[deprecated")) || structs.iter().any(|s| contains_word(&sig.text, s)) {
+          continue;
+        }
+        push_docs(&mut out, &dynamic_fn_docs(&item.docs, safety), "");
+        push_attrs(&mut out, &item.attrs);
+        if !is_pub {
+          // Some helpers are only used under a `cfg` for a higher feature.
+          out.push_str("#[allow(dead_code)]\n");
+        }
+        let _ = writeln!(out, "#[inline]\n#[target_feature(enable = {:?})]", feature);
+        out.push_str(&first.replacen("fn ", "unsafe fn ", 1));
+        out.push('\n');
+        push_lines(&mut out, &item.code[1..]);
+        if first.starts_with("pub fn ") {
+          token_method(&mut methods, item, &sig);
+        }
+      } else if kind.starts_with("const fn ") || kind.starts_with("const ") || enums.iter().any(|e| impl_target(first) == Some(e)) || kind.starts_with("enum ") {
+        // These don't use the feature, so they're copied as they are.
+        push_docs(&mut out, &without_examples(&item.docs), "");
+        push_attrs(&mut out, &item.attrs);
+        if !is_pub && !first.starts_with("impl") {
+          out.push_str("#[allow(dead_code)]\n");
+        }
+        push_lines(&mut out, &item.code);
+      } else {
+        continue;
+      }
+      out.push('\n');
+    }
+    let _ = write!(out, "impl {} {{\n{}}}\n", token, methods);
+
+    fs::write(Path::new(&out_dir).join(format!("dynamic_{}.rs", module)), out).unwrap();
+  }
+}
+
+/// A top level item of a source file.
+struct Item<'a> {
+  docs: Vec<&'a str>,
+  attrs: Vec<&'a str>,
+  code: Vec<&'a str>,
+}
+
+/// Splits a file into its top level items, skipping comments and `use`s.
+fn parse_items(text: &str) -> Vec<Item<'_>> {
+  let lines: Vec<&str> = text.lines().collect();
+  let mut items = Vec::new();
+  let mut docs = Vec::new();
+  let mut attrs = Vec::new();
+  let mut i = 0;
+  while i < lines.len() {
+    let line = lines[i];
+    if line.starts_with("///") {
+      docs.push(line);
+    } else if line.starts_with("#[") {
+      attrs.push(line);
+    } else if line.is_empty() || line.starts_with("//") || line.starts_with("#!") || line.starts_with("use ") || line.starts_with(' ') {
+      docs.clear();
+      attrs.clear();
+    } else {
+      let start = i;
+      if !(line.ends_with(';') || line.ends_with('}')) {
+        while !(lines[i].starts_with('}') || lines[i] == "];") {
+          i += 1;
+        }
+      }
+      items.push(Item { docs: core::mem::take(&mut docs), attrs: core::mem::take(&mut attrs), code: lines[start..=i].to_vec() });
+    }
+    i += 1;
+  }
+  items
+}
+
+/// The leading identifier of the string.
+fn ident_prefix(s: &str) -> &str {
+  let end = s.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(s.len());
+  &s[..end]
+}
+
+/// If `word` appears in `text` as a whole identifier.
+fn contains_word(text: &str, word: &str) -> bool {
+  text.match_indices(word).any(|(i, _)| {
+    let before = text[..i].chars().next_back();
+    let after = text[i + word.len()..].chars().next();
+    let is_ident = |c: Option<char>| c.is_some_and(|c| c.is_ascii_alphanumeric() || c == '_');
+    !is_ident(before) && !is_ident(after)
+  })
+}
+
+/// The type that an `impl` line is for.
+fn impl_target(line: &str) -> Option<&str> {
+  let rest = line.strip_prefix("impl ")?;
+  let rest = rest.rsplit(" for ").next()?;
+  Some(ident_prefix(rest))
+}
+
+/// The doc comment with the examples taken out, since they're for the `cfg`
+/// version.
+fn without_examples(docs: &[&str]) -> Vec<String> {
+  let mut out: Vec<String> = Vec::new();
+  let mut in_example = false;
+  for line in docs {
+    let repeated_blank = line.trim() == "///" && out.last().is_none_or(|l| l.trim() == "///");
+    if line.trim_start().starts_with("/// ```") {
+      in_example = !in_example;
+    } else if !in_example && !repeated_blank {
+      out.push(line.to_string());
+    }
+  }
+  while out.last().is_some_and(|l| l.trim() == "///") {
+    out.pop();
+  }
+  out
+}
+
+/// The doc comment for a `dynamic` function: the same docs, without the
+/// examples, and with a safety section before the intrinsic and assembly list.
+fn dynamic_fn_docs(docs: &[&str], safety: &str) -> Vec<String> {
+  let mut out = without_examples(docs);
+  let at = out.iter().position(|l| l.starts_with("/// * **Intrinsic:**") || l.starts_with("/// * **Assembly:**")).unwrap_or(out.len());
+  let mut section = Vec::new();
+  if at > 0 && out[at - 1] != "///" {
+    section.push("///".to_string());
+  }
+  section.push("/// ## Safety".to_string());
+  section.extend(wrap_doc(safety, ""));
+  if at < out.len() {
+    section.push("///".to_string());
+  }
+  out.splice(at..at, section);
+  out
+}
+
+/// Wraps text into doc comment lines of at most 80 columns.
+fn wrap_doc(text: &str, indent: &str) -> Vec<String> {
+  let mut lines = Vec::new();
+  let mut line = format!("{}///", indent);
+  for word in text.split_whitespace() {
+    if line.len() + 1 + word.len() > 80 && line.len() > indent.len() + 3 {
+      lines.push(core::mem::replace(&mut line, format!("{}///", indent)));
+    }
+    line.push(' ');
+    line.push_str(word);
+  }
+  lines.push(line);
+  lines
+}
+
+fn push_docs(out: &mut String, docs: &[String], indent: &str) {
+  for line in docs {
+    let _ = writeln!(out, "{}{}", indent, line);
+  }
+}
+
+/// Pushes the attributes that also apply to the copy.
+fn push_attrs(out: &mut String, attrs: &[&str]) {
+  for attr in attrs.iter().filter(|a| !a.starts_with("#[inline") && !a.starts_with("#[cfg_attr(docsrs")) {
+    out.push_str(attr);
+    out.push('\n');
+  }
+}
+
+fn push_lines(out: &mut String, lines: &[&str]) {
+  for line in lines {
+    out.push_str(line);
+    out.push('\n');
+  }
+}
+
+/// The parts of a function signature that a token method needs.
+struct Signature {
+  /// The whole signature on one line, without the `{`.
+  text: String,
+  name: String,
+  /// The generic parameters, with the angle brackets.
+  generics: String,
+  /// The parameter list, without the parentheses.
+  params: String,
+  /// Whatever follows the parameter list, such as the return type.
+  rest: String,
+}
+
+impl Signature {
+  fn parse(code: &[&str]) -> Self {
+    let mut text = String::new();
+    for line in code {
+      text.push_str(line.trim());
+      text.push(' ');
+      if line.ends_with('{') {
+        break;
+      }
+    }
+    let text = text.trim_end().trim_end_matches('{').trim_end().replace("( ", "(").replace(", )", ")");
+    let after_fn = &text[text.find("fn ").unwrap() + 3..];
+    let name = ident_prefix(after_fn).to_string();
+    let mut rest = &after_fn[name.len()..];
+    let mut generics = String::new();
+    if rest.starts_with('<') {
+      let end = closing(rest, '<', '>');
+      generics = rest[..=end].to_string();
+      rest = &rest[end + 1..];
+    }
+    let end = closing(rest, '(', ')');
+    let params = rest[1..end].trim().trim_end_matches(',').to_string();
+    let rest = rest[end + 1..].trim().to_string();
+    Self { text, name, generics, params, rest }
+  }
+
+  /// The names of the parameters, to pass them along.
+  fn args(&self) -> Vec<String> {
+    split_top_level(&self.params).iter().map(|p| p.split(':').next().unwrap().trim().trim_start_matches("mut ").to_string()).collect()
+  }
+
+  /// The explicit generic arguments, such as `::<IMM>`, if there are any.
+  fn turbofish(&self) -> String {
+    if self.generics.is_empty() {
+      return String::new();
+    }
+    let inner = &self.generics[1..self.generics.len() - 1];
+    let names: Vec<&str> = split_top_level(inner).into_iter().filter(|g| !g.starts_with('\'')).map(|g| ident_prefix(g.trim_start_matches("const "))).collect();
+    if names.is_empty() {
+      String::new()
+    } else {
+      format!("::<{}>", names.join(", "))
+    }
+  }
+}
+
+/// The index of the bracket that closes the one that `s` starts with.
+fn closing(s: &str, open: char, close: char) -> usize {
+  let mut depth = 0;
+  for (i, c) in s.char_indices() {
+    if c == open {
+      depth += 1;
+    } else if c == close && !s[..i].ends_with('-') {
+      depth -= 1;
+      if depth == 0 {
+        return i;
+      }
+    }
+  }
+  panic!("unbalanced `{}` in `{}`", open, s);
+}
+
+/// Splits on the commas that aren't within any brackets.
+fn split_top_level(s: &str) -> Vec<&str> {
+  let mut parts = Vec::new();
+  let mut depth = 0;
+  let mut start = 0;
+  for (i, c) in s.char_indices() {
+    match c {
+      '(' | '[' | '<' => depth += 1,
+      ')' | ']' => depth -= 1,
+      '>' if !s[..i].ends_with('-') => depth -= 1,
+      ',' if depth == 0 => {
+        parts.push(s[start..i].trim());
+        start = i + 1;
+      }
+      _ => (),
+    }
+  }
+  parts.push(s[start..].trim());
+  parts.retain(|p| !p.is_empty());
+  parts
+}
+
+/// Appends the token method that calls the `dynamic` version of a function.
+fn token_method(out: &mut String, item: &Item, sig: &Signature) {
+  if !out.is_empty() {
+    out.push('\n');
+  }
+  let summary: Vec<&str> = item.docs.iter().map(|l| l.trim_start_matches("///").trim()).take_while(|l| !l.is_empty() && !l.starts_with("```") && !l.starts_with("* **")).collect();
+  let summary = summary.join(" ");
+  push_docs(out, &wrap_doc(&format!("{}, see `{}`.", summary.trim_end_matches('.'), sig.name), "  "), "");
+  for attr in item.attrs.iter().filter(|a| !a.starts_with("#[inline") && !a.starts_with("#[cfg_attr(docsrs") && !a.starts_with("#[rustfmt")) {
+    let _ = writeln!(out, "  {}", attr);
+  }
+  let params: Vec<String> = split_top_level(&sig.params).iter().map(|p| p.trim_start_matches("mut ").to_string()).collect();
+  let mut self_params = vec!["self".to_string()];
+  self_params.extend(params);
+  let rest = if sig.rest.is_empty() { String::new() } else { format!(" {}", sig.rest) };
+  let _ = writeln!(out, "  #[inline]\n  pub fn {}{}({}){} {{", sig.name, sig.generics, self_params.join(", "), rest);
+  out.push_str("    // Safety: the token proves that the feature is available.\n");
+  let _ = writeln!(out, "    unsafe {{ {}{}({}) }}\n  }}", sig.name, sig.turbofish(), sig.args().join(", "));
+}
@@ -0,0 +1,38 @@
+//! A machine readable table describing each function in the crate.
+//!
+//! This is meant for tooling such as benchmark harnesses, binding generators,
+//! and auditors, which would otherwise have to scrape the docs. The table is
+//! generated from the same doc lines that rustdoc shows, so the two always
+//! agree.
+//!
+//! The table lists every function in the crate's source, including ones that
+//! aren't part of the current build because their target feature isn't
+//! enabled. Check the `target_features` field if that matters to you.
+//!
+//! ```
+//! use safe_arch::introspection::*;
+//! let info =
+//!   INTRINSIC_TABLE.iter().find(|i| i.function == "shuffle_ai_f32_all_m128i").unwrap();
+//! assert_eq!(info.intrinsic, "_mm_shuffle_epi32");
+//! assert_eq!(info.target_features, &["sse2"]);
+//! assert_eq!(info.instructions, &["pshufd xmm, xmm, imm8"]);
+//! ```
+
+/// Information about one function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IntrinsicInfo {
+  /// The name of the `safe_arch` function.
+  pub function: &'static str,
+  /// The intrinsic from `core::arch` that the function is built on, or an
+  /// empty string if it's a composite of other functions.
+  pub intrinsic: &'static str,
+  /// The target features that the function is gated on.
+  pub target_features: &'static [&'static str],
+  /// The instruction(s) the function compiles to, as listed in its docs. If
+  /// there's more than one entry, the compiler picks one of them. This is
+  /// empty for functions whose docs don't list an instruction.
+  pub instructions: &'static [&'static str],
+}
+
+/// Every function in the crate, in source order.
+pub static INTRINSIC_TABLE: &[IntrinsicInfo] = include!(concat!(env!("OUT_DIR"), "/intrinsic_table.rs"));
@@ -4,14 +4,15 @@
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::transmute_ptr_to_ptr)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(feature = "portable_simd", feature(portable_simd))]
 
 //! A crate that safely exposes arch intrinsics via `#[cfg()]`.
 //!
 //! `safe_arch` lets you safely use CPU intrinsics. Those things in the
 //! [`core::arch`](core::arch) modules. It works purely via `#[cfg()]` and
 //! compile time CPU feature declaration. If you want to check for a feature at
-//! runtime and then call an intrinsic or use a fallback path based on that then
-//! this crate is sadly not for you.
+//! runtime and then call an intrinsic or use a fallback path based on that, see
+//! the `runtime` feature below.
 //!
 //! SIMD register types are "newtype'd" so that better trait impls can be given
 //! to them, but the inner value is a `pub` field so feel free to just grab it
@@ -19,6 +20,11 @@
 //! `From`/`Into` of appropriate data types, and appropriate operator
 //! overloading.
 //!
+//! The array and bit pattern constructors of the newtypes, along with the
+//! `zeroed_*` and `set_splat_*` functions, are `const fn`. This lets you keep
+//! SIMD constants in a `const` or `static` instead of rebuilding them at
+//! runtime.
+//!
 //! * Most intrinsics (like addition and multiplication) are totally safe to use
 //!   as long as the CPU feature is available. In this case, what you get is 1:1
 //!   with the actual intrinsic.
@@ -28,15 +34,43 @@
 //!   * Try the [bytemuck](https://docs.rs/bytemuck) crate (and turn on the
 //!     `bytemuck` feature of this crate) if you want help safely casting
 //!     between reference types.
+//! * On Nightly, the `portable_simd` feature adds `From`/`Into` impls between
+//!   the register types and the matching `core::simd::Simd` types, so you can
+//!   mix portable SIMD code with exact intrinsic calls.
+//! * The `flush_denormals` feature adds `FlushDenormalsGuard`, which turns on
+//!   flush-to-zero and denormals-are-zero for the current thread. Changing the
+//!   float environment is something that LLVM doesn't expect, so read the
+//!   safety notes before using it.
+//! * Each CPU feature module (`sse`, `sse2`, ..., `avx2`, plus `adx`, `aes`,
+//!   and the other small ones) has a Cargo feature of the same name. They're
+//!   all on by default, and a module is built only if both its Cargo feature
+//!   and its target feature are enabled. Use `default-features = false` and
+//!   list the ones you want to shrink compile times and the docs.
+//! * The `math` feature adds polynomial approximations of `exp`, `ln`, `sin`,
+//!   and `cos` on `m256` lanes (when `avx2` is also enabled), each with a
+//!   documented maximum error.
+//! * The `runtime` feature adds token types like `Avx2Token`, which you only
+//!   get by checking the CPU at runtime. Their methods use that feature even
+//!   when it isn't enabled at compile time, which is how to do runtime dispatch
+//!   with this crate. It also adds the [`dynamic`] module, with the `unsafe`
+//!   `#[target_feature]` functions that the tokens call.
+//! * The `introspection` feature adds the `introspection` module, with a
+//!   static table listing each function along with the intrinsic, target
+//!   features, and instructions it uses. This is meant for tooling.
+//! * The `half` feature adds variants of the half-precision conversions that
+//!   use [half](https://docs.rs/half) crate types instead of raw `u16` bit
+//!   patterns.
 //! * Some intrinsics are not safe unless you're _very_ careful about how you
 //!   use them, such as the streaming operations requiring you to use them in
-//!   combination with an appropriate memory fence. Those operations aren't
-//!   exposed here.
+//!   combination with an appropriate memory fence. Streaming stores are only
+//!   exposed through [`streaming_stores`], and streaming loads through
+//!   [`streaming_loads`], which handle the fences for you.
 //! * Some intrinsics mess with the processor state, such as changing the
 //!   floating point flags, saving and loading special register state, and so
 //!   on. LLVM doesn't really support you messing with that within a high level
-//!   language, so those operations aren't exposed here. Use assembly or
-//!   something if you want to do that.
+//!   language, so those operations aren't exposed here (other than the opt-in
+//!   `flush_denormals` feature). Use assembly or something if you want to do
+//!   that.
 //!
 //! ## Naming Conventions
 //! The `safe_arch` crate does not simply use the "official" names for each
@@ -51,8 +85,8 @@
 //! * `x86` / `x86_64` (Intel, AMD, etc)
 //!   * 128-bit: `sse`, `sse2`, `sse3`, `ssse3`, `sse4.1`, `sse4.2`
 //!   * 256-bit: `avx`, `avx2`
-//!   * Other: `adx`, `aes`, `bmi1`, `bmi2`, `fma`, `lzcnt`, `pclmulqdq`,
-//!     `popcnt`, `rdrand`, `rdseed`
+//!   * Other: `adx`, `aes`, `bmi1`, `bmi2`, `f16c`, `fma`, `lzcnt`,
+//!     `pclmulqdq`, `popcnt`, `rdrand`, `rdseed`
 //!
 //! ## Compile Time CPU Target Features
 //!
@@ -92,8 +126,12 @@
 //! deferring the check for the feature to runtime. This means that, if you
 //! _did_ want a check at the start of your program, to confirm that all the
 //! assumed features are present and error out when the assumptions don't hold,
-//! you can't use that macro. You gotta use CPUID and check manually. rip.
-//! Hopefully we can make that process easier in a future version of this crate.
+//! you can't use that macro. You gotta use CPUID and check manually. To help
+//! with that, [`cpu_features`] runs `cpuid` and decodes the feature bits for
+//! you, so a startup check is just a matter of comparing the fields you need.
+//! Better yet, [`ensure_cpu_compatible`] compares all the target features of
+//! the build for you, and its error lists what's missing. Make it the first
+//! thing that `main` does when you build with extra target features.
 //!
 //! [steam-survey]:
 //! https://store.steampowered.com/hwsurvey/Steam-Hardware-Software-Survey-Welcome-to-Steam
@@ -124,6 +162,28 @@
 //! Accordingly, if you plan to call this crate or not depending on what
 //! features are enabled in the build you'll also need to control your use of
 //! this crate via cfg attribute, not cfg macro.
+//!
+//! ### Shipping One Build For Many CPUs
+//!
+//! The `cfg` modules only ever hold what the build's target features allow, so
+//! they can't give one artifact with a path for each CPU. For that, turn on the
+//! `runtime` feature, which is compiled whatever the target features are:
+//! * The token types (such as `Avx2Token`) are made by checking the CPU, and
+//!   their methods are safe to call.
+//! * The [`dynamic`] module has the same functions as `unsafe`
+//!   `#[target_feature]` functions, for use within your own
+//!   `#[target_feature]` code.
+//! * [`simd_dispatch!`], [`best_available!`], and [`with_feature!`] pick
+//!   between code paths using the tokens.
+//!
+//! So `runtime` is the "compile always" mode. Every CPU feature module that
+//! has its Cargo feature on is built into [`dynamic`] (by `build.rs`, from the
+//! module's own source), and has a token with a method for each of its public
+//! functions: from `SseToken` through `Avx2Token`, and `AdxToken` through
+//! `RdseedToken`. The [`dynamic`] module docs list the few things that are
+//! left out. The `cfg` modules themselves stay gated on their target features,
+//! so that code built for a known CPU can keep calling safe functions with no
+//! token at all.
 
 use core::{
   convert::AsRef,
@@ -133,41 +193,367 @@ use core::{
 
 pub mod naming_conventions;
 
+#[cfg(feature = "introspection")]
+#[cfg_attr(docsrs, doc(cfg(feature = "introspection")))]
+pub mod introspection;
+
 /// Turns a round operator token to the correct constant value.
+///
+/// This is a thin shim over [`RoundMode`]: `round_op!(Nearest)` is the same as
+/// `RoundMode::Nearest.imm()`, and `NegInf`, `PosInf`, and `Zero` are
+/// `Floor`, `Ceil`, and `TowardZero`. Use the enum instead.
 #[macro_export]
-#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+#[deprecated(since = "0.8.0", note = "use `RoundMode::Name.imm()` instead of `round_op!(Name)`")]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 // Note(Lokathor): keep this at the crate root.
 macro_rules! round_op {
-  (Nearest) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::{_MM_FROUND_NO_EXC, _MM_FROUND_TO_NEAREST_INT};
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::{_MM_FROUND_NO_EXC, _MM_FROUND_TO_NEAREST_INT};
-    _MM_FROUND_NO_EXC | _MM_FROUND_TO_NEAREST_INT
-  }};
-  (NegInf) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::{_MM_FROUND_NO_EXC, _MM_FROUND_TO_NEG_INF};
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::{_MM_FROUND_NO_EXC, _MM_FROUND_TO_NEG_INF};
-    _MM_FROUND_NO_EXC | _MM_FROUND_TO_NEG_INF
-  }};
-  (PosInf) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::{_MM_FROUND_NO_EXC, _MM_FROUND_TO_POS_INF};
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::{_MM_FROUND_NO_EXC, _MM_FROUND_TO_POS_INF};
-    _MM_FROUND_NO_EXC | _MM_FROUND_TO_POS_INF
-  }};
-  (Zero) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::{_mm256_round_pd, _MM_FROUND_NO_EXC, _MM_FROUND_TO_ZERO};
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::{_mm256_round_pd, _MM_FROUND_NO_EXC, _MM_FROUND_TO_ZERO};
-    _MM_FROUND_NO_EXC | _MM_FROUND_TO_ZERO
+  (Nearest) => {
+    $crate::RoundMode::Nearest.imm()
+  };
+  (NegInf) => {
+    $crate::RoundMode::Floor.imm()
+  };
+  (PosInf) => {
+    $crate::RoundMode::Ceil.imm()
+  };
+  (Zero) => {
+    $crate::RoundMode::TowardZero.imm()
+  };
+}
+
+/// Makes a function that picks the best of several kernels at runtime.
+///
+/// Give the signature of the function, then each `Token => kernel` pair from
+/// the best to the worst, then an `else` kernel to use when none of them are
+/// available. Each kernel takes the token as its first argument (the `else`
+/// kernel doesn't), followed by the same arguments as the function. The token
+/// must be named by its type name alone (such as `Avx2Token`).
+///
+/// For each token this makes an `unsafe` function with
+/// `#[target_feature(enable = ..)]` for that token's feature, which calls the
+/// kernel, and it's only called once the token has been detected. Mark the
+/// kernels `#[inline]` (or `#[inline(always)]`) so that they're compiled into
+/// those functions: then the kernel and the token methods that it calls all
+/// use the feature, so they can inline together and the compiler can also
+/// vectorize the rest of the kernel's code with that feature. Write the
+/// kernels with the token methods (or the [`dynamic`] functions), since the
+/// `cfg` functions of this crate only exist for features that are on at
+/// compile time.
+///
+/// The first call checks the CPU and remembers which choice was the first one
+/// available, so every later call is just a load and a branch.
+/// ```
+/// # use safe_arch::*;
+/// #[inline(always)]
+/// fn sum_avx2(t: Avx2Token, data: &[i32]) -> i32 {
+///   let mut chunks = data.chunks_exact(8);
+///   let mut total = m256i::default();
+///   for chunk in &mut chunks {
+///     let mut lanes = [0_i32; 8];
+///     lanes.copy_from_slice(chunk);
+///     total = t.add_i32_m256i(total, m256i::from(lanes));
+///   }
+///   let lanes: [i32; 8] = total.into();
+///   lanes.iter().chain(chunks.remainder()).fold(0, |a, b| a.wrapping_add(*b))
+/// }
+///
+/// fn sum_scalar(data: &[i32]) -> i32 {
+///   data.iter().fold(0, |a, b| a.wrapping_add(*b))
+/// }
+///
+/// simd_dispatch! {
+///   /// Adds up all the values, wrapping on overflow.
+///   pub fn sum(data: &[i32]) -> i32;
+///   Avx2Token => sum_avx2,
+///   else => sum_scalar,
+/// }
+///
+/// let data: Vec<i32> = (1..=100).collect();
+/// assert_eq!(sum(&data), 5050);
+/// assert_eq!(sum(&data[..3]), 6);
+/// ```
+#[cfg(feature = "runtime")]
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "runtime")))]
+macro_rules! simd_dispatch {
+  // The index of the first available choice, counting from 1.
+  (@pick $idx:expr; else => $fallback:path $(,)?) => {
+    $idx
+  };
+  (@pick $idx:expr; $token:ident => $kernel:path, $($rest:tt)*) => {
+    if <$crate::$token>::detect().is_some() {
+      $idx
+    } else {
+      $crate::simd_dispatch!(@pick $idx + 1; $($rest)*)
+    }
+  };
+  // Calls the choice with index `$chosen`.
+  (@call $chosen:ident, $idx:expr, ($($arg:ident: $arg_ty:ty),*) -> $ret:ty; else => $fallback:path $(,)?) => {
+    $fallback($($arg),*)
+  };
+  (@call $chosen:ident, $idx:expr, ($($arg:ident: $arg_ty:ty),*) -> $ret:ty; $token:ident => $kernel:path, $($rest:tt)*) => {
+    if $chosen == $idx {
+      $crate::simd_dispatch!(@kernel $token, $kernel, ($($arg: $arg_ty),*) -> $ret);
+      // Safety: `$chosen` is only ever this index once this token has been
+      // detected, see below.
+      unsafe { with_feature($($arg),*) }
+    } else {
+      $crate::simd_dispatch!(@call $chosen, $idx + 1, ($($arg: $arg_ty),*) -> $ret; $($rest)*)
+    }
+  };
+  // `target_feature` needs a literal, so there's an arm for each token.
+  (@kernel SseToken, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "sse", SseToken, $($t)*);
+  };
+  (@kernel Sse2Token, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "sse2", Sse2Token, $($t)*);
+  };
+  (@kernel Sse3Token, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "sse3", Sse3Token, $($t)*);
+  };
+  (@kernel Ssse3Token, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "ssse3", Ssse3Token, $($t)*);
+  };
+  (@kernel Sse41Token, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "sse4.1", Sse41Token, $($t)*);
+  };
+  (@kernel Sse42Token, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "sse4.2", Sse42Token, $($t)*);
+  };
+  (@kernel AvxToken, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "avx", AvxToken, $($t)*);
+  };
+  (@kernel Avx2Token, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "avx2", Avx2Token, $($t)*);
+  };
+  (@kernel AdxToken, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "adx", AdxToken, $($t)*);
+  };
+  (@kernel AesToken, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "aes", AesToken, $($t)*);
+  };
+  (@kernel Bmi1Token, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "bmi1", Bmi1Token, $($t)*);
+  };
+  (@kernel Bmi2Token, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "bmi2", Bmi2Token, $($t)*);
+  };
+  (@kernel F16cToken, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "f16c", F16cToken, $($t)*);
+  };
+  (@kernel FmaToken, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "fma", FmaToken, $($t)*);
+  };
+  (@kernel LzcntToken, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "lzcnt", LzcntToken, $($t)*);
+  };
+  (@kernel PclmulqdqToken, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "pclmulqdq", PclmulqdqToken, $($t)*);
+  };
+  (@kernel PopcntToken, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "popcnt", PopcntToken, $($t)*);
+  };
+  (@kernel RdrandToken, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "rdrand", RdrandToken, $($t)*);
+  };
+  (@kernel RdseedToken, $($t:tt)*) => {
+    $crate::simd_dispatch!(@kernel_fn "rdseed", RdseedToken, $($t)*);
+  };
+  (@kernel_fn $feature:literal, $token:ident, $kernel:path, ($($arg:ident: $arg_ty:ty),*) -> $ret:ty) => {
+    #[inline]
+    #[target_feature(enable = $feature)]
+    unsafe fn with_feature($($arg: $arg_ty),*) -> $ret {
+      // Safety: the caller has detected the token.
+      #[allow(unused_unsafe)]
+      let token = unsafe { <$crate::$token>::new_unchecked() };
+      $kernel(token, $($arg),*)
+    }
+  };
+  (
+    $(#[$attr:meta])*
+    $v:vis fn $name:ident($($arg:ident: $arg_ty:ty),* $(,)?) -> $ret:ty;
+    $($choices:tt)*
+  ) => {
+    $(#[$attr])*
+    #[inline]
+    $v fn $name($($arg: $arg_ty),*) -> $ret {
+      use ::core::sync::atomic::{AtomicUsize, Ordering};
+      // 0 until the first call, then the index of the chosen kernel. An index
+      // is only stored after its token (or the tokens of all the choices
+      // before the `else` kernel) was checked, and the CPU doesn't change
+      // while the program runs, so every thread that races on the first call
+      // stores the same index. Nothing else is read through this, so
+      // `Relaxed` is enough.
+      static CHOSEN: AtomicUsize = AtomicUsize::new(0);
+      let mut chosen = CHOSEN.load(Ordering::Relaxed);
+      if chosen == 0 {
+        chosen = $crate::simd_dispatch!(@pick 1_usize; $($choices)*);
+        CHOSEN.store(chosen, Ordering::Relaxed);
+      }
+      $crate::simd_dispatch!(@call chosen, 1_usize, ($($arg: $arg_ty),*) -> $ret; $($choices)*)
+    }
+  };
+  (
+    $(#[$attr:meta])*
+    $v:vis fn $name:ident($($arg:ident: $arg_ty:ty),* $(,)?);
+    $($choices:tt)*
+  ) => {
+    $crate::simd_dispatch! {
+      $(#[$attr])*
+      $v fn $name($($arg: $arg_ty),*) -> ();
+      $($choices)*
+    }
+  };
+}
+
+/// Runs the best closure that's available, out of the ones that `features`
+/// allows.
+///
+/// Give a `CpuFeatures`, then each `Token => closure` pair from the best to
+/// the worst, then an `else` closure. A closure is picked if its token's
+/// feature is set in `features` *and* that token is detected, and it's called
+/// with the token. If none of them are picked the `else` closure is called
+/// with no arguments. All of the closures must return the same type.
+///
+/// Pass [`cpu_features`] to simply get the best one. Since the token is still
+/// detected, `features` can't turn on anything that the CPU doesn't have, but
+/// clearing fields of it is an easy way to test each fallback path, or to let
+/// users cap what a program uses.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([-1, 2, -3, 4]);
+/// let run = |features| {
+///   best_available!(features;
+///     Ssse3Token => |t: Ssse3Token| <[i32; 4]>::from(t.abs_i32_m128i(a)),
+///     else => || {
+///       let mut x: [i32; 4] = a.into();
+///       x.iter_mut().for_each(|x| *x = x.wrapping_abs());
+///       x
+///     },
+///   )
+/// };
+/// assert_eq!(run(cpu_features()), [1, 2, 3, 4]);
+/// assert_eq!(run(CpuFeatures::default()), [1, 2, 3, 4]);
+/// ```
+#[cfg(feature = "runtime")]
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "runtime")))]
+macro_rules! best_available {
+  ($features:expr; $($token:ty => $f:expr,)* else => $fallback:expr $(,)?) => {{
+    let features: $crate::CpuFeatures = $features;
+    'best: {
+      $(
+        if features.has(<$token>::FEATURE) == Some(true) {
+          if let Some(token) = <$token>::detect() {
+            break 'best ($f)(token);
+          }
+        }
+      )*
+      ($fallback)()
+    }
   }};
 }
 
+/// Runs a block with a feature's token if the CPU has that feature, or another
+/// block if it doesn't.
+///
+/// This is a lighter option than [`simd_dispatch!`] for small bits of code.
+/// The feature is named like the Cargo feature of its module (such as `sse4_1`
+/// or `avx2`), and the name in parentheses is bound to the token within the
+/// first block. Both blocks must have the same type.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([7_i32; 8]);
+/// let doubled: [i32; 8] = with_feature!(avx2(t) => {
+///   t.add_i32_m256i(a, a).into()
+/// } else {
+///   <[i32; 8]>::from(a).map(|x| x * 2)
+/// });
+/// assert_eq!(doubled, [14; 8]);
+/// ```
+#[cfg(feature = "runtime")]
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "runtime")))]
+macro_rules! with_feature {
+  (@token sse) => { $crate::SseToken };
+  (@token sse2) => { $crate::Sse2Token };
+  (@token sse3) => { $crate::Sse3Token };
+  (@token ssse3) => { $crate::Ssse3Token };
+  (@token sse4_1) => { $crate::Sse41Token };
+  (@token sse4_2) => { $crate::Sse42Token };
+  (@token avx) => { $crate::AvxToken };
+  (@token avx2) => { $crate::Avx2Token };
+  (@token adx) => { $crate::AdxToken };
+  (@token aes) => { $crate::AesToken };
+  (@token bmi1) => { $crate::Bmi1Token };
+  (@token bmi2) => { $crate::Bmi2Token };
+  (@token f16c) => { $crate::F16cToken };
+  (@token fma) => { $crate::FmaToken };
+  (@token lzcnt) => { $crate::LzcntToken };
+  (@token pclmulqdq) => { $crate::PclmulqdqToken };
+  (@token popcnt) => { $crate::PopcntToken };
+  (@token rdrand) => { $crate::RdrandToken };
+  (@token rdseed) => { $crate::RdseedToken };
+  ($feature:ident($t:ident) => $yes:block else $no:block) => {
+    match <$crate::with_feature!(@token $feature)>::detect() {
+      Some($t) => $yes,
+      None => $no,
+    }
+  };
+}
+
+/// Compile time check that an immediate value fits in the given number of bits.
+///
+/// Naming `ImmBits::<IMM, BITS>::OK` in a function body turns an out of range
+/// immediate into a compile error when that function is used, instead of the
+/// value being silently masked (or causing an LLVM error).
+#[allow(dead_code)]
+struct ImmBits<const IMM: i32, const BITS: u32>;
+#[allow(dead_code)]
+impl<const IMM: i32, const BITS: u32> ImmBits<IMM, BITS> {
+  const OK: () = assert!(IMM >= 0 && IMM < (1 << BITS), "immediate value out of range");
+}
+
+/// Builds the byte control for a `pshufb` style shuffle of `N` lanes.
+///
+/// The lanes are `16 / N` bytes each, so this can move `i16`, `i32`, or `i64`
+/// lanes as well as bytes. Output lane `i` comes from input lane `lanes[i]`,
+/// and an index of `N` or more zeroes that output lane instead. Turn the
+/// output into a register with `m128i::from` to use with
+/// `shuffle_av_i8z_all_m128i`. For `shuffle_av_i8z_half_m256i`, use it for
+/// both halves.
+/// ```
+/// # use safe_arch::*;
+/// // Reverse the `i16` lanes, and zero the last one.
+/// let control = lane_map_to_bytes([7, 6, 5, 4, 3, 2, 1, 8]);
+/// assert_eq!(control[..4], [14, 15, 12, 13]);
+/// assert_eq!(control[14..], [0x80, 0x80]);
+/// # #[cfg(all(target_feature = "ssse3", feature = "ssse3"))]
+/// # {
+/// let a = m128i::from([0_i16, 1, 2, 3, 4, 5, 6, 7]);
+/// let c: [i16; 8] = shuffle_av_i8z_all_m128i(a, m128i::from(control)).into();
+/// assert_eq!(c, [7, 6, 5, 4, 3, 2, 1, 0]);
+/// # }
+/// ```
+#[must_use]
+pub const fn lane_map_to_bytes<const N: usize>(lanes: [usize; N]) -> [u8; 16] {
+  assert!(N > 0 && N <= 16 && 16 % N == 0, "the lane count must divide 16");
+  let width = 16 / N;
+  let mut control = [0x80_u8; 16];
+  let mut i = 0;
+  while i < N {
+    if lanes[i] < N {
+      let mut b = 0;
+      while b < width {
+        control[i * width + b] = (lanes[i] * width + b) as u8;
+        b += 1;
+      }
+    }
+    i += 1;
+  }
+  control
+}
+
 /// Declares a private mod and then a glob `use` with the visibility specified.
 macro_rules! submodule {
   ($v:vis $name:ident) => {
@@ -209,53 +595,179 @@ submodule!(pub x86_x64 {
   submodule!(pub m256d_);
   submodule!(pub m256i_);
 
+  submodule!(pub cpuid);
+
+  pub mod prelude;
+
   // Note(Lokathor): We only include these sub-modules with the actual functions
   // if the feature is enabled. Ae *also* have a cfg attribute on the inside of
   // the modules as a "double-verification" of sorts. Technically either way on
-  // its own would also be fine.
+  // its own would also be fine. Each module also has a Cargo feature of the
+  // same name (all on by default), so that users can leave out modules they
+  // don't want to compile or see in the docs.
 
   // These CPU features follow a fairly clear and strict progression that's easy
   // to remember. Most of them offer a fair pile of new functions.
-  #[cfg(target_feature = "sse")]
+  #[cfg(all(target_feature = "sse", feature = "sse"))]
   submodule!(pub sse);
-  #[cfg(target_feature = "sse2")]
+  #[cfg(all(target_feature = "sse2", feature = "sse2"))]
   submodule!(pub sse2);
-  #[cfg(target_feature = "sse3")]
+  #[cfg(all(target_feature = "sse3", feature = "sse3"))]
   submodule!(pub sse3);
-  #[cfg(target_feature = "ssse3")]
+  #[cfg(all(target_feature = "ssse3", feature = "ssse3"))]
   submodule!(pub ssse3);
-  #[cfg(target_feature = "sse4.1")]
+  #[cfg(all(target_feature = "sse4.1", feature = "sse4_1"))]
   submodule!(pub sse4_1);
-  #[cfg(target_feature = "sse4.2")]
+  #[cfg(all(target_feature = "sse4.2", feature = "sse4_2"))]
   submodule!(pub sse4_2);
-  #[cfg(target_feature = "avx")]
+  #[cfg(all(target_feature = "avx", feature = "avx"))]
   submodule!(pub avx);
-  #[cfg(target_feature = "avx2")]
+  #[cfg(all(target_feature = "avx2", feature = "avx2"))]
   submodule!(pub avx2);
 
   // These features aren't as easy to remember the progression of and they each
   // only add a small handful of functions.
-  #[cfg(target_feature = "adx")]
+  #[cfg(all(target_feature = "adx", feature = "adx"))]
   submodule!(pub adx);
-  #[cfg(target_feature = "aes")]
+  #[cfg(all(target_feature = "aes", feature = "aes"))]
   submodule!(pub aes);
-  #[cfg(target_feature = "bmi1")]
+  #[cfg(all(target_feature = "bmi1", feature = "bmi1"))]
   submodule!(pub bmi1);
-  #[cfg(target_feature = "bmi2")]
+  #[cfg(all(target_feature = "bmi2", feature = "bmi2"))]
   submodule!(pub bmi2);
-  #[cfg(target_feature = "fma")]
+  #[cfg(all(target_feature = "f16c", feature = "f16c"))]
+  submodule!(pub f16c);
+  #[cfg(all(target_feature = "fma", feature = "fma"))]
   submodule!(pub fma);
-  #[cfg(target_feature = "lzcnt")]
+  #[cfg(all(target_feature = "lzcnt", feature = "lzcnt"))]
   submodule!(pub lzcnt);
-  #[cfg(target_feature = "pclmulqdq")]
+  #[cfg(all(target_feature = "pclmulqdq", feature = "pclmulqdq"))]
   submodule!(pub pclmulqdq);
-  #[cfg(target_feature = "popcnt")]
+  #[cfg(all(target_feature = "popcnt", feature = "popcnt"))]
   submodule!(pub popcnt);
-  #[cfg(target_feature = "rdrand")]
+  #[cfg(all(target_feature = "rdrand", feature = "rdrand"))]
   submodule!(pub rdrand);
-  #[cfg(target_feature = "rdseed")]
+  #[cfg(all(target_feature = "rdseed", feature = "rdseed"))]
   submodule!(pub rdseed);
 
+  // This one isn't a CPU feature, it's extra functions built from the `avx2`
+  // module, so it's off by default.
+  #[cfg(all(target_feature = "avx2", feature = "math"))]
+  submodule!(pub math);
+
+  // Generic access to the register types, with each impl gated on the module
+  // that its functions come from.
+  submodule!(pub simd_vector);
+
+  // This one also isn't gated on a target feature, because the whole point is
+  // to check for features at runtime.
+  #[cfg(feature = "runtime")]
+  submodule!(pub runtime);
+  #[cfg(feature = "runtime")]
+  pub mod dynamic;
+
+  /// The modules that are in this build, by their Cargo feature names.
+  ///
+  /// A module is built when both its Cargo feature and its target feature are
+  /// enabled, so this is the easy way to report (or check) what a build has
+  /// without repeating those `cfg` conditions.
+  /// ```
+  /// # use safe_arch::*;
+  /// let modules = compiled_modules();
+  /// assert_eq!(modules.contains(&"avx2"), cfg!(all(target_feature = "avx2", feature = "avx2")));
+  /// assert!(compiled_with("sse4_1") == modules.contains(&"sse4_1"));
+  /// ```
+  #[must_use]
+  pub const fn compiled_modules() -> &'static [&'static str] {
+    &[
+      #[cfg(all(target_feature = "sse", feature = "sse"))]
+      "sse",
+      #[cfg(all(target_feature = "sse2", feature = "sse2"))]
+      "sse2",
+      #[cfg(all(target_feature = "sse3", feature = "sse3"))]
+      "sse3",
+      #[cfg(all(target_feature = "ssse3", feature = "ssse3"))]
+      "ssse3",
+      #[cfg(all(target_feature = "sse4.1", feature = "sse4_1"))]
+      "sse4_1",
+      #[cfg(all(target_feature = "sse4.2", feature = "sse4_2"))]
+      "sse4_2",
+      #[cfg(all(target_feature = "avx", feature = "avx"))]
+      "avx",
+      #[cfg(all(target_feature = "avx2", feature = "avx2"))]
+      "avx2",
+      #[cfg(all(target_feature = "adx", feature = "adx"))]
+      "adx",
+      #[cfg(all(target_feature = "aes", feature = "aes"))]
+      "aes",
+      #[cfg(all(target_feature = "bmi1", feature = "bmi1"))]
+      "bmi1",
+      #[cfg(all(target_feature = "bmi2", feature = "bmi2"))]
+      "bmi2",
+      #[cfg(all(target_feature = "f16c", feature = "f16c"))]
+      "f16c",
+      #[cfg(all(target_feature = "fma", feature = "fma"))]
+      "fma",
+      #[cfg(all(target_feature = "lzcnt", feature = "lzcnt"))]
+      "lzcnt",
+      #[cfg(all(target_feature = "pclmulqdq", feature = "pclmulqdq"))]
+      "pclmulqdq",
+      #[cfg(all(target_feature = "popcnt", feature = "popcnt"))]
+      "popcnt",
+      #[cfg(all(target_feature = "rdrand", feature = "rdrand"))]
+      "rdrand",
+      #[cfg(all(target_feature = "rdseed", feature = "rdseed"))]
+      "rdseed",
+      #[cfg(all(target_feature = "avx2", feature = "math"))]
+      "math",
+      #[cfg(feature = "runtime")]
+      "runtime",
+    ]
+  }
+
+  /// If the module with this Cargo feature name is in this build.
+  ///
+  /// See [`compiled_modules`].
+  #[must_use]
+  pub fn compiled_with(module: &str) -> bool {
+    compiled_modules().contains(&module)
+  }
+
+  /// A one line summary of the build's target features and the modules that
+  /// they turned on, for logs and bug reports.
+  ///
+  /// It displays like `target features: sse sse2 ...; modules: sse sse2 ...`.
+  /// ```
+  /// # use safe_arch::*;
+  /// let summary = target_feature_summary().to_string();
+  /// assert!(summary.starts_with("target features: "));
+  /// let (features, _modules) = summary.split_once(';').unwrap();
+  /// let features: Vec<&str> = features.split_whitespace().collect();
+  /// assert_eq!(features.contains(&"sse2"), cfg!(target_feature = "sse2"));
+  /// ```
+  #[must_use]
+  pub fn target_feature_summary() -> TargetFeatureSummary {
+    TargetFeatureSummary
+  }
+
+  /// The [`Display`](core::fmt::Display) type of [`target_feature_summary`].
+  #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+  pub struct TargetFeatureSummary;
+
+  impl core::fmt::Display for TargetFeatureSummary {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+      f.write_str("target features:")?;
+      for (name, _) in COMPILED_FEATURES.as_list().iter().filter(|(_, b)| *b) {
+        write!(f, " {}", name)?;
+      }
+      f.write_str("; modules:")?;
+      for module in compiled_modules() {
+        write!(f, " {}", module)?;
+      }
+      Ok(())
+    }
+  }
+
   /// Reads the CPU's timestamp counter value.
   ///
   /// This is a monotonically increasing time-stamp that goes up every clock
@@ -300,6 +812,8 @@ submodule!(pub x86_x64 {
   /// * **Intrinsic:** `_bswap`
   /// * **Assembly:** `bswap r32`
   pub fn byte_swap_i32(i: i32) -> i32 {
+    // Note: newer compilers consider this intrinsic safe to call.
+    #[allow(unused_unsafe)]
     unsafe { _bswap(i) }
   }
 
@@ -309,10 +823,75 @@ submodule!(pub x86_x64 {
   /// # use safe_arch::*;
   /// assert_eq!(byte_swap_i64(0x0A123456_789ABC01), 0x01BC9A78_5634120A);
   /// ```
+  ///
+  /// On 32-bit x86 this is done as two 32-bit swaps.
+  ///
   /// * **Intrinsic:** `_bswap64`
   /// * **Assembly:** `bswap r64`
-  #[cfg(target_arch="x86_64")]
   pub fn byte_swap_i64(i: i64) -> i64 {
-    unsafe { _bswap64(i) }
+    #[cfg(target_arch="x86_64")]
+    {
+      #[allow(unused_unsafe)]
+      unsafe { _bswap64(i) }
+    }
+    #[cfg(not(target_arch="x86_64"))]
+    {
+      let low = byte_swap_i32(i as i32) as u32;
+      let high = byte_swap_i32((i >> 32) as i32) as u32;
+      ((u64::from(low) << 32) | u64::from(high)) as i64
+    }
+  }
+
+  /// Builds a shuffle control value from four 2-bit lane indexes.
+  ///
+  /// The indexes are given in output lane order, so `i0` is the source lane
+  /// for output lane 0 and so on. This is for the `shuffle` and `permute`
+  /// functions that pick each of four lanes with two bits (such as
+  /// [`shuffle_ai_f32_all_m128i`]). With the two input shuffles such as
+  /// `shuffle_abi_f32_all_m128`, output lanes 0 and 1 pick from `a`, and
+  /// output lanes 2 and 3 pick from `b`. When used in a const context an index
+  /// of 4 or more is a compile error.
+  /// ```
+  /// # use safe_arch::*;
+  /// assert_eq!(shuffle_control_2bit(0, 2, 2, 1), 0b01_10_10_00);
+  /// # #[cfg(all(target_feature = "sse2", feature = "sse2"))]
+  /// # {
+  /// let a = m128i::from([6, 7, 8, 9]);
+  /// let c = shuffle_ai_f32_all_m128i::<{ shuffle_control_2bit(0, 2, 2, 1) }>(a);
+  /// assert_eq!(<[i32; 4]>::from(c), [6, 8, 8, 7]);
+  /// # }
+  /// ```
+  #[must_use]
+  #[inline(always)]
+  pub const fn shuffle_control_2bit(i0: i32, i1: i32, i2: i32, i3: i32) -> i32 {
+    assert!(i0 >= 0 && i0 < 4 && i1 >= 0 && i1 < 4);
+    assert!(i2 >= 0 && i2 < 4 && i3 >= 0 && i3 < 4);
+    i0 | (i1 << 2) | (i2 << 4) | (i3 << 6)
+  }
+
+  /// Builds a shuffle control value from four 1-bit lane selections.
+  ///
+  /// The selections are given in output lane order, so `i0` is the selection
+  /// for output lane 0 and so on. This is for the `f64` shuffle and permute
+  /// functions that pick each lane with one bit (such as
+  /// [`shuffle_abi_f64_all_m128d`], which only uses `i0` and `i1`). When used
+  /// in a const context a selection other than 0 or 1 is a compile error.
+  /// ```
+  /// # use safe_arch::*;
+  /// assert_eq!(shuffle_control_1bit(0, 1, 0, 0), 0b10);
+  /// # #[cfg(all(target_feature = "sse2", feature = "sse2"))]
+  /// # {
+  /// let a = m128d::from_array([1.0, 2.0]);
+  /// let b = m128d::from_array([3.0, 4.0]);
+  /// let c = shuffle_abi_f64_all_m128d::<{ shuffle_control_1bit(0, 1, 0, 0) }>(a, b);
+  /// assert_eq!(c.to_array(), [1.0, 4.0]);
+  /// # }
+  /// ```
+  #[must_use]
+  #[inline(always)]
+  pub const fn shuffle_control_1bit(i0: i32, i1: i32, i2: i32, i3: i32) -> i32 {
+    assert!(i0 >= 0 && i0 < 2 && i1 >= 0 && i1 < 2);
+    assert!(i2 >= 0 && i2 < 2 && i3 >= 0 && i3 < 2);
+    i0 | (i1 << 1) | (i2 << 2) | (i3 << 3)
   }
 });
@@ -151,8 +151,11 @@
 //!   all shuffle.
 //!   * `shuffle_{args}_{lane-type}_{lane-sources}_{simd-type}`.
 //!   * "args" is the input arguments: `a` (one arg) or `ab` (two args), then
-//!     either `v` (runtime-varying) or `i` (immediate). All the immediate
-//!     shuffles are macros, of course.
+//!     either `v` (runtime-varying) or `i` (immediate). The immediate
+//!     shuffles take their control as a const generic, and
+//!     [`shuffle_control_2bit`](crate::shuffle_control_2bit) and
+//!     [`shuffle_control_1bit`](crate::shuffle_control_1bit) can build it from
+//!     the lane indexes.
 //!   * "lane type" is `f32`, `f64`, `i8`, etc. If there's a `z` after the type
 //!     then you'll also be able to zero an output position instead of making it
 //!     come from a particular source lane.
@@ -34,3 +34,39 @@ pub fn add_carry_u32(c_in: u8, a: u32, b: u32, out: &mut u32) -> u8 {
 pub fn add_carry_u64(c_in: u8, a: u64, b: u64, out: &mut u64) -> u8 {
   unsafe { _addcarryx_u64(c_in, a, b, out) }
 }
+
+/// Adds a multi-word number into another, as in `acc += addend`.
+///
+/// Both slices are little-endian words (index 0 is the lowest word). The carry
+/// moves through the rest of `acc` past the end of `addend`, and the carry out
+/// of the top word is returned.
+///
+/// Panics if `addend` is longer than `acc`.
+/// ```
+/// # use safe_arch::*;
+/// let mut acc = [u64::MAX, u64::MAX, 7];
+/// assert_eq!(add_carry_u64_slices(&mut acc, &[1]), 0);
+/// assert_eq!(acc, [0, 0, 8]);
+///
+/// let mut acc = [u64::MAX, u64::MAX];
+/// assert_eq!(add_carry_u64_slices(&mut acc, &[2, 0]), 1);
+/// assert_eq!(acc, [1, 0]);
+/// ```
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "adx")))]
+#[cfg(target_arch = "x86_64")]
+pub fn add_carry_u64_slices(acc: &mut [u64], addend: &[u64]) -> u8 {
+  assert!(addend.len() <= acc.len(), "addend is longer than acc");
+  let (low, high) = acc.split_at_mut(addend.len());
+  let mut c = 0;
+  for (a, b) in low.iter_mut().zip(addend.iter()) {
+    c = add_carry_u64(c, *a, *b, a);
+  }
+  for a in high.iter_mut() {
+    if c == 0 {
+      break;
+    }
+    c = add_carry_u64(c, *a, 0, a);
+  }
+  c
+}
@@ -4,6 +4,11 @@ use super::*;
 
 /// Perform one round of an AES decryption flow on `a` using the `round_key`.
 ///
+/// This is a round of the "equivalent inverse cipher", so the round keys
+/// aren't just the encryption keys backwards: all but the first and last also
+/// need [`aes_inv_mix_columns_m128i`]. Use [`aes_inverse_round_keys`] to get
+/// them.
+///
 /// * **Intrinsic:** [`_mm_aesdec_si128`]
 /// * **Assembly:** `aesdec xmm, xmm`
 #[must_use]
@@ -71,6 +76,302 @@ pub fn aes_inv_mix_columns_m128i(a: m128i) -> m128i {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "aes")))]
 pub fn aes_key_gen_assist_m128i<const IMM: i32>(a: m128i) -> m128i {
+  let () = ImmBits::<IMM, 8>::OK;
   m128i(unsafe { _mm_aeskeygenassist_si128(a.0, IMM) })
 }
 
+/// `a ^ (a << 32) ^ (a << 64) ^ (a << 96)`, the prefix xor of the `u32` lanes
+/// that every key expansion step needs.
+#[inline(always)]
+fn aes_key_spread(a: m128i) -> m128i {
+  unsafe {
+    let a = _mm_xor_si128(a.0, _mm_slli_si128::<4>(a.0));
+    m128i(_mm_xor_si128(a, _mm_slli_si128::<8>(a)))
+  }
+}
+
+/// One AES-128 key expansion step, using the round constant `RCON`.
+#[inline(always)]
+fn aes_key_step_128<const RCON: i32>(key: m128i) -> m128i {
+  let assist = aes_key_gen_assist_m128i::<RCON>(key);
+  unsafe { m128i(_mm_xor_si128(aes_key_spread(key).0, _mm_shuffle_epi32::<0xFF>(assist.0))) }
+}
+
+/// Expands an AES-128 cipher key into the 11 round keys of the encryption
+/// flow.
+///
+/// Key `0` is the cipher key itself. To encrypt a block, xor it with key `0`,
+/// then use [`aes_encrypt_m128i`] with keys `1..=9`, and finish with
+/// [`aes_encrypt_last_m128i`] using key `10`.
+/// ```
+/// # use safe_arch::*;
+/// // FIPS-197, Appendix C.1
+/// let k = aes_key_expand_128([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+/// let plain = m128i::from(0xFFEEDDCCBBAA99887766554433221100_u128);
+/// let mut c = plain ^ k[0];
+/// for round_key in &k[1..10] {
+///   c = aes_encrypt_m128i(c, *round_key);
+/// }
+/// c = aes_encrypt_last_m128i(c, k[10]);
+/// assert_eq!(u128::from(c), 0x5AC5B47080B7CDD830047B6AD8E0C469);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "aes")))]
+pub fn aes_key_expand_128(key: [u8; 16]) -> [m128i; 11] {
+  let k0 = m128i::from(key);
+  let k1 = aes_key_step_128::<0x01>(k0);
+  let k2 = aes_key_step_128::<0x02>(k1);
+  let k3 = aes_key_step_128::<0x04>(k2);
+  let k4 = aes_key_step_128::<0x08>(k3);
+  let k5 = aes_key_step_128::<0x10>(k4);
+  let k6 = aes_key_step_128::<0x20>(k5);
+  let k7 = aes_key_step_128::<0x40>(k6);
+  let k8 = aes_key_step_128::<0x80>(k7);
+  let k9 = aes_key_step_128::<0x1B>(k8);
+  let k10 = aes_key_step_128::<0x36>(k9);
+  [k0, k1, k2, k3, k4, k5, k6, k7, k8, k9, k10]
+}
+
+/// One AES-192 key expansion step, using the round constant `RCON`.
+///
+/// The key state is six words: four in `a`, and two in the low lanes of `b`.
+/// The upper lanes of `b` are garbage.
+#[inline(always)]
+fn aes_key_step_192<const RCON: i32>(a: m128i, b: m128i) -> (m128i, m128i) {
+  let assist = aes_key_gen_assist_m128i::<RCON>(b);
+  unsafe {
+    let a = _mm_xor_si128(aes_key_spread(a).0, _mm_shuffle_epi32::<0x55>(assist.0));
+    let b = _mm_xor_si128(_mm_xor_si128(b.0, _mm_slli_si128::<4>(b.0)), _mm_shuffle_epi32::<0xFF>(a));
+    (m128i(a), m128i(b))
+  }
+}
+
+/// Expands an AES-192 cipher key into the 13 round keys of the encryption
+/// flow.
+///
+/// This works like [`aes_key_expand_128`], but with keys `1..=11` for the
+/// [`aes_encrypt_m128i`] rounds and key `12` for the last round.
+/// ```
+/// # use safe_arch::*;
+/// // FIPS-197, Appendix C.2
+/// let mut key = [0_u8; 24];
+/// key.iter_mut().enumerate().for_each(|(i, k)| *k = i as u8);
+/// let k = aes_key_expand_192(key);
+/// let plain = m128i::from(0xFFEEDDCCBBAA99887766554433221100_u128);
+/// let mut c = plain ^ k[0];
+/// for round_key in &k[1..12] {
+///   c = aes_encrypt_m128i(c, *round_key);
+/// }
+/// c = aes_encrypt_last_m128i(c, k[12]);
+/// assert_eq!(u128::from(c), 0x91710DECA070AF6EE0DF4C86A47CA9DD);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "aes")))]
+pub fn aes_key_expand_192(key: [u8; 24]) -> [m128i; 13] {
+  // The schedule steps six words at a time, which don't line up with the four
+  // word round keys, so collect the words first.
+  let mut words = [0_u32; 54];
+  for (w, bytes) in words.iter_mut().zip(key.chunks_exact(4)) {
+    *w = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+  }
+  let mut a = m128i::from([words[0], words[1], words[2], words[3]]);
+  let mut b = m128i::from([words[4], words[5], 0, 0]);
+  let mut store = |i: usize, a: m128i, b: m128i| {
+    let a: [u32; 4] = a.into();
+    let b: [u32; 4] = b.into();
+    words[i * 6..i * 6 + 4].copy_from_slice(&a);
+    words[i * 6 + 4..i * 6 + 6].copy_from_slice(&b[..2]);
+  };
+  (a, b) = aes_key_step_192::<0x01>(a, b);
+  store(1, a, b);
+  (a, b) = aes_key_step_192::<0x02>(a, b);
+  store(2, a, b);
+  (a, b) = aes_key_step_192::<0x04>(a, b);
+  store(3, a, b);
+  (a, b) = aes_key_step_192::<0x08>(a, b);
+  store(4, a, b);
+  (a, b) = aes_key_step_192::<0x10>(a, b);
+  store(5, a, b);
+  (a, b) = aes_key_step_192::<0x20>(a, b);
+  store(6, a, b);
+  (a, b) = aes_key_step_192::<0x40>(a, b);
+  store(7, a, b);
+  (a, b) = aes_key_step_192::<0x80>(a, b);
+  store(8, a, b);
+
+  let mut out = [m128i::default(); 13];
+  for (k, w) in out.iter_mut().zip(words.chunks_exact(4)) {
+    *k = m128i::from([w[0], w[1], w[2], w[3]]);
+  }
+  out
+}
+
+/// One AES-256 key expansion step, using the round constant `RCON`.
+///
+/// This gives the next two round keys, from the two before them.
+#[inline(always)]
+fn aes_key_step_256<const RCON: i32>(a: m128i, b: m128i) -> (m128i, m128i) {
+  let assist = aes_key_gen_assist_m128i::<RCON>(b);
+  let a = unsafe { m128i(_mm_xor_si128(aes_key_spread(a).0, _mm_shuffle_epi32::<0xFF>(assist.0))) };
+  // The odd keys take `SubWord` without the rotate or round constant.
+  let assist = aes_key_gen_assist_m128i::<0>(a);
+  let b = unsafe { m128i(_mm_xor_si128(aes_key_spread(b).0, _mm_shuffle_epi32::<0xAA>(assist.0))) };
+  (a, b)
+}
+
+/// Expands an AES-256 cipher key into the 15 round keys of the encryption
+/// flow.
+///
+/// This works like [`aes_key_expand_128`], but with keys `1..=13` for the
+/// [`aes_encrypt_m128i`] rounds and key `14` for the last round.
+/// ```
+/// # use safe_arch::*;
+/// // FIPS-197, Appendix C.3
+/// let mut key = [0_u8; 32];
+/// key.iter_mut().enumerate().for_each(|(i, k)| *k = i as u8);
+/// let k = aes_key_expand_256(key);
+/// let plain = m128i::from(0xFFEEDDCCBBAA99887766554433221100_u128);
+/// let mut c = plain ^ k[0];
+/// for round_key in &k[1..14] {
+///   c = aes_encrypt_m128i(c, *round_key);
+/// }
+/// c = aes_encrypt_last_m128i(c, k[14]);
+/// assert_eq!(u128::from(c), 0x8960494B9049FCEABF456751CAB7A28E);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "aes")))]
+pub fn aes_key_expand_256(key: [u8; 32]) -> [m128i; 15] {
+  let mut low = [0_u8; 16];
+  let mut high = [0_u8; 16];
+  low.copy_from_slice(&key[..16]);
+  high.copy_from_slice(&key[16..]);
+  let k0 = m128i::from(low);
+  let k1 = m128i::from(high);
+  let (k2, k3) = aes_key_step_256::<0x01>(k0, k1);
+  let (k4, k5) = aes_key_step_256::<0x02>(k2, k3);
+  let (k6, k7) = aes_key_step_256::<0x04>(k4, k5);
+  let (k8, k9) = aes_key_step_256::<0x08>(k6, k7);
+  let (k10, k11) = aes_key_step_256::<0x10>(k8, k9);
+  let (k12, k13) = aes_key_step_256::<0x20>(k10, k11);
+  let (k14, _) = aes_key_step_256::<0x40>(k12, k13);
+  [k0, k1, k2, k3, k4, k5, k6, k7, k8, k9, k10, k11, k12, k13, k14]
+}
+
+/// Turns encryption round keys into the round keys of the decryption flow.
+///
+/// This is the key schedule of the "equivalent inverse cipher": the keys in
+/// the opposite order, with [`aes_inv_mix_columns_m128i`] applied to all but
+/// the first and last. To decrypt a block, xor it with key `0` of the output,
+/// then use [`aes_decrypt_m128i`] with the middle keys, and finish with
+/// [`aes_decrypt_last_m128i`] using the last key.
+/// ```
+/// # use safe_arch::*;
+/// // FIPS-197, Appendix C.1
+/// let k = aes_inverse_round_keys(aes_key_expand_128([
+///   0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+/// ]));
+/// let cipher = m128i::from(0x5AC5B47080B7CDD830047B6AD8E0C469_u128);
+/// let mut p = cipher ^ k[0];
+/// for round_key in &k[1..10] {
+///   p = aes_decrypt_m128i(p, *round_key);
+/// }
+/// p = aes_decrypt_last_m128i(p, k[10]);
+/// assert_eq!(u128::from(p), 0xFFEEDDCCBBAA99887766554433221100);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "aes")))]
+pub fn aes_inverse_round_keys<const N: usize>(round_keys: [m128i; N]) -> [m128i; N] {
+  let mut out = round_keys;
+  out.reverse();
+  if N > 2 {
+    for k in out[1..N - 1].iter_mut() {
+      *k = aes_inv_mix_columns_m128i(*k);
+    }
+  }
+  out
+}
+
+/// Encrypts each block in place with the round keys, as in ECB mode.
+///
+/// `round_keys` is the output of one of the `aes_key_expand` functions (or any
+/// other slice of at least 2 keys). The blocks are independent of each other,
+/// so they're encrypted 8 at a time, which keeps the AES unit busy instead of
+/// waiting on each round in turn.
+///
+/// Panics if `round_keys` has fewer than 2 keys.
+/// ```
+/// # use safe_arch::*;
+/// let k = aes_key_expand_128([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+/// let mut blocks = [m128i::from(0xFFEEDDCCBBAA99887766554433221100_u128); 3];
+/// aes_ecb_encrypt_blocks(&k, &mut blocks);
+/// for b in blocks.iter() {
+///   assert_eq!(u128::from(*b), 0x5AC5B47080B7CDD830047B6AD8E0C469);
+/// }
+/// ```
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "aes")))]
+pub fn aes_ecb_encrypt_blocks(round_keys: &[m128i], blocks: &mut [m128i]) {
+  assert!(round_keys.len() >= 2, "AES needs at least 2 round keys");
+  let first = round_keys[0];
+  let last = round_keys[round_keys.len() - 1];
+  let middle = &round_keys[1..round_keys.len() - 1];
+  let whiten = |a: m128i| m128i(unsafe { _mm_xor_si128(a.0, first.0) });
+
+  let mut chunks = blocks.chunks_exact_mut(8);
+  for chunk in &mut chunks {
+    let mut b = [m128i::default(); 8];
+    for (b, c) in b.iter_mut().zip(chunk.iter()) {
+      *b = whiten(*c);
+    }
+    for k in middle {
+      for b in b.iter_mut() {
+        *b = aes_encrypt_m128i(*b, *k);
+      }
+    }
+    for (b, c) in b.iter().zip(chunk.iter_mut()) {
+      *c = aes_encrypt_last_m128i(*b, last);
+    }
+  }
+  for c in chunks.into_remainder() {
+    let mut b = whiten(*c);
+    for k in middle {
+      b = aes_encrypt_m128i(b, *k);
+    }
+    *c = aes_encrypt_last_m128i(b, last);
+  }
+}
+
+/// Fills `out` with CTR mode keystream blocks, and gives the next counter.
+///
+/// Block `i` is the encryption of `counter + i` (wrapping), stored as a big
+/// endian 128-bit number, which is the layout of NIST SP 800-38A. Xor the
+/// keystream into the data to encrypt or decrypt it. The blocks are encrypted
+/// with [`aes_ecb_encrypt_blocks`], so `round_keys` works the same way.
+///
+/// Panics if `round_keys` has fewer than 2 keys.
+/// ```
+/// # use safe_arch::*;
+/// let k = aes_key_expand_128([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+/// let mut stream = [m128i::default(); 5];
+/// let next = aes_ctr_keystream(&k, 7, &mut stream);
+/// assert_eq!(next, 12);
+/// let mut expected = [m128i::from(9_u128.to_be_bytes())];
+/// aes_ecb_encrypt_blocks(&k, &mut expected);
+/// assert_eq!(<[u8; 16]>::from(stream[2]), <[u8; 16]>::from(expected[0]));
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "aes")))]
+pub fn aes_ctr_keystream(round_keys: &[m128i], counter: u128, out: &mut [m128i]) -> u128 {
+  let mut next = counter;
+  for block in out.iter_mut() {
+    *block = m128i::from(next.to_be_bytes());
+    next = next.wrapping_add(1);
+  }
+  aes_ecb_encrypt_blocks(round_keys, out);
+  next
+}
@@ -92,29 +92,31 @@ pub fn bitandnot_m256(a: m256, b: m256) -> m256 {
 
 /// Blends the `f64` lanes according to the immediate mask.
 ///
-/// Each bit 0 though 3 controls output lane 0 through 3. Use 0 for the `a`
+/// Each bit 0 through 3 controls output lane 0 through 3. Use 0 for the `a`
 /// value and 1 for the `b` value.
 ///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// * **Intrinsic:** [`_mm256_blend_pd`]
+/// * **Assembly:** `vblendpd ymm, ymm, ymm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn blend_m256d<const IMM: i32>(a: m256d, b: m256d) -> m256d {
+  let () = ImmBits::<IMM, 4>::OK;
   m256d(unsafe { _mm256_blend_pd(a.0, b.0, IMM) })
 }
 
 /// Blends the `f32` lanes according to the immediate mask.
 ///
-/// Each bit 0 though 7 controls lane 0 through 7. Use 0 for the `$a` value and
-/// 1 for the `$b` value.
+/// Each bit 0 through 7 controls output lane 0 through 7. Use 0 for the `a`
+/// value and 1 for the `b` value.
 ///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// * **Intrinsic:** [`_mm256_blend_ps`]
+/// * **Assembly:** `vblendps ymm, ymm, ymm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn blend_m256<const IMM: i32>(a: m256, b: m256) -> m256 {
+  let () = ImmBits::<IMM, 8>::OK;
   m256(unsafe { _mm256_blend_ps(a.0, b.0, IMM) })
 }
 
@@ -146,6 +148,34 @@ pub fn blend_varying_m256(a: m256, b: m256, mask: m256) -> m256 {
   m256(unsafe { _mm256_blendv_ps(a.0, b.0, mask.0) })
 }
 
+/// Lanewise select: lanes of `if_true` where `mask` is set, otherwise the lanes
+/// of `if_false`.
+///
+/// Each lane of `mask` should be all 1s (true) or all 0s (false), as the
+/// `cmp_*_mask` functions give.
+///
+/// This is [`blend_varying_m256d`] with the arguments in `select` order.
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn select_m256d(mask: m256d, if_true: m256d, if_false: m256d) -> m256d {
+  blend_varying_m256d(if_false, if_true, mask)
+}
+
+/// Lanewise select: lanes of `if_true` where `mask` is set, otherwise the lanes
+/// of `if_false`.
+///
+/// Each lane of `mask` should be all 1s (true) or all 0s (false), as the
+/// `cmp_*_mask` functions give.
+///
+/// This is [`blend_varying_m256`] with the arguments in `select` order.
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn select_m256(mask: m256, if_true: m256, if_false: m256) -> m256 {
+  blend_varying_m256(if_false, if_true, mask)
+}
+
 /// Load an `m128d` and splat it to the lower and upper half of an `m256d`
 ///
 /// * **Intrinsic:** [``]
@@ -190,6 +220,59 @@ pub fn load_f32_splat_m256(a: &f32) -> m256 {
   m256(unsafe { _mm256_broadcast_ss(a) })
 }
 
+/// Load `a[index]` and splat it to all lanes of an `m256`.
+///
+/// Panics if `index` is out of bounds.
+/// ```
+/// # use safe_arch::*;
+/// let row = [1.0_f32, 2.0, 3.0, 4.0];
+/// assert_eq!(load_splat_from_slice_m256(&row, 2).to_array(), [3.0; 8]);
+/// ```
+/// * **Intrinsic:** [`_mm256_broadcast_ss`]
+/// * **Assembly:** `vbroadcastss ymm, m32`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn load_splat_from_slice_m256(a: &[f32], index: usize) -> m256 {
+  load_f32_splat_m256(&a[index])
+}
+
+/// Load `a[index]` and splat it to all lanes of an `m256d`.
+///
+/// Panics if `index` is out of bounds.
+/// ```
+/// # use safe_arch::*;
+/// let row = [1.0_f64, 2.0, 3.0, 4.0];
+/// assert_eq!(load_splat_from_slice_m256d(&row, 3).to_array(), [4.0; 4]);
+/// ```
+/// * **Intrinsic:** [`_mm256_broadcast_sd`]
+/// * **Assembly:** `vbroadcastsd ymm, m64`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn load_splat_from_slice_m256d(a: &[f64], index: usize) -> m256d {
+  load_f64_splat_m256d(&a[index])
+}
+
+/// Load `a[index]` and splat it to all `i32` lanes of an `m256i`.
+///
+/// Panics if `index` is out of bounds.
+/// ```
+/// # use safe_arch::*;
+/// let row = [1_i32, -2, 3, 4];
+/// let b: [i32; 8] = load_splat_from_slice_m256i(&row, 1).into();
+/// assert_eq!(b, [-2; 8]);
+/// ```
+/// * **Intrinsic:** [`_mm256_broadcast_ss`]
+/// * **Assembly:** `vbroadcastss ymm, m32`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn load_splat_from_slice_m256i(a: &[i32], index: usize) -> m256i {
+  let f: &f32 = unsafe { &*(&a[index] as *const i32 as *const f32) };
+  cast_to_m256i_from_m256(load_f32_splat_m256(f))
+}
+
 /// Bit-preserving cast to `m256` from `m256d`.
 ///
 /// * **Intrinsic:** [``]
@@ -312,129 +395,80 @@ pub fn ceil_m256(a: m256) -> m256 {
 }
 
 /// Turns a comparison operator token to the correct constant value.
+///
+/// This is a thin shim over [`CmpOp`]: `cmp_op!(Name)` is the same as
+/// `CmpOp::Name.imm()`, which you should use instead.
 #[macro_export]
+#[deprecated(since = "0.8.0", note = "use `CmpOp::Name.imm()` instead of `cmp_op!(Name)`")]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 macro_rules! cmp_op {
-  (EqualOrdered) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::_CMP_EQ_OQ;
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::_CMP_EQ_OQ;
-    _CMP_EQ_OQ
-  }};
-  (EqualUnordered) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::_CMP_EQ_UQ;
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::_CMP_EQ_UQ;
-    _CMP_EQ_UQ
-  }};
-  (False) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::_CMP_FALSE_OQ;
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::_CMP_FALSE_OQ;
-    _CMP_FALSE_OQ
-  }};
-  (GreaterEqualOrdered) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::_CMP_GE_OQ;
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::_CMP_GE_OQ;
-    _CMP_GE_OQ
-  }};
-  (GreaterThanOrdered) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::_CMP_GT_OQ;
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::_CMP_GT_OQ;
-    _CMP_GT_OQ
-  }};
-  (LessEqualOrdered) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::_CMP_LE_OQ;
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::_CMP_LE_OQ;
-    _CMP_LE_OQ
-  }};
-  (LessThanOrdered) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::_CMP_LT_OQ;
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::_CMP_LT_OQ;
-    _CMP_LT_OQ
-  }};
-  (NotEqualOrdered) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::_CMP_NEQ_OQ;
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::_CMP_NEQ_OQ;
-    _CMP_NEQ_OQ
-  }};
-  (NotEqualUnordered) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::_CMP_NEQ_UQ;
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::_CMP_NEQ_UQ;
-    _CMP_NEQ_UQ
-  }};
-  (NotGreaterEqualUnordered) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::_CMP_NGE_UQ;
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::_CMP_NGE_UQ;
-    _CMP_NGE_UQ
-  }};
-  (NotGreaterThanUnordered) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::_CMP_NGT_UQ;
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::_CMP_NGT_UQ;
-    _CMP_NGT_UQ
-  }};
-  (NotLessEqualUnordered) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::_CMP_NLE_UQ;
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::_CMP_NLE_UQ;
-    _CMP_NLE_UQ
-  }};
-  (NotLessThanUnordered) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::_CMP_NLT_UQ;
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::_CMP_NLT_UQ;
-    _CMP_NLT_UQ
-  }};
-  (Ordered) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::_CMP_ORD_Q;
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::_CMP_ORD_Q;
-    _CMP_ORD_Q
-  }};
-  (True) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::_CMP_TRUE_UQ;
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::_CMP_TRUE_UQ;
-    _CMP_TRUE_UQ
-  }};
-  (Unordered) => {{
-    #[cfg(target_arch = "x86")]
-    use ::core::arch::x86::_CMP_UNORD_Q;
-    #[cfg(target_arch = "x86_64")]
-    use ::core::arch::x86_64::_CMP_UNORD_Q;
-    _CMP_UNORD_Q
-  }};
-  ($unknown_op:tt) => {{
-    compile_error!("The operation name given is invalid.");
-  }};
+  ($op:ident) => {
+    $crate::CmpOp::$op.imm()
+  };
+}
+
+/// A comparison operator, for use with the `cmp_op_mask` functions.
+///
+/// As a normal type it can be stored, passed around, and named in generic code.
+/// Stable Rust doesn't allow an enum as a const generic parameter, so use
+/// [`CmpOp::imm`] to turn it into the immediate value that the functions take.
+/// ```
+/// # use safe_arch::*;
+/// const LT: i32 = CmpOp::LessThanOrdered.imm();
+/// let a = m256::from_array([1.0, 5.0, 0.0, 7.0, 1.0, 5.0, 0.0, f32::NAN]);
+/// let b = set_splat_m256(2.0);
+/// let mask = cmp_op_mask_m256::<LT>(a, b);
+/// assert_eq!(move_mask_m256(mask), 0b0101_0101);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i32)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub enum CmpOp {
+  /// `a == b`, false if either is NaN.
+  EqualOrdered = _CMP_EQ_OQ,
+  /// `a == b`, true if either is NaN.
+  EqualUnordered = _CMP_EQ_UQ,
+  /// Always false.
+  False = _CMP_FALSE_OQ,
+  /// `a >= b`, false if either is NaN.
+  GreaterEqualOrdered = _CMP_GE_OQ,
+  /// `a > b`, false if either is NaN.
+  GreaterThanOrdered = _CMP_GT_OQ,
+  /// `a <= b`, false if either is NaN.
+  LessEqualOrdered = _CMP_LE_OQ,
+  /// `a < b`, false if either is NaN.
+  LessThanOrdered = _CMP_LT_OQ,
+  /// `a != b`, false if either is NaN.
+  NotEqualOrdered = _CMP_NEQ_OQ,
+  /// `a != b`, true if either is NaN.
+  NotEqualUnordered = _CMP_NEQ_UQ,
+  /// `!(a >= b)`, true if either is NaN.
+  NotGreaterEqualUnordered = _CMP_NGE_UQ,
+  /// `!(a > b)`, true if either is NaN.
+  NotGreaterThanUnordered = _CMP_NGT_UQ,
+  /// `!(a <= b)`, true if either is NaN.
+  NotLessEqualUnordered = _CMP_NLE_UQ,
+  /// `!(a < b)`, true if either is NaN.
+  NotLessThanUnordered = _CMP_NLT_UQ,
+  /// True if neither is NaN.
+  Ordered = _CMP_ORD_Q,
+  /// Always true.
+  True = _CMP_TRUE_UQ,
+  /// True if either is NaN.
+  Unordered = _CMP_UNORD_Q,
+}
+impl CmpOp {
+  /// The immediate value for this operator.
+  #[must_use]
+  #[inline(always)]
+  pub const fn imm(self) -> i32 {
+    self as i32
+  }
 }
 
 /// Compare `f32` lanes according to the operation specified, mask output.
 ///
-/// * Operators are according to the [`cmp_op`] macro.
+/// * Operators are according to [`CmpOp`].
 ///
 /// * **Intrinsic:** [``]
 /// * **Assembly:**
@@ -442,12 +476,13 @@ macro_rules! cmp_op {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn cmp_op_mask_m128<const OP: i32>(a: m128, b: m128) -> m128 {
+  let () = ImmBits::<OP, 5>::OK;
   m128(unsafe { _mm_cmp_ps(a.0, b.0, OP) })
 }
 
 /// Compare `f32` lanes according to the operation specified, mask output.
 ///
-/// * Operators are according to the [`cmp_op`] macro.
+/// * Operators are according to [`CmpOp`].
 ///
 /// * **Intrinsic:** [``]
 /// * **Assembly:**
@@ -455,12 +490,13 @@ pub fn cmp_op_mask_m128<const OP: i32>(a: m128, b: m128) -> m128 {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn cmp_op_mask_m128_s<const OP: i32>(a: m128, b: m128) -> m128 {
+  let () = ImmBits::<OP, 5>::OK;
   m128(unsafe { _mm_cmp_ss(a.0, b.0, OP) })
 }
 
 /// Compare `f32` lanes according to the operation specified, mask output.
 ///
-/// * Operators are according to the [`cmp_op`] macro.
+/// * Operators are according to [`CmpOp`].
 ///
 /// * **Intrinsic:** [``]
 /// * **Assembly:**
@@ -468,12 +504,13 @@ pub fn cmp_op_mask_m128_s<const OP: i32>(a: m128, b: m128) -> m128 {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn cmp_op_mask_m256<const OP: i32>(a: m256, b: m256) -> m256 {
+  let () = ImmBits::<OP, 5>::OK;
   m256(unsafe { _mm256_cmp_ps(a.0, b.0, OP) })
 }
 
 /// Compare `f64` lanes according to the operation specified, mask output.
 ///
-/// * Operators are according to the [`cmp_op`] macro.
+/// * Operators are according to [`CmpOp`].
 ///
 /// * **Intrinsic:** [``]
 /// * **Assembly:**
@@ -481,12 +518,13 @@ pub fn cmp_op_mask_m256<const OP: i32>(a: m256, b: m256) -> m256 {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn cmp_op_mask_m128d<const OP: i32>(a: m128d, b: m128d) -> m128d {
+  let () = ImmBits::<OP, 5>::OK;
   m128d(unsafe { _mm_cmp_pd(a.0, b.0, OP) })
 }
 
 /// Compare `f64` lanes according to the operation specified, mask output.
 ///
-/// * Operators are according to the [`cmp_op`] macro.
+/// * Operators are according to [`CmpOp`].
 ///
 /// * **Intrinsic:** [``]
 /// * **Assembly:**
@@ -494,12 +532,13 @@ pub fn cmp_op_mask_m128d<const OP: i32>(a: m128d, b: m128d) -> m128d {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn cmp_op_mask_m128d_s<const OP: i32>(a: m128d, b: m128d) -> m128d {
+  let () = ImmBits::<OP, 5>::OK;
   m128d(unsafe { _mm_cmp_sd(a.0, b.0, OP) })
 }
 
 /// Compare `f64` lanes according to the operation specified, mask output.
 ///
-/// * Operators are according to the [`cmp_op`] macro.
+/// * Operators are according to [`CmpOp`].
 ///
 /// * **Intrinsic:** [``]
 /// * **Assembly:**
@@ -507,6 +546,7 @@ pub fn cmp_op_mask_m128d_s<const OP: i32>(a: m128d, b: m128d) -> m128d {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn cmp_op_mask_m256d<const OP: i32>(a: m256d, b: m256d) -> m256d {
+  let () = ImmBits::<OP, 5>::OK;
   m256d(unsafe { _mm256_cmp_pd(a.0, b.0, OP) })
 }
 
@@ -631,6 +671,58 @@ pub fn convert_truncate_to_i32_m256i_from_m256(a: m256) -> m256i {
   m256i(unsafe { _mm256_cvttps_epi32(a.0) })
 }
 
+/// Convert `f32` lanes to `i32` lanes with truncation, saturating like an `as`
+/// cast.
+///
+/// Out of range lanes are clamped to `i32::MIN` or `i32::MAX` (instead of all
+/// becoming `i32::MIN`), and NaN lanes become 0.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256::from_array([1.5, -1.5, f32::NAN, 3.0e9, -3.0e9, f32::INFINITY, 0.0, 7.9]);
+/// let b: [i32; 8] = convert_saturating_to_i32_m256i_from_m256(a).into();
+/// assert_eq!(b, [1, -1, 0, i32::MAX, i32::MIN, i32::MAX, 0, 7]);
+/// ```
+/// * **Intrinsic:** [`_mm256_cvttps_epi32`], plus a fixup
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn convert_saturating_to_i32_m256i_from_m256(a: m256) -> m256i {
+  let t = cast_to_m256_from_m256i(convert_truncate_to_i32_m256i_from_m256(a));
+  // Positive overflow gave `i32::MIN`, flipping all the bits makes `i32::MAX`.
+  let too_big = cmp_op_mask_m256::<{ CmpOp::GreaterEqualOrdered.imm() }>(
+    a,
+    set_splat_m256(2147483648.0),
+  );
+  let not_nan = cmp_op_mask_m256::<{ CmpOp::Ordered.imm() }>(a, a);
+  cast_to_m256i_from_m256(bitand_m256(bitxor_m256(t, too_big), not_nan))
+}
+
+/// Convert `f64` lanes to `i32` lanes with truncation, saturating like an `as`
+/// cast.
+///
+/// Out of range lanes are clamped to `i32::MIN` or `i32::MAX` (instead of all
+/// becoming `i32::MIN`), and NaN lanes become 0.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256d::from_array([-2.5, f64::NAN, 1.0e10, -1.0e10]);
+/// let b: [i32; 4] = convert_saturating_to_i32_m128i_from_m256d(a).into();
+/// assert_eq!(b, [-2, 0, i32::MAX, i32::MIN]);
+/// ```
+/// * **Intrinsic:** [`_mm256_cvttpd_epi32`], plus a fixup
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn convert_saturating_to_i32_m128i_from_m256d(a: m256d) -> m128i {
+  // Every `i32` fits in an `f64`, so zero the NaN lanes and then clamp.
+  let not_nan =
+    bitand_m256d(a, cmp_op_mask_m256d::<{ CmpOp::Ordered.imm() }>(a, a));
+  let clamped = min_m256d(
+    max_m256d(not_nan, set_splat_m256d(i32::MIN as f64)),
+    set_splat_m256d(i32::MAX as f64),
+  );
+  convert_truncate_to_i32_m128i_from_m256d(clamped)
+}
+
 /// Lanewise `a / b` with `f64`.
 ///
 /// * **Intrinsic:** [``]
@@ -665,62 +757,177 @@ pub fn div_m256(a: m256, b: m256) -> m256 {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn dot_product_m256<const IMM: i32>(a: m256, b: m256) -> m256 {
+  let () = ImmBits::<IMM, 8>::OK;
   m256(unsafe { _mm256_dp_ps(a.0, b.0, IMM) })
 }
 
+/// The dot product of the `f64` lanes of `a` and `b`.
+///
+/// There's no `f64` form of `vdpps` for 256 bits, so this is a multiply
+/// followed by a shuffle and add tree.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256d::from_array([1.0, 2.0, 3.0, 4.0]);
+/// let b = m256d::from_array([5.0, 6.0, 7.0, -8.0]);
+/// assert_eq!(dot_product_m256d(a, b), 6.0);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn dot_product_m256d(a: m256d, b: m256d) -> f64 {
+  let product = mul_m256d(a, b);
+  let low = cast_to_m128d_from_m256d(product);
+  let high = extract_m128d_from_m256d::<1>(product);
+  let pair = add_m128d(low, high);
+  get_f64_from_m128d_s(add_m128d_s(pair, unpack_high_m128d(pair, pair)))
+}
+
+/// The dot product of two `f32` slices.
+///
+/// As with [`Iterator::zip`], if one slice is longer than the other then the
+/// extra elements are ignored.
+///
+/// Each step is a [`mul_add_m256`], so when the `fma` module is in the build
+/// the multiply and add are fused, and the result can differ (slightly)
+/// between builds. Either way the additions aren't done left-to-right.
+/// ```
+/// # use safe_arch::*;
+/// let a = [1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0];
+/// let b = [2.0_f32; 11];
+/// assert_eq!(dot_f32(&a, &b), 132.0);
+/// // the extra element of `a` is ignored
+/// assert_eq!(dot_f32(&a, &b[..10]), 110.0);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn dot_f32(a: &[f32], b: &[f32]) -> f32 {
+  let len = a.len().min(b.len());
+  let mut a_chunks = a[..len].chunks_exact(8);
+  let mut b_chunks = b[..len].chunks_exact(8);
+  let mut sum = zeroed_m256();
+  for (x, y) in (&mut a_chunks).zip(&mut b_chunks) {
+    let x = load_unaligned_m256(core::convert::TryInto::try_into(x).unwrap());
+    let y = load_unaligned_m256(core::convert::TryInto::try_into(y).unwrap());
+    sum = mul_add_m256(x, y, sum);
+  }
+  let x = load_partial_m256(a_chunks.remainder());
+  let y = load_partial_m256(b_chunks.remainder());
+  reduce_add_m256(mul_add_m256(x, y, sum))
+}
+
 /// Extracts an `i32` lane from `m256i`
 ///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// * **Intrinsic:** [`_mm256_extract_epi32`]
+/// * **Assembly:** multiple instructions
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn extract_i32_from_m256i<const IMM: i32>(a: m256i) -> i32 {
+  let () = ImmBits::<IMM, 3>::OK;
   unsafe { _mm256_extract_epi32(a.0, IMM) }
 }
 
 /// Extracts an `i64` lane from `m256i`
 ///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// On 32-bit x86 this goes through memory instead.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([5_i64, 6, 7, 8]);
+/// assert_eq!(extract_i64_from_m256i::<2>(a), 7);
+/// ```
+/// * **Intrinsic:** [`_mm256_extract_epi64`]
+/// * **Assembly:** multiple instructions
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
-#[cfg(target_arch = "x86_64")]
 pub fn extract_i64_from_m256i<const IMM: i32>(a: m256i) -> i64 {
-  unsafe { _mm256_extract_epi64(a.0, IMM) }
+  let () = ImmBits::<IMM, 2>::OK;
+  #[cfg(target_arch = "x86_64")]
+  {
+    unsafe { _mm256_extract_epi64(a.0, IMM) }
+  }
+  #[cfg(not(target_arch = "x86_64"))]
+  {
+    <[i64; 4]>::from(a)[IMM as usize]
+  }
 }
 
-/// Extracts an `m128d` from `m256d`
+/// Extracts an `f32` lane from `m256`
 ///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// There's no single instruction for this, so it takes the correct half and
+/// then moves the lane down to the bottom of it.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256::from_array([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+/// assert_eq!(extract_f32_from_m256::<0>(a), 1.0);
+/// assert_eq!(extract_f32_from_m256::<2>(a), 3.0);
+/// assert_eq!(extract_f32_from_m256::<7>(a), 8.0);
+/// ```
+/// * **Intrinsic:** [`_mm256_extractf128_ps`], [`_mm_permute_ps`]
+/// * **Assembly:** multiple instructions
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn extract_f32_from_m256<const IMM: i32>(a: m256) -> f32 {
+  let () = ImmBits::<IMM, 3>::OK;
+  let half = if IMM < 4 { cast_to_m128_from_m256(a) } else { extract_m128_from_m256::<1>(a) };
+  get_f32_from_m128_s(match IMM & 3 {
+    0 => half,
+    1 => permute_m128::<1>(half),
+    2 => permute_m128::<2>(half),
+    _ => permute_m128::<3>(half),
+  })
+}
+
+/// Extracts the low (`0`) or high (`1`) `m128d` half of an `m256d`.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256d::from_array([1.0, 2.0, 3.0, 4.0]);
+/// assert_eq!(extract_m128d_from_m256d::<0>(a).to_array(), [1.0, 2.0]);
+/// assert_eq!(extract_m128d_from_m256d::<1>(a).to_array(), [3.0, 4.0]);
+/// ```
+/// * **Intrinsic:** [`_mm256_extractf128_pd`]
+/// * **Assembly:** `vextractf128 xmm, ymm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn extract_m128d_from_m256d<const IMM: i32>(a: m256d) -> m128d {
+  let () = ImmBits::<IMM, 1>::OK;
   m128d(unsafe { _mm256_extractf128_pd(a.0, IMM) })
 }
 
-/// Extracts an `m128` from `m256`
-///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// Extracts the low (`0`) or high (`1`) `m128` half of an `m256`.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256::from_array([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+/// assert_eq!(extract_m128_from_m256::<0>(a).to_array(), [1.0, 2.0, 3.0, 4.0]);
+/// assert_eq!(extract_m128_from_m256::<1>(a).to_array(), [5.0, 6.0, 7.0, 8.0]);
+/// ```
+/// * **Intrinsic:** [`_mm256_extractf128_ps`]
+/// * **Assembly:** `vextractf128 xmm, ymm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn extract_m128_from_m256<const IMM: i32>(a: m256) -> m128 {
+  let () = ImmBits::<IMM, 1>::OK;
   m128(unsafe { _mm256_extractf128_ps(a.0, IMM) })
 }
 
-/// Extracts an `m128i` from `m256i`
-///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// Extracts the low (`0`) or high (`1`) `m128i` half of an `m256i`.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([5_u128, 6_u128]);
+/// assert_eq!(extract_m128i_from_m256i::<0>(a), m128i::from(5_u128));
+/// assert_eq!(extract_m128i_from_m256i::<1>(a), m128i::from(6_u128));
+/// ```
+/// * **Intrinsic:** [`_mm256_extractf128_si256`]
+/// * **Assembly:** `vextractf128 xmm, ymm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn extract_m128i_from_m256i<const IMM: i32>(a: m256i) -> m128i {
+  let () = ImmBits::<IMM, 1>::OK;
   m128i(unsafe { _mm256_extractf128_si256(a.0, IMM) })
 }
 
@@ -792,68 +999,129 @@ pub fn sub_horizontal_m256(a: m256, b: m256) -> m256 {
 
 /// Inserts an `i8` to `m256i`
 ///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// * **Intrinsic:** [`_mm256_insert_epi8`]
+/// * **Assembly:** multiple instructions
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn insert_i8_to_m256i<const IMM: i32>(a: m256i, i: i8) -> m256i {
+  let () = ImmBits::<IMM, 5>::OK;
   m256i(unsafe { _mm256_insert_epi8(a.0, i, IMM) })
 }
 
 /// Inserts an `i16` to `m256i`
 ///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// * **Intrinsic:** [`_mm256_insert_epi16`]
+/// * **Assembly:** multiple instructions
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn insert_i16_to_m256i<const IMM: i32>(a: m256i, i: i16) -> m256i {
+  let () = ImmBits::<IMM, 4>::OK;
   m256i(unsafe { _mm256_insert_epi16(a.0, i, IMM) })
 }
 
 /// Inserts an `i32` to `m256i`
 ///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// * **Intrinsic:** [`_mm256_insert_epi32`]
+/// * **Assembly:** multiple instructions
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn insert_i32_to_m256i<const IMM: i32>(a: m256i, i: i32) -> m256i {
+  let () = ImmBits::<IMM, 3>::OK;
   m256i(unsafe { _mm256_insert_epi32(a.0, i, IMM) })
 }
 
 /// Inserts an `i64` to `m256i`
 ///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// On 32-bit x86 this goes through memory instead.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([5_i64, 6, 7, 8]);
+/// let b: [i64; 4] = insert_i64_to_m256i::<3>(a, -1).into();
+/// assert_eq!(b, [5, 6, 7, -1]);
+/// ```
+/// * **Intrinsic:** [`_mm256_insert_epi64`]
+/// * **Assembly:** multiple instructions
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
-#[cfg(target_arch = "x86_64")]
 pub fn insert_i64_to_m256i<const IMM: i32>(a: m256i, i: i64) -> m256i {
-  m256i(unsafe { _mm256_insert_epi64(a.0, i, IMM) })
+  let () = ImmBits::<IMM, 2>::OK;
+  #[cfg(target_arch = "x86_64")]
+  {
+    m256i(unsafe { _mm256_insert_epi64(a.0, i, IMM) })
+  }
+  #[cfg(not(target_arch = "x86_64"))]
+  {
+    let mut lanes = <[i64; 4]>::from(a);
+    lanes[IMM as usize] = i;
+    m256i::from(lanes)
+  }
 }
 
-/// Inserts an `m128d` to `m256d`
-///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// Inserts an `f32` to `m256`
+///
+/// There's no single instruction for this, so it splats the value and then
+/// blends in the one lane.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256::from_array([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+/// let b = insert_f32_to_m256::<5>(a, -1.0).to_array();
+/// assert_eq!(b, [1.0, 2.0, 3.0, 4.0, 5.0, -1.0, 7.0, 8.0]);
+/// ```
+/// * **Intrinsic:** [`_mm256_set1_ps`], [`_mm256_blend_ps`]
+/// * **Assembly:** multiple instructions
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn insert_f32_to_m256<const IMM: i32>(a: m256, f: f32) -> m256 {
+  let () = ImmBits::<IMM, 3>::OK;
+  let b = set_splat_m256(f);
+  match IMM {
+    0 => blend_m256::<0b0000_0001>(a, b),
+    1 => blend_m256::<0b0000_0010>(a, b),
+    2 => blend_m256::<0b0000_0100>(a, b),
+    3 => blend_m256::<0b0000_1000>(a, b),
+    4 => blend_m256::<0b0001_0000>(a, b),
+    5 => blend_m256::<0b0010_0000>(a, b),
+    6 => blend_m256::<0b0100_0000>(a, b),
+    _ => blend_m256::<0b1000_0000>(a, b),
+  }
+}
+
+/// Inserts an `m128d` as the low (`0`) or high (`1`) half of an `m256d`.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256d::from_array([1.0, 2.0, 3.0, 4.0]);
+/// let b = m128d::from_array([8.0, 9.0]);
+/// assert_eq!(insert_m128d_to_m256d::<1>(a, b).to_array(), [1.0, 2.0, 8.0, 9.0]);
+/// ```
+/// * **Intrinsic:** [`_mm256_insertf128_pd`]
+/// * **Assembly:** `vinsertf128 ymm, ymm, xmm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn insert_m128d_to_m256d<const IMM: i32>(a: m256d, b: m128d) -> m256d {
+  let () = ImmBits::<IMM, 1>::OK;
   m256d(unsafe { _mm256_insertf128_pd(a.0, b.0, IMM) })
 }
 
-/// Inserts an `m128` to `m256`
-///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// Inserts an `m128` as the low (`0`) or high (`1`) half of an `m256`.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256::from_array([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+/// let b = m128::from_array([0.0; 4]);
+/// assert_eq!(insert_m128_to_m256::<0>(a, b).to_array(), [0.0, 0.0, 0.0, 0.0, 5.0, 6.0, 7.0, 8.0]);
+/// ```
+/// * **Intrinsic:** [`_mm256_insertf128_ps`]
+/// * **Assembly:** `vinsertf128 ymm, ymm, xmm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn insert_m128_to_m256<const IMM: i32>(a: m256, b: m128) -> m256 {
+  let () = ImmBits::<IMM, 1>::OK;
   m256(unsafe { _mm256_insertf128_ps(a.0, b.0, IMM) })
 }
 
@@ -863,12 +1131,13 @@ pub fn insert_m128_to_m256<const IMM: i32>(a: m256, b: m128) -> m256 {
 /// but not AVX2. If you plan on having AVX2 available please use
 /// [`insert_m128i_to_m256i`], it will do the same task with better performance.
 ///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// * **Intrinsic:** [`_mm256_insertf128_si256`]
+/// * **Assembly:** `vinsertf128 ymm, ymm, xmm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn insert_m128i_to_m256i_slow_avx<const IMM: i32>(a: m256i, b: m128i) -> m256i {
+  let () = ImmBits::<IMM, 1>::OK;
   m256i(unsafe { _mm256_insertf128_si256(a.0, b.0, IMM) })
 }
 
@@ -1023,6 +1292,54 @@ pub fn load_masked_m256(a: &m256, mask: m256i) -> m256 {
   m256(unsafe { _mm256_maskload_ps(a as *const m256 as *const f32, mask.0) })
 }
 
+/// Builds a mask with the lowest `min(n, 8)` of the `i32` lanes active.
+#[must_use]
+#[inline(always)]
+pub(crate) fn partial_mask_i32_m256i(n: usize) -> m256i {
+  static MASKS: [i32; 16] = [-1, -1, -1, -1, -1, -1, -1, -1, 0, 0, 0, 0, 0, 0, 0, 0];
+  let start = 8 - n.min(8);
+  m256i(unsafe { _mm256_loadu_si256(MASKS[start..].as_ptr().cast()) })
+}
+
+/// Builds a mask with the lowest `min(n, 4)` of the `i64` lanes active.
+#[must_use]
+#[inline(always)]
+pub(crate) fn partial_mask_i64_m256i(n: usize) -> m256i {
+  static MASKS: [i64; 8] = [-1, -1, -1, -1, 0, 0, 0, 0];
+  let start = 4 - n.min(4);
+  m256i(unsafe { _mm256_loadu_si256(MASKS[start..].as_ptr().cast()) })
+}
+
+/// Loads up to 8 lanes from the slice, zeroing any lanes past the end.
+///
+/// Only the elements that are actually in the slice are read, so this is safe
+/// to use on the tail of a buffer.
+///
+/// * **Intrinsic:** [`_mm256_maskload_ps`]
+/// * **Assembly:** `vmaskmovps ymm, ymm, m256`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn load_partial_m256(a: &[f32]) -> m256 {
+  let mask = partial_mask_i32_m256i(a.len());
+  m256(unsafe { _mm256_maskload_ps(a.as_ptr(), mask.0) })
+}
+
+/// Loads up to 4 lanes from the slice, zeroing any lanes past the end.
+///
+/// Only the elements that are actually in the slice are read, so this is safe
+/// to use on the tail of a buffer.
+///
+/// * **Intrinsic:** [`_mm256_maskload_pd`]
+/// * **Assembly:** `vmaskmovpd ymm, ymm, m256`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn load_partial_m256d(a: &[f64]) -> m256d {
+  let mask = partial_mask_i64_m256i(a.len());
+  m256d(unsafe { _mm256_maskload_pd(a.as_ptr(), mask.0) })
+}
+
 /// Store data from a register into memory according to a mask.
 ///
 /// When the high bit of a mask lane isn't set that lane is not written.
@@ -1071,6 +1388,122 @@ pub fn store_masked_m256(addr: &mut m256, mask: m256i, a: m256) {
   unsafe { _mm256_maskstore_ps(addr as *mut m256 as *mut f32, mask.0, a.0) }
 }
 
+/// Stores the low lanes of the register into the slice, up to 8 lanes.
+///
+/// If the slice is shorter than the register, lanes past the end of the slice
+/// are not written.
+///
+/// * **Intrinsic:** [`_mm256_maskstore_ps`]
+/// * **Assembly:** `vmaskmovps m256, ymm, ymm`
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn store_partial_m256(addr: &mut [f32], a: m256) {
+  let mask = partial_mask_i32_m256i(addr.len());
+  unsafe { _mm256_maskstore_ps(addr.as_mut_ptr(), mask.0, a.0) }
+}
+
+/// Stores the low lanes of the register into the slice, up to 4 lanes.
+///
+/// If the slice is shorter than the register, lanes past the end of the slice
+/// are not written.
+///
+/// * **Intrinsic:** [`_mm256_maskstore_pd`]
+/// * **Assembly:** `vmaskmovpd m256, ymm, ymm`
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn store_partial_m256d(addr: &mut [f64], a: m256d) {
+  let mask = partial_mask_i64_m256i(addr.len());
+  unsafe { _mm256_maskstore_pd(addr.as_mut_ptr(), mask.0, a.0) }
+}
+
+/// Splits a slice into whole `m256` registers plus the leftover tail.
+///
+/// The registers are read with unaligned loads. On `avx` hardware those cost
+/// the same as aligned loads when the data is aligned anyway, so there's no
+/// alignment prologue. The tail is less than 8 elements long, so you can
+/// finish with [`load_partial_m256`] or a scalar loop.
+/// ```
+/// # use safe_arch::*;
+/// let data: Vec<f32> = (0..19).map(|i| i as f32).collect();
+/// let (chunks, tail) = simd_chunks_m256(&data);
+/// let sum = chunks.fold(zeroed_m256(), add_m256);
+/// assert_eq!(sum.to_array(), [8.0, 10.0, 12.0, 14.0, 16.0, 18.0, 20.0, 22.0]);
+/// assert_eq!(tail, &[16.0, 17.0, 18.0]);
+/// ```
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn simd_chunks_m256(a: &[f32]) -> (impl Iterator<Item = m256> + '_, &[f32]) {
+  let chunks = a.chunks_exact(8);
+  let tail = chunks.remainder();
+  (chunks.map(|c| load_unaligned_m256(core::convert::TryInto::try_into(c).unwrap())), tail)
+}
+
+/// Splits a slice into whole `m256d` registers plus the leftover tail.
+///
+/// As [`simd_chunks_m256`], but for `f64`, so the tail is less than 4
+/// elements long.
+/// ```
+/// # use safe_arch::*;
+/// let data = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+/// let (chunks, tail) = simd_chunks_m256d(&data);
+/// let sum = chunks.fold(zeroed_m256d(), add_m256d);
+/// assert_eq!(sum.to_array(), [6.0, 8.0, 10.0, 12.0]);
+/// assert_eq!(tail, &[9.0]);
+/// ```
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn simd_chunks_m256d(a: &[f64]) -> (impl Iterator<Item = m256d> + '_, &[f64]) {
+  let chunks = a.chunks_exact(4);
+  let tail = chunks.remainder();
+  (chunks.map(|c| load_unaligned_m256d(core::convert::TryInto::try_into(c).unwrap())), tail)
+}
+
+/// Runs `f` over each whole `m256` of a slice, storing the results back in
+/// place, and returns the leftover tail.
+///
+/// The tail is less than 8 elements long, so you can finish with
+/// [`load_partial_m256`] and [`store_partial_m256`].
+/// ```
+/// # use safe_arch::*;
+/// let mut data = [1.0_f32; 10];
+/// let tail = simd_chunks_mut_m256(&mut data, |a| add_m256(a, a));
+/// store_partial_m256(tail, mul_m256(load_partial_m256(tail), set_splat_m256(3.0)));
+/// assert_eq!(data, [2.0, 2.0, 2.0, 2.0, 2.0, 2.0, 2.0, 2.0, 3.0, 3.0]);
+/// ```
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn simd_chunks_mut_m256(a: &mut [f32], mut f: impl FnMut(m256) -> m256) -> &mut [f32] {
+  let mut chunks = a.chunks_exact_mut(8);
+  for c in &mut chunks {
+    let c: &mut [f32; 8] = core::convert::TryInto::try_into(c).unwrap();
+    store_unaligned_m256(c, f(load_unaligned_m256(c)));
+  }
+  chunks.into_remainder()
+}
+
+/// Runs `f` over each whole `m256d` of a slice, storing the results back in
+/// place, and returns the leftover tail.
+///
+/// As [`simd_chunks_mut_m256`], but for `f64`, so the tail is less than 4
+/// elements long.
+/// ```
+/// # use safe_arch::*;
+/// let mut data = [1.0, 2.0, 3.0, 4.0, 5.0];
+/// let tail = simd_chunks_mut_m256d(&mut data, |a| mul_m256d(a, a));
+/// tail[0] = 0.0;
+/// assert_eq!(data, [1.0, 4.0, 9.0, 16.0, 0.0]);
+/// ```
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn simd_chunks_mut_m256d(a: &mut [f64], mut f: impl FnMut(m256d) -> m256d) -> &mut [f64] {
+  let mut chunks = a.chunks_exact_mut(4);
+  for c in &mut chunks {
+    let c: &mut [f64; 4] = core::convert::TryInto::try_into(c).unwrap();
+    store_unaligned_m256d(c, f(load_unaligned_m256d(c)));
+  }
+  chunks.into_remainder()
+}
+
 /// Lanewise `max(a, b)`.
 ///
 /// * **Intrinsic:** [``]
@@ -1082,6 +1515,32 @@ pub fn max_m256d(a: m256d, b: m256d) -> m256d {
   m256d(unsafe { _mm256_max_pd(a.0, b.0) })
 }
 
+/// Lanewise `max(a, b)`, with a NaN in either input giving a NaN output.
+///
+/// [`max_m256d`] uses the `b` lane whenever either lane is NaN, so a NaN in
+/// `a` is silently lost. This keeps it instead. When both lanes are zero (of
+/// either sign) the `b` lane is still the one you get.
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn max_nan_propagating_m256d(a: m256d, b: m256d) -> m256d {
+  let a_nan = cmp_op_mask_m256d::<{ CmpOp::Unordered.imm() }>(a, a);
+  blend_varying_m256d(max_m256d(a, b), a, a_nan)
+}
+
+/// Lanewise `max(a, b)`, with the IEEE-754 `maxNum` NaN rules.
+///
+/// If only one lane is NaN the other lane is used, and the output is only NaN
+/// when both lanes are NaN. This matches [`f64::max`]. When both lanes are
+/// zero (of either sign) the `b` lane is used.
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn max_num_m256d(a: m256d, b: m256d) -> m256d {
+  let b_nan = cmp_op_mask_m256d::<{ CmpOp::Unordered.imm() }>(b, b);
+  blend_varying_m256d(max_m256d(a, b), a, b_nan)
+}
+
 /// Lanewise `max(a, b)`.
 ///
 /// * **Intrinsic:** [``]
@@ -1093,6 +1552,32 @@ pub fn max_m256(a: m256, b: m256) -> m256 {
   m256(unsafe { _mm256_max_ps(a.0, b.0) })
 }
 
+/// Lanewise `max(a, b)`, with a NaN in either input giving a NaN output.
+///
+/// [`max_m256`] uses the `b` lane whenever either lane is NaN, so a NaN in
+/// `a` is silently lost. This keeps it instead. When both lanes are zero (of
+/// either sign) the `b` lane is still the one you get.
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn max_nan_propagating_m256(a: m256, b: m256) -> m256 {
+  let a_nan = cmp_op_mask_m256::<{ CmpOp::Unordered.imm() }>(a, a);
+  blend_varying_m256(max_m256(a, b), a, a_nan)
+}
+
+/// Lanewise `max(a, b)`, with the IEEE-754 `maxNum` NaN rules.
+///
+/// If only one lane is NaN the other lane is used, and the output is only NaN
+/// when both lanes are NaN. This matches [`f32::max`]. When both lanes are
+/// zero (of either sign) the `b` lane is used.
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn max_num_m256(a: m256, b: m256) -> m256 {
+  let b_nan = cmp_op_mask_m256::<{ CmpOp::Unordered.imm() }>(b, b);
+  blend_varying_m256(max_m256(a, b), a, b_nan)
+}
+
 /// Lanewise `min(a, b)`.
 ///
 /// * **Intrinsic:** [``]
@@ -1104,6 +1589,54 @@ pub fn min_m256d(a: m256d, b: m256d) -> m256d {
   m256d(unsafe { _mm256_min_pd(a.0, b.0) })
 }
 
+/// Lanewise `min(a, b)`, with a NaN in either input giving a NaN output.
+///
+/// [`min_m256d`] uses the `b` lane whenever either lane is NaN, so a NaN in
+/// `a` is silently lost. This keeps it instead. When both lanes are zero (of
+/// either sign) the `b` lane is still the one you get.
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn min_nan_propagating_m256d(a: m256d, b: m256d) -> m256d {
+  let a_nan = cmp_op_mask_m256d::<{ CmpOp::Unordered.imm() }>(a, a);
+  blend_varying_m256d(min_m256d(a, b), a, a_nan)
+}
+
+/// Lanewise `min(a, b)`, with the IEEE-754 `minNum` NaN rules.
+///
+/// If only one lane is NaN the other lane is used, and the output is only NaN
+/// when both lanes are NaN. This matches [`f64::min`]. When both lanes are
+/// zero (of either sign) the `b` lane is used.
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn min_num_m256d(a: m256d, b: m256d) -> m256d {
+  let b_nan = cmp_op_mask_m256d::<{ CmpOp::Unordered.imm() }>(b, b);
+  blend_varying_m256d(min_m256d(a, b), a, b_nan)
+}
+
+/// Lanewise `x` clamped to the range `lo..=hi`.
+///
+/// A NaN lane of `x` stays NaN, as with [`f64::clamp`]. Unlike that function
+/// this doesn't panic: a NaN `lo` or `hi` lane means that bound is skipped,
+/// and when `lo > hi` the output is `hi`.
+/// ```
+/// # use safe_arch::*;
+/// let x = m256d::from_array([-5.0, 0.5, 9.0, f64::NAN]);
+/// let lo = set_splat_m256d(0.0);
+/// let hi = set_splat_m256d(1.0);
+/// let c = clamp_m256d(x, lo, hi).to_array();
+/// assert_eq!(&c[..3], &[0.0, 0.5, 1.0]);
+/// assert!(c[3].is_nan());
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn clamp_m256d(x: m256d, lo: m256d, hi: m256d) -> m256d {
+  // With these argument orders a NaN `x` is what gets passed along.
+  min_m256d(hi, max_m256d(lo, x))
+}
+
 /// Lanewise `min(a, b)`.
 ///
 /// * **Intrinsic:** [``]
@@ -1115,6 +1648,54 @@ pub fn min_m256(a: m256, b: m256) -> m256 {
   m256(unsafe { _mm256_min_ps(a.0, b.0) })
 }
 
+/// Lanewise `min(a, b)`, with a NaN in either input giving a NaN output.
+///
+/// [`min_m256`] uses the `b` lane whenever either lane is NaN, so a NaN in
+/// `a` is silently lost. This keeps it instead. When both lanes are zero (of
+/// either sign) the `b` lane is still the one you get.
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn min_nan_propagating_m256(a: m256, b: m256) -> m256 {
+  let a_nan = cmp_op_mask_m256::<{ CmpOp::Unordered.imm() }>(a, a);
+  blend_varying_m256(min_m256(a, b), a, a_nan)
+}
+
+/// Lanewise `min(a, b)`, with the IEEE-754 `minNum` NaN rules.
+///
+/// If only one lane is NaN the other lane is used, and the output is only NaN
+/// when both lanes are NaN. This matches [`f32::min`]. When both lanes are
+/// zero (of either sign) the `b` lane is used.
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn min_num_m256(a: m256, b: m256) -> m256 {
+  let b_nan = cmp_op_mask_m256::<{ CmpOp::Unordered.imm() }>(b, b);
+  blend_varying_m256(min_m256(a, b), a, b_nan)
+}
+
+/// Lanewise `x` clamped to the range `lo..=hi`.
+///
+/// A NaN lane of `x` stays NaN, as with [`f32::clamp`]. Unlike that function
+/// this doesn't panic: a NaN `lo` or `hi` lane means that bound is skipped,
+/// and when `lo > hi` the output is `hi`.
+/// ```
+/// # use safe_arch::*;
+/// let x = m256::from_array([-5.0, 0.5, 9.0, 0.0, 1.0, -0.25, 1.5, f32::NAN]);
+/// let lo = set_splat_m256(0.0);
+/// let hi = set_splat_m256(1.0);
+/// let c = clamp_m256(x, lo, hi).to_array();
+/// assert_eq!(&c[..7], &[0.0, 0.5, 1.0, 0.0, 1.0, 0.0, 1.0]);
+/// assert!(c[7].is_nan());
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn clamp_m256(x: m256, lo: m256, hi: m256) -> m256 {
+  // With these argument orders a NaN `x` is what gets passed along.
+  min_m256(hi, max_m256(lo, x))
+}
+
 /// Duplicate the odd-indexed lanes to the even lanes.
 ///
 /// * **Intrinsic:** [``]
@@ -1159,6 +1740,35 @@ pub fn move_mask_m256d(a: m256d) -> i32 {
   unsafe { _mm256_movemask_pd(a.0) }
 }
 
+/// If any lane of a comparison mask is true.
+///
+/// A lane counts as true if its sign bit is set, which is how the
+/// comparison functions make their masks.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256d::from_array([1.0, 2.0, 3.0, 4.0]);
+/// let m = cmp_op_mask_m256d::<{ CmpOp::LessThanOrdered.imm() }>(a, set_splat_m256d(3.5));
+/// assert!(any_lane_true_m256d(m));
+/// assert!(!all_lanes_true_m256d(m));
+/// assert!(!any_lane_true_m256d(zeroed_m256d()));
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn any_lane_true_m256d(mask: m256d) -> bool {
+  move_mask_m256d(mask) != 0
+}
+
+/// If every lane of a comparison mask is true.
+///
+/// See [`any_lane_true_m256d`] for what counts as true.
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn all_lanes_true_m256d(mask: m256d) -> bool {
+  move_mask_m256d(mask) == 0xF
+}
+
 /// Computes the bitwise AND of 256 bits in `a` and
 /// `b`, returns 1 if the result is zero, otherwise 0.
 /// * **Intrinsic:** [`_mm256_testz_ps`]
@@ -1192,6 +1802,18 @@ pub fn testc_m256(a: m256, b: m256) -> i32 {
   unsafe { _mm256_testc_ps(a.0, b.0) }
 }
 
+/// Returns 1 if, among the sign bits set in `b`, `a` has both 0s and 1s, otherwise 0.
+///
+/// That's the same as both [`testz_m256`] and [`testc_m256`] returning 0.
+/// * **Intrinsic:** [`_mm256_testnzc_ps`]
+/// * **Assembly:** vtestps ymm, ymm
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn testnzc_m256(a: m256, b: m256) -> i32 {
+  unsafe { _mm256_testnzc_ps(a.0, b.0) }
+}
+
 /// Compute the bitwise of sign bit NOT of `a` and then AND with `b`,
 /// returns 1 if the result is zero, otherwise 0.
 /// * **Intrinsic:** [`_mm_testc_ps`]
@@ -1203,6 +1825,18 @@ pub fn testc_m128(a: m128, b: m128) -> i32 {
   unsafe { _mm_testc_ps(a.0, b.0) }
 }
 
+/// Returns 1 if, among the sign bits set in `b`, `a` has both 0s and 1s, otherwise 0.
+///
+/// That's the same as both [`testz_m128`] and [`testc_m128`] returning 0.
+/// * **Intrinsic:** [`_mm_testnzc_ps`]
+/// * **Assembly:** vtestps xmm, xmm
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn testnzc_m128(a: m128, b: m128) -> i32 {
+  unsafe { _mm_testnzc_ps(a.0, b.0) }
+}
+
 /// Computes the bitwise of sign bit AND of 256 bits in `a` and
 /// `b`, returns 1 if the result is zero, otherwise 0.
 /// * **Intrinsic:** [`_mm256_testz_pd`]
@@ -1236,10 +1870,22 @@ pub fn testc_m256d(a: m256d, b: m256d) -> i32 {
   unsafe { _mm256_testc_pd(a.0, b.0) }
 }
 
+/// Returns 1 if, among the sign bits set in `b`, `a` has both 0s and 1s, otherwise 0.
+///
+/// That's the same as both [`testz_m256d`] and [`testc_m256d`] returning 0.
+/// * **Intrinsic:** [`_mm256_testnzc_pd`]
+/// * **Assembly:** vtestpd ymm, ymm
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn testnzc_m256d(a: m256d, b: m256d) -> i32 {
+  unsafe { _mm256_testnzc_pd(a.0, b.0) }
+}
+
 /// Compute the bitwise of sign bit NOT of `a` and then AND with `b`,
 /// returns 1 if the result is zero, otherwise 0.
-/// * **Intrinsic:** [`_mm_testc_si128`]
-/// * **Assembly:** vptest xmm, xmm
+/// * **Intrinsic:** [`_mm_testc_pd`]
+/// * **Assembly:** vtestpd xmm, xmm
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
@@ -1247,6 +1893,18 @@ pub fn testc_m128d(a: m128d, b: m128d) -> i32 {
   unsafe { _mm_testc_pd(a.0, b.0) }
 }
 
+/// Returns 1 if, among the sign bits set in `b`, `a` has both 0s and 1s, otherwise 0.
+///
+/// That's the same as both [`testz_m128d`] and [`testc_m128d`] returning 0.
+/// * **Intrinsic:** [`_mm_testnzc_pd`]
+/// * **Assembly:** vtestpd xmm, xmm
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn testnzc_m128d(a: m128d, b: m128d) -> i32 {
+  unsafe { _mm_testnzc_pd(a.0, b.0) }
+}
+
 /// Computes the bitwise of sign bit AND of 256 bits in `a` and
 /// `b`, returns 1 if the result is zero, otherwise 0.
 /// * **Intrinsic:** [`_mm256_testz_si256`]
@@ -1269,6 +1927,65 @@ pub fn testc_m256i(a: m256i, b: m256i) -> i32 {
   unsafe { _mm256_testc_si256(a.0, b.0) }
 }
 
+/// Returns 1 if, among the bits set in `b`, `a` has both 0s and 1s, otherwise 0.
+///
+/// That's the same as both [`testz_m256i`] and [`testc_m256i`] returning 0.
+/// * **Intrinsic:** [`_mm256_testnzc_si256`]
+/// * **Assembly:** vptest ymm, ymm
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn testnzc_m256i(a: m256i, b: m256i) -> i32 {
+  unsafe { _mm256_testnzc_si256(a.0, b.0) }
+}
+
+/// Returns if all masked bits are 0, `(a & mask) == 0`.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([0b1_0000_i64, 0, 0, 0]);
+/// assert_eq!(test_all_zeroes_m256i(a, m256i::from([0b0_1111_i64, -1, -1, -1])), 1);
+/// assert_eq!(test_all_zeroes_m256i(a, m256i::from([-1_i64; 4])), 0);
+/// ```
+/// * **Intrinsic:** [`_mm256_testz_si256`]
+/// * **Assembly:** vptest ymm, ymm
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn test_all_zeroes_m256i(a: m256i, mask: m256i) -> i32 {
+  unsafe { _mm256_testz_si256(a.0, mask.0) }
+}
+
+/// Tests if all bits are 1.
+/// ```
+/// # use safe_arch::*;
+/// assert_eq!(test_all_ones_m256i(m256i::from([-1_i64; 4])), 1);
+/// assert_eq!(test_all_ones_m256i(m256i::from([-1_i64, -1, -1, 0])), 0);
+/// ```
+/// * **Intrinsic:** [`_mm256_testc_si256`]
+/// * **Assembly:** vpcmpeqd ymm, ymm, ymm / vptest ymm, ymm
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn test_all_ones_m256i(a: m256i) -> i32 {
+  unsafe { _mm256_testc_si256(a.0, _mm256_set1_epi64x(-1)) }
+}
+
+/// Returns if, among the masked bits, there's both 0s and 1s.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([0b1_0000_i64, 0, 0, 0]);
+/// assert_eq!(test_mixed_ones_and_zeroes_m256i(a, m256i::from([0b1_1111_i64, 0, 0, 0])), 1);
+/// assert_eq!(test_mixed_ones_and_zeroes_m256i(a, m256i::from([0b0_1111_i64, 0, 0, 0])), 0);
+/// ```
+/// * **Intrinsic:** [`_mm256_testnzc_si256`]
+/// * **Assembly:** vptest ymm, ymm
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn test_mixed_ones_and_zeroes_m256i(a: m256i, mask: m256i) -> i32 {
+  unsafe { _mm256_testnzc_si256(a.0, mask.0) }
+}
+
 /// Collects the sign bit of each lane into a 4-bit value.
 ///
 /// * **Intrinsic:** [``]
@@ -1280,6 +1997,35 @@ pub fn move_mask_m256(a: m256) -> i32 {
   unsafe { _mm256_movemask_ps(a.0) }
 }
 
+/// If any lane of a comparison mask is true.
+///
+/// A lane counts as true if its sign bit is set, which is how the
+/// comparison functions make their masks.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256::from_array([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+/// let m = cmp_op_mask_m256::<{ CmpOp::LessThanOrdered.imm() }>(a, set_splat_m256(3.5));
+/// assert!(any_lane_true_m256(m));
+/// assert!(!all_lanes_true_m256(m));
+/// assert!(!any_lane_true_m256(zeroed_m256()));
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn any_lane_true_m256(mask: m256) -> bool {
+  move_mask_m256(mask) != 0
+}
+
+/// If every lane of a comparison mask is true.
+///
+/// See [`any_lane_true_m256`] for what counts as true.
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn all_lanes_true_m256(mask: m256) -> bool {
+  move_mask_m256(mask) == 0xFF
+}
+
 /// Lanewise `a * b` with `f64` lanes.
 ///
 /// * **Intrinsic:** [``]
@@ -1291,6 +2037,35 @@ pub fn mul_m256d(a: m256d, b: m256d) -> m256d {
   m256d(unsafe { _mm256_mul_pd(a.0, b.0) })
 }
 
+/// Lanewise `a * b + c`, fused when the `fma` module is in the build.
+///
+/// With `fma` this is `fused_mul_add_m256d`, which rounds once. Otherwise
+/// it's a multiply and then an add, which rounds twice. So the result can
+/// differ in the last bit between builds, but this is the same call either
+/// way.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256d::from_array([1.0, 2.0, 3.0, 4.0]);
+/// let c = mul_add_m256d(a, set_splat_m256d(2.0), set_splat_m256d(1.0)).to_array();
+/// assert_eq!(c, [3.0, 5.0, 7.0, 9.0]);
+/// ```
+/// * **Assembly:** one of
+///   * `vfmadd213pd ymm, ymm, ymm`
+///   * `vmulpd ymm, ymm, ymm` then `vaddpd ymm, ymm, ymm`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn mul_add_m256d(a: m256d, b: m256d, c: m256d) -> m256d {
+  #[cfg(all(target_feature = "fma", feature = "fma"))]
+  {
+    fused_mul_add_m256d(a, b, c)
+  }
+  #[cfg(not(all(target_feature = "fma", feature = "fma")))]
+  {
+    add_m256d(mul_m256d(a, b), c)
+  }
+}
+
 /// Lanewise `a * b` with `f32` lanes.
 ///
 /// * **Intrinsic:** [``]
@@ -1302,6 +2077,35 @@ pub fn mul_m256(a: m256, b: m256) -> m256 {
   m256(unsafe { _mm256_mul_ps(a.0, b.0) })
 }
 
+/// Lanewise `a * b + c`, fused when the `fma` module is in the build.
+///
+/// With `fma` this is `fused_mul_add_m256`, which rounds once. Otherwise
+/// it's a multiply and then an add, which rounds twice. So the result can
+/// differ in the last bit between builds, but this is the same call either
+/// way.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256::from_array([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+/// let c = mul_add_m256(a, set_splat_m256(2.0), set_splat_m256(1.0)).to_array();
+/// assert_eq!(c, [3.0, 5.0, 7.0, 9.0, 11.0, 13.0, 15.0, 17.0]);
+/// ```
+/// * **Assembly:** one of
+///   * `vfmadd213ps ymm, ymm, ymm`
+///   * `vmulps ymm, ymm, ymm` then `vaddps ymm, ymm, ymm`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn mul_add_m256(a: m256, b: m256, c: m256) -> m256 {
+  #[cfg(all(target_feature = "fma", feature = "fma"))]
+  {
+    fused_mul_add_m256(a, b, c)
+  }
+  #[cfg(not(all(target_feature = "fma", feature = "fma")))]
+  {
+    add_m256(mul_m256(a, b), c)
+  }
+}
+
 /// Bitwise `a | b`.
 ///
 /// * **Intrinsic:** [``]
@@ -1332,6 +2136,7 @@ pub fn bitor_m256(a: m256, b: m256) -> m256 {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn permute_m128d<const MASK: i32>(a: m128d) -> m128d {
+  let () = ImmBits::<MASK, 2>::OK;
   m128d(unsafe { _mm_permute_pd(a.0, MASK) })
 }
 
@@ -1344,6 +2149,7 @@ pub fn permute_m128d<const MASK: i32>(a: m128d) -> m128d {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn permute_m256d<const MASK: i32>(a: m256d) -> m256d {
+  let () = ImmBits::<MASK, 4>::OK;
   m256d(unsafe { _mm256_permute_pd(a.0, MASK) })
 }
 
@@ -1355,6 +2161,7 @@ pub fn permute_m256d<const MASK: i32>(a: m256d) -> m256d {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn permute_m128<const MASK: i32>(a: m128) -> m128 {
+  let () = ImmBits::<MASK, 8>::OK;
   m128(unsafe { _mm_permute_ps(a.0, MASK) })
 }
 
@@ -1366,6 +2173,7 @@ pub fn permute_m128<const MASK: i32>(a: m128) -> m128 {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn permute_m256<const MASK: i32>(a: m256) -> m256 {
+  let () = ImmBits::<MASK, 8>::OK;
   m256(unsafe { _mm256_permute_ps(a.0, MASK) })
 }
 
@@ -1381,6 +2189,7 @@ pub fn permute_m256<const MASK: i32>(a: m256) -> m256 {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn permute2z_m256d<const MASK: i32>(a: m256d, b: m256d) -> m256d {
+  let () = ImmBits::<MASK, 8>::OK;
   m256d(unsafe { _mm256_permute2f128_pd(a.0, b.0, MASK) })
 }
 
@@ -1396,6 +2205,7 @@ pub fn permute2z_m256d<const MASK: i32>(a: m256d, b: m256d) -> m256d {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn permute2z_m256<const MASK: i32>(a: m256, b: m256) -> m256 {
+  let () = ImmBits::<MASK, 8>::OK;
   m256(unsafe { _mm256_permute2f128_ps(a.0, b.0, MASK) })
 }
 
@@ -1414,6 +2224,7 @@ pub fn permute2z_m256<const MASK: i32>(a: m256, b: m256) -> m256 {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn permute2z_m256i<const MASK: i32>(a: m256i, b: m256i) -> m256i {
+  let () = ImmBits::<MASK, 8>::OK;
   m256i(unsafe { _mm256_permute2f128_si256(a.0, b.0, MASK) })
 }
 
@@ -1469,6 +2280,9 @@ pub fn shuffle_av_f32_half_m256(a: m256, v: m256i) -> m256 {
 
 /// Reciprocal of `f32` lanes.
 ///
+/// This is an approximation with a relative error of at most `1.5 * 2^-12`.
+/// See [`reciprocal_refined_m256`] for a more accurate version.
+///
 /// * **Intrinsic:** [``]
 /// * **Assembly:**
 #[must_use]
@@ -1478,29 +2292,65 @@ pub fn reciprocal_m256(a: m256) -> m256 {
   m256(unsafe { _mm256_rcp_ps(a.0) })
 }
 
+/// Reciprocal of `f32` lanes, refined with one Newton-Raphson step.
+///
+/// This works like [`reciprocal_refined_m128`], but twice as wide. The
+/// relative error is about `2^-22`, and `0.0` or infinite lanes come out as
+/// NaN.
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn reciprocal_refined_m256(a: m256) -> m256 {
+  let x = reciprocal_m256(a);
+  mul_m256(x, sub_m256(set_splat_m256(2.0), mul_m256(a, x)))
+}
+
 /// Rounds each lane in the style specified.
 ///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// The `OP` is usually a [`RoundMode`] immediate.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256d::from_array([-0.1, 1.6, 2.5, -2.5]);
+/// assert_eq!(round_m256d::<{ RoundMode::Nearest.imm() }>(a).to_array(), [0.0, 2.0, 2.0, -2.0]);
+/// assert_eq!(round_m256d::<{ RoundMode::Floor.imm() }>(a).to_array(), [-1.0, 1.0, 2.0, -3.0]);
+/// assert_eq!(round_m256d::<{ RoundMode::Ceil.imm() }>(a).to_array(), [0.0, 2.0, 3.0, -2.0]);
+/// assert_eq!(round_m256d::<{ RoundMode::TowardZero.imm() }>(a).to_array(), [0.0, 1.0, 2.0, -2.0]);
+/// ```
+/// * **Intrinsic:** [`_mm256_round_pd`]
+/// * **Assembly:** `vroundpd ymm, ymm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn round_m256d<const OP: i32>(a: m256d) -> m256d {
+  let () = ImmBits::<OP, 4>::OK;
   m256d(unsafe { _mm256_round_pd(a.0, OP) })
 }
 
 /// Rounds each lane in the style specified.
 ///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// The `OP` is usually a [`RoundMode`] immediate.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256::from_array([-0.1, 1.6, 2.5, -2.5, 3.5, 0.5, -7.9, 7.9]);
+/// let c = round_m256::<{ RoundMode::Nearest.imm() }>(a).to_array();
+/// assert_eq!(c, [0.0, 2.0, 2.0, -2.0, 4.0, 0.0, -8.0, 8.0]);
+/// let c = round_m256::<{ RoundMode::TowardZero.imm() }>(a).to_array();
+/// assert_eq!(c, [0.0, 1.0, 2.0, -2.0, 3.0, 0.0, -7.0, 7.0]);
+/// ```
+/// * **Intrinsic:** [`_mm256_round_ps`]
+/// * **Assembly:** `vroundps ymm, ymm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn round_m256<const OP: i32>(a: m256) -> m256 {
+  let () = ImmBits::<OP, 4>::OK;
   m256(unsafe { _mm256_round_ps(a.0, OP) })
 }
 
-/// Reciprocal of `f32` lanes.
+/// Reciprocal square root of `f32` lanes.
+///
+/// This is an approximation with a relative error of at most `1.5 * 2^-12`.
+/// See [`reciprocal_sqrt_refined_m256`] for a more accurate version.
 ///
 /// * **Intrinsic:** [``]
 /// * **Assembly:**
@@ -1511,6 +2361,79 @@ pub fn reciprocal_sqrt_m256(a: m256) -> m256 {
   m256(unsafe { _mm256_rsqrt_ps(a.0) })
 }
 
+/// Reciprocal square root of `f32` lanes, refined with one Newton-Raphson step.
+///
+/// This works like [`reciprocal_sqrt_refined_m128`], but twice as wide. The
+/// relative error is about `2^-22`, and `0.0` or infinite lanes come out as
+/// NaN.
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn reciprocal_sqrt_refined_m256(a: m256) -> m256 {
+  let y = reciprocal_sqrt_m256(a);
+  let half_a_y_y = mul_m256(mul_m256(set_splat_m256(0.5), a), mul_m256(y, y));
+  mul_m256(y, sub_m256(set_splat_m256(1.5), half_a_y_y))
+}
+
+/// Adds all eight lanes of `a` together.
+///
+/// This is a shuffle and add tree, so the order of the additions isn't
+/// left-to-right (which can matter for float rounding).
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn reduce_add_m256(a: m256) -> f32 {
+  let low = cast_to_m128_from_m256(a);
+  let high = extract_m128_from_m256::<1>(a);
+  reduce_add_m128(add_m128(low, high))
+}
+
+/// The minimum of all eight lanes of `a`.
+///
+/// If any lane is NaN the result is unspecified, the same as with
+/// [`min_m256`].
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn reduce_min_m256(a: m256) -> f32 {
+  let low = cast_to_m128_from_m256(a);
+  let high = extract_m128_from_m256::<1>(a);
+  let quad = min_m128(low, high);
+  let pairs = min_m128(quad, move_high_low_m128(quad, quad));
+  let one = shuffle_abi_f32_all_m128::<0b01>(pairs, pairs);
+  get_f32_from_m128_s(min_m128_s(pairs, one))
+}
+
+/// The maximum of all eight lanes of `a`.
+///
+/// If any lane is NaN the result is unspecified, the same as with
+/// [`max_m256`].
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn reduce_max_m256(a: m256) -> f32 {
+  let low = cast_to_m128_from_m256(a);
+  let high = extract_m128_from_m256::<1>(a);
+  let quad = max_m128(low, high);
+  let pairs = max_m128(quad, move_high_low_m128(quad, quad));
+  let one = shuffle_abi_f32_all_m128::<0b01>(pairs, pairs);
+  get_f32_from_m128_s(max_m128_s(pairs, one))
+}
+
+/// Inclusive prefix sum of the `f32` lanes.
+///
+/// Output lane `n` is the sum of input lanes `0..=n`, including across the
+/// boundary between the two 128 bit halves.
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn prefix_sum_m256(a: m256) -> m256 {
+  let low = prefix_sum_m128(cast_to_m128_from_m256(a));
+  let high = prefix_sum_m128(extract_m128_from_m256::<1>(a));
+  let carry = shuffle_abi_f32_all_m128::<0b11_11_11_11>(low, low);
+  set_m128_m256(add_m128(high, carry), low)
+}
+
 /// Set `i8` args into an `m256i` lane.
 ///
 /// * **Intrinsic:** [``]
@@ -1578,8 +2501,10 @@ pub fn set_i64_m256i(e3: i64, e2: i64, e1: i64, e0: i64) -> m256i {
 
 /// Set `m128` args into an `m256`.
 ///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// This undoes [`split_m256`].
+///
+/// * **Intrinsic:** [`_mm256_set_m128`]
+/// * **Assembly:** `vinsertf128 ymm, ymm, xmm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
@@ -1589,8 +2514,10 @@ pub fn set_m128_m256(high: m128, low: m128) -> m256 {
 
 /// Set `m128d` args into an `m256d`.
 ///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// This undoes [`split_m256d`].
+///
+/// * **Intrinsic:** [`_mm256_set_m128d`]
+/// * **Assembly:** `vinsertf128 ymm, ymm, xmm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
@@ -1603,8 +2530,10 @@ pub fn set_m128d_m256d(
 
 /// Set `m128i` args into an `m256i`.
 ///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// This undoes [`split_m256i`].
+///
+/// * **Intrinsic:** [`_mm256_set_m128i`]
+/// * **Assembly:** `vinsertf128 ymm, ymm, xmm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
@@ -1615,6 +2544,64 @@ pub fn set_m128i_m256i(
   m256i(unsafe { _mm256_set_m128i(hi.0, lo.0) })
 }
 
+/// Splits an `m256` into its `(high, low)` halves.
+///
+/// This undoes [`set_m128_m256`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m256::from_array([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+/// let (high, low) = split_m256(a);
+/// assert_eq!(high.to_array(), [5.0, 6.0, 7.0, 8.0]);
+/// assert_eq!(low.to_array(), [1.0, 2.0, 3.0, 4.0]);
+/// assert_eq!(set_m128_m256(high, low).to_array(), a.to_array());
+/// ```
+/// * **Intrinsic:** [`_mm256_extractf128_ps`]
+/// * **Assembly:** `vextractf128 xmm, ymm, imm8`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn split_m256(a: m256) -> (m128, m128) {
+  (extract_m128_from_m256::<1>(a), cast_to_m128_from_m256(a))
+}
+
+/// Splits an `m256d` into its `(high, low)` halves.
+///
+/// This undoes [`set_m128d_m256d`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m256d::from_array([1.0, 2.0, 3.0, 4.0]);
+/// let (high, low) = split_m256d(a);
+/// assert_eq!(high.to_array(), [3.0, 4.0]);
+/// assert_eq!(low.to_array(), [1.0, 2.0]);
+/// ```
+/// * **Intrinsic:** [`_mm256_extractf128_pd`]
+/// * **Assembly:** `vextractf128 xmm, ymm, imm8`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn split_m256d(a: m256d) -> (m128d, m128d) {
+  (extract_m128d_from_m256d::<1>(a), cast_to_m128d_from_m256d(a))
+}
+
+/// Splits an `m256i` into its `(high, low)` halves.
+///
+/// This undoes [`set_m128i_m256i`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([5_u128, 6_u128]);
+/// let (high, low) = split_m256i(a);
+/// assert_eq!(u128::from(high), 6);
+/// assert_eq!(u128::from(low), 5);
+/// ```
+/// * **Intrinsic:** [`_mm256_extractf128_si256`]
+/// * **Assembly:** `vextractf128 xmm, ymm, imm8`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn split_m256i(a: m256i) -> (m128i, m128i) {
+  (extract_m128i_from_m256i::<1>(a), cast_to_m128i_from_m256i(a))
+}
+
 /// Set `f64` args into an `m256d` lane.
 ///
 /// * **Intrinsic:** [``]
@@ -1652,8 +2639,8 @@ pub fn set_m256(
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
-pub fn set_splat_i8_m256i(i: i8) -> m256i {
-  m256i(unsafe { _mm256_set1_epi8(i) })
+pub const fn set_splat_i8_m256i(i: i8) -> m256i {
+  unsafe { core::mem::transmute([i; 32]) }
 }
 
 /// Splat an `i16` arg into an `m256i` lane.
@@ -1663,8 +2650,8 @@ pub fn set_splat_i8_m256i(i: i8) -> m256i {
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
-pub fn set_splat_i16_m256i(i: i16) -> m256i {
-  m256i(unsafe { _mm256_set1_epi16(i) })
+pub const fn set_splat_i16_m256i(i: i16) -> m256i {
+  unsafe { core::mem::transmute([i; 16]) }
 }
 
 /// Splat an `i32` arg into an `m256i` lane.
@@ -1674,8 +2661,8 @@ pub fn set_splat_i16_m256i(i: i16) -> m256i {
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
-pub fn set_splat_i32_m256i(i: i32) -> m256i {
-  m256i(unsafe { _mm256_set1_epi32(i) })
+pub const fn set_splat_i32_m256i(i: i32) -> m256i {
+  unsafe { core::mem::transmute([i; 8]) }
 }
 
 /// Splat an `i64` arg into an `m256i` lane.
@@ -1684,10 +2671,9 @@ pub fn set_splat_i32_m256i(i: i32) -> m256i {
 /// * **Assembly:**
 #[must_use]
 #[inline(always)]
-#[cfg(target_arch = "x86_64")]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
-pub fn set_splat_i64_m256i(i: i64) -> m256i {
-  m256i(unsafe { _mm256_set1_epi64x(i) })
+pub const fn set_splat_i64_m256i(i: i64) -> m256i {
+  unsafe { core::mem::transmute([i; 4]) }
 }
 
 /// Splat an `f64` arg into an `m256d` lane.
@@ -1697,8 +2683,8 @@ pub fn set_splat_i64_m256i(i: i64) -> m256i {
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
-pub fn set_splat_m256d(f: f64) -> m256d {
-  m256d(unsafe { _mm256_set1_pd(f) })
+pub const fn set_splat_m256d(f: f64) -> m256d {
+  m256d::from_array([f; 4])
 }
 
 /// Splat an `f32` arg into an `m256` lane.
@@ -1709,12 +2695,10 @@ pub fn set_splat_m256d(f: f64) -> m256d {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 #[rustfmt::skip]
-pub fn set_splat_m256(
+pub const fn set_splat_m256(
   f: f32,
 ) -> m256 {
-  m256(unsafe {
-    _mm256_set1_ps(f)
-  })
+  m256::from_array([f; 8])
 }
 
 /// Set `i8` args into an `m256i` lane.
@@ -1858,8 +2842,8 @@ pub fn set_reversed_m256(
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
-pub fn zeroed_m256d() -> m256d {
-  m256d(unsafe { _mm256_setzero_pd() })
+pub const fn zeroed_m256d() -> m256d {
+  m256d::from_array([0.0; 4])
 }
 
 /// A zeroed `m256`
@@ -1869,8 +2853,8 @@ pub fn zeroed_m256d() -> m256d {
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
-pub fn zeroed_m256() -> m256 {
-  m256(unsafe { _mm256_setzero_ps() })
+pub const fn zeroed_m256() -> m256 {
+  m256::from_array([0.0; 8])
 }
 
 /// A zeroed `m256i`
@@ -1880,14 +2864,14 @@ pub fn zeroed_m256() -> m256 {
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
-pub fn zeroed_m256i() -> m256i {
-  m256i(unsafe { _mm256_setzero_si256() })
+pub const fn zeroed_m256i() -> m256i {
+  unsafe { core::mem::transmute([0_u64; 4]) }
 }
 
 /// Shuffle the `f64` lanes from `a` and `b` together using an immediate control
 /// value.
 ///
-/// The control value uses the lowest 4 bits only.
+/// The control value must fit in 4 bits.
 /// * bit 0 picks between lanes 0 or 1 from A.
 /// * bit 1 picks between lanes 0 or 1 from B.
 /// * bit 2 picks between lanes 2 or 3 from A.
@@ -1902,6 +2886,7 @@ pub fn zeroed_m256i() -> m256i {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn shuffle_m256d<const IMM: i32>(a: m256d, b: m256d) -> m256d {
+  let () = ImmBits::<IMM, 4>::OK;
   m256d(unsafe { _mm256_shuffle_pd(a.0, b.0, IMM) })
 }
 
@@ -1919,6 +2904,7 @@ pub fn shuffle_m256d<const IMM: i32>(a: m256d, b: m256d) -> m256d {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
 pub fn shuffle_m256<const IMM: i32>(a: m256, b: m256) -> m256 {
+  let () = ImmBits::<IMM, 8>::OK;
   m256(unsafe { _mm256_shuffle_ps(a.0, b.0, IMM) })
 }
 
@@ -1974,6 +2960,38 @@ pub fn store_m256i(addr: &mut m256i, a: m256i) {
   unsafe { _mm256_store_si256(addr as *mut m256i as *mut __m256i, a.0) }
 }
 
+impl<'a> StreamingStores<'a> {
+  /// Stores the value to the reference given, bypassing the cache.
+  ///
+  /// * **Intrinsic:** [`_mm256_stream_ps`]
+  /// * **Assembly:** `vmovntps m256, ymm`
+  #[inline(always)]
+  #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+  pub fn store_nontemporal_m256(&mut self, r: &'a mut m256, a: m256) {
+    unsafe { _mm256_stream_ps(r as *mut m256 as *mut f32, a.0) }
+  }
+
+  /// Stores the value to the reference given, bypassing the cache.
+  ///
+  /// * **Intrinsic:** [`_mm256_stream_pd`]
+  /// * **Assembly:** `vmovntpd m256, ymm`
+  #[inline(always)]
+  #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+  pub fn store_nontemporal_m256d(&mut self, r: &'a mut m256d, a: m256d) {
+    unsafe { _mm256_stream_pd(r as *mut m256d as *mut f64, a.0) }
+  }
+
+  /// Stores the value to the reference given, bypassing the cache.
+  ///
+  /// * **Intrinsic:** [`_mm256_stream_si256`]
+  /// * **Assembly:** `vmovntdq m256, ymm`
+  #[inline(always)]
+  #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+  pub fn store_nontemporal_m256i(&mut self, r: &'a mut m256i, a: m256i) {
+    unsafe { _mm256_stream_si256(r as *mut m256i as *mut __m256i, a.0) }
+  }
+}
+
 /// Store data from a register into memory.
 ///
 /// * **Intrinsic:** [``]
@@ -2056,6 +3074,124 @@ pub fn sub_m256(a: m256, b: m256) -> m256 {
   m256(unsafe { _mm256_sub_ps(a.0, b.0) })
 }
 
+/// Transpose eight `m256` as if they were an 8x8 matrix.
+///
+/// Each register is one row going in, and one column coming out.
+///
+/// This is built from [`unpack_lo_m256`], [`unpack_hi_m256`],
+/// [`shuffle_m256`], and [`permute2z_m256`].
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn transpose_8x8_m256(rows: &mut [m256; 8]) {
+  let [r0, r1, r2, r3, r4, r5, r6, r7] = *rows;
+  let t0 = unpack_lo_m256(r0, r1);
+  let t1 = unpack_hi_m256(r0, r1);
+  let t2 = unpack_lo_m256(r2, r3);
+  let t3 = unpack_hi_m256(r2, r3);
+  let t4 = unpack_lo_m256(r4, r5);
+  let t5 = unpack_hi_m256(r4, r5);
+  let t6 = unpack_lo_m256(r6, r7);
+  let t7 = unpack_hi_m256(r6, r7);
+  let u0 = shuffle_m256::<0b01_00_01_00>(t0, t2);
+  let u1 = shuffle_m256::<0b11_10_11_10>(t0, t2);
+  let u2 = shuffle_m256::<0b01_00_01_00>(t1, t3);
+  let u3 = shuffle_m256::<0b11_10_11_10>(t1, t3);
+  let u4 = shuffle_m256::<0b01_00_01_00>(t4, t6);
+  let u5 = shuffle_m256::<0b11_10_11_10>(t4, t6);
+  let u6 = shuffle_m256::<0b01_00_01_00>(t5, t7);
+  let u7 = shuffle_m256::<0b11_10_11_10>(t5, t7);
+  *rows = [
+    permute2z_m256::<0x20>(u0, u4),
+    permute2z_m256::<0x20>(u1, u5),
+    permute2z_m256::<0x20>(u2, u6),
+    permute2z_m256::<0x20>(u3, u7),
+    permute2z_m256::<0x31>(u0, u4),
+    permute2z_m256::<0x31>(u1, u5),
+    permute2z_m256::<0x31>(u2, u6),
+    permute2z_m256::<0x31>(u3, u7),
+  ];
+}
+
+/// Transpose four `m256d` as if they were a 4x4 matrix.
+///
+/// Each register is one row going in, and one column coming out.
+///
+/// This is built from [`unpack_lo_m256d`], [`unpack_hi_m256d`], and
+/// [`permute2z_m256d`].
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn transpose_4x4_m256d(rows: &mut [m256d; 4]) {
+  let [r0, r1, r2, r3] = *rows;
+  let t0 = unpack_lo_m256d(r0, r1);
+  let t1 = unpack_hi_m256d(r0, r1);
+  let t2 = unpack_lo_m256d(r2, r3);
+  let t3 = unpack_hi_m256d(r2, r3);
+  *rows = [
+    permute2z_m256d::<0x20>(t0, t2),
+    permute2z_m256d::<0x20>(t1, t3),
+    permute2z_m256d::<0x31>(t0, t2),
+    permute2z_m256d::<0x31>(t1, t3),
+  ];
+}
+
+/// Splits 8 values of 3 channels each (such as `xyz` points) into one register
+/// per channel.
+///
+/// This is [`deinterleave_3_m128`] at twice the width. The halves are first
+/// rearranged so that each 128-bit half holds 4 whole points, and then both
+/// halves do the same shuffles.
+/// ```
+/// # use safe_arch::*;
+/// let mut aos = [[0.0_f32; 8]; 3];
+/// for i in 0..24 {
+///   aos[i / 8][i % 8] = ((i % 3) * 10 + i / 3) as f32;
+/// }
+/// let [a, b, c] = aos.map(m256::from_array);
+/// let [x, y, z] = deinterleave_3_m256([a, b, c]);
+/// assert_eq!(x.to_array(), [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]);
+/// assert_eq!(y.to_array(), [10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0, 17.0]);
+/// assert_eq!(z.to_array(), [20.0, 21.0, 22.0, 23.0, 24.0, 25.0, 26.0, 27.0]);
+/// let [a2, b2, c2] = interleave_3_m256([x, y, z]);
+/// assert_eq!((a2.to_array(), b2.to_array(), c2.to_array()), (a.to_array(), b.to_array(), c.to_array()));
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn deinterleave_3_m256(aos: [m256; 3]) -> [m256; 3] {
+  let [a, b, c] = aos;
+  // Points 0..4 go in the low halves, and points 4..8 in the high halves.
+  let (a, b, c) = (permute2z_m256::<0x30>(a, b), permute2z_m256::<0x21>(a, c), permute2z_m256::<0x30>(b, c));
+  let t = shuffle_m256::<0b01_01_10_10>(b, c);
+  let x = shuffle_m256::<0b10_00_11_00>(a, t);
+  let t = shuffle_m256::<0b00_00_01_01>(a, b);
+  let u = shuffle_m256::<0b10_10_11_11>(b, c);
+  let y = shuffle_m256::<0b10_00_10_00>(t, u);
+  let t = shuffle_m256::<0b01_01_10_10>(a, b);
+  let z = shuffle_m256::<0b11_00_10_00>(t, c);
+  [x, y, z]
+}
+
+/// Merges one register per channel into 8 values of 3 channels each.
+///
+/// This undoes [`deinterleave_3_m256`].
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn interleave_3_m256(soa: [m256; 3]) -> [m256; 3] {
+  let [x, y, z] = soa;
+  let t = shuffle_m256::<0b00_00_00_00>(x, y);
+  let u = shuffle_m256::<0b01_01_00_00>(z, x);
+  let a = shuffle_m256::<0b10_00_10_00>(t, u);
+  let t = shuffle_m256::<0b01_01_01_01>(y, z);
+  let u = shuffle_m256::<0b10_10_10_10>(x, y);
+  let b = shuffle_m256::<0b10_00_10_00>(t, u);
+  let t = shuffle_m256::<0b11_11_10_10>(z, x);
+  let u = shuffle_m256::<0b11_11_11_11>(y, z);
+  let c = shuffle_m256::<0b10_00_10_00>(t, u);
+  // Each half holds 4 whole points, so put them back in memory order.
+  [permute2z_m256::<0x20>(a, b), permute2z_m256::<0x30>(c, a), permute2z_m256::<0x31>(b, c)]
+}
+
 /// Unpack and interleave the high lanes.
 ///
 /// * **Intrinsic:** [``]
@@ -2111,6 +3247,65 @@ pub fn bitxor_m256d(a: m256d, b: m256d) -> m256d {
   m256d(unsafe { _mm256_xor_pd(a.0, b.0) })
 }
 
+/// Lanewise `-a`, by flipping the sign bit.
+///
+/// Unlike `0.0 - a`, this turns `0.0` into `-0.0` (and back), and it flips the
+/// sign of NaN lanes too, as [`f64`]'s `Neg` does.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256d::from_array([1.0, -2.0, 0.0, -0.0]);
+/// let c = negate_m256d(a).to_array();
+/// let expected: [f64; 4] = [-1.0, 2.0, -0.0, 0.0];
+/// for (c, e) in c.iter().zip(expected.iter()) {
+///   assert_eq!(c.to_bits(), e.to_bits());
+/// }
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn negate_m256d(a: m256d) -> m256d {
+  bitxor_m256d(a, set_splat_m256d(-0.0))
+}
+
+/// Lanewise `|a|`, by clearing the sign bit.
+///
+/// This matches [`f64::abs`], including for `-0.0` and NaN lanes.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256d::from_array([1.0, -2.0, 0.0, -0.0]);
+/// let c = abs_m256d(a).to_array();
+/// let expected: [f64; 4] = [1.0, 2.0, 0.0, 0.0];
+/// for (c, e) in c.iter().zip(expected.iter()) {
+///   assert_eq!(c.to_bits(), e.to_bits());
+/// }
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn abs_m256d(a: m256d) -> m256d {
+  bitandnot_m256d(set_splat_m256d(-0.0), a)
+}
+
+/// Lanewise the magnitude of `magnitude` with the sign bit of `sign`.
+///
+/// This matches [`f64::copysign`], so the sign of zero and NaN lanes counts.
+/// ```
+/// # use safe_arch::*;
+/// let magnitude = m256d::from_array([1.0, -2.0, 0.0, -0.0]);
+/// let sign = m256d::from_array([-1.0, 1.0, -0.0, 0.0]);
+/// let c = copysign_m256d(magnitude, sign).to_array();
+/// for ((c, m), s) in c.iter().zip(magnitude.to_array().iter()).zip(sign.to_array().iter()) {
+///   assert_eq!(c.to_bits(), m.copysign(*s).to_bits());
+/// }
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn copysign_m256d(magnitude: m256d, sign: m256d) -> m256d {
+  let sign_bit = set_splat_m256d(-0.0);
+  bitor_m256d(bitandnot_m256d(sign_bit, magnitude), bitand_m256d(sign_bit, sign))
+}
+
 /// Bitwise `a ^ b`.
 ///
 /// * **Intrinsic:** [``]
@@ -2122,10 +3317,72 @@ pub fn bitxor_m256(a: m256, b: m256) -> m256 {
   m256(unsafe { _mm256_xor_ps(a.0, b.0) })
 }
 
+/// Lanewise `-a`, by flipping the sign bit.
+///
+/// Unlike `0.0 - a`, this turns `0.0` into `-0.0` (and back), and it flips the
+/// sign of NaN lanes too, as [`f32`]'s `Neg` does.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256::from_array([1.0, -2.0, 0.0, -0.0, f32::INFINITY, -3.5, f32::NAN, 7.0]);
+/// let c = negate_m256(a).to_array();
+/// let expected: [f32; 8] = [-1.0, 2.0, -0.0, 0.0, -f32::INFINITY, 3.5, -f32::NAN, -7.0];
+/// for (c, e) in c.iter().zip(expected.iter()) {
+///   assert_eq!(c.to_bits(), e.to_bits());
+/// }
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn negate_m256(a: m256) -> m256 {
+  bitxor_m256(a, set_splat_m256(-0.0))
+}
+
+/// Lanewise `|a|`, by clearing the sign bit.
+///
+/// This matches [`f32::abs`], including for `-0.0` and NaN lanes.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256::from_array([1.0, -2.0, 0.0, -0.0, f32::INFINITY, -3.5, f32::NAN, 7.0]);
+/// let c = abs_m256(a).to_array();
+/// let expected: [f32; 8] = [1.0, 2.0, 0.0, 0.0, f32::INFINITY, 3.5, f32::NAN, 7.0];
+/// for (c, e) in c.iter().zip(expected.iter()) {
+///   assert_eq!(c.to_bits(), e.to_bits());
+/// }
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn abs_m256(a: m256) -> m256 {
+  bitandnot_m256(set_splat_m256(-0.0), a)
+}
+
+/// Lanewise the magnitude of `magnitude` with the sign bit of `sign`.
+///
+/// This matches [`f32::copysign`], so the sign of zero and NaN lanes counts.
+/// ```
+/// # use safe_arch::*;
+/// let magnitude = m256::from_array([1.0, -2.0, 0.0, -0.0, f32::INFINITY, -3.5, f32::NAN, 7.0]);
+/// let sign = m256::from_array([-1.0, 1.0, -0.0, 0.0, -1.0, 1.0, -1.0, -1.0]);
+/// let c = copysign_m256(magnitude, sign).to_array();
+/// for ((c, m), s) in c.iter().zip(magnitude.to_array().iter()).zip(sign.to_array().iter()) {
+///   assert_eq!(c.to_bits(), m.copysign(*s).to_bits());
+/// }
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub fn copysign_m256(magnitude: m256, sign: m256) -> m256 {
+  let sign_bit = set_splat_m256(-0.0);
+  bitor_m256(bitandnot_m256(sign_bit, magnitude), bitand_m256(sign_bit, sign))
+}
+
 /// Zero extend an `m128d` to `m256d`
 ///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// Unlike a cast, the high half of the output is defined to be zero.
+///
+/// * **Intrinsic:** [`_mm256_zextpd128_pd256`]
+/// * **Assembly:** `vmovaps xmm, xmm` (or nothing, if the high half is
+///   already zero)
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
@@ -2135,8 +3392,11 @@ pub fn zero_extend_m128d(a: m128d) -> m256d {
 
 /// Zero extend an `m128` to `m256`
 ///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// Unlike a cast, the high half of the output is defined to be zero.
+///
+/// * **Intrinsic:** [`_mm256_zextps128_ps256`]
+/// * **Assembly:** `vmovaps xmm, xmm` (or nothing, if the high half is
+///   already zero)
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
@@ -2146,8 +3406,11 @@ pub fn zero_extend_m128(a: m128) -> m256 {
 
 /// Zero extend an `m128i` to `m256i`
 ///
-/// * **Intrinsic:** [``]
-/// * **Assembly:**
+/// Unlike a cast, the high half of the output is defined to be zero.
+///
+/// * **Intrinsic:** [`_mm256_zextsi128_si256`]
+/// * **Assembly:** `vmovaps xmm, xmm` (or nothing, if the high half is
+///   already zero)
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
@@ -22,6 +22,7 @@ use super::*;
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
 pub fn blend_imm_i32_m128i<const IMM: i32>(a: m128i, b: m128i) -> m128i {
+  let () = ImmBits::<IMM, 4>::OK;
   m128i(unsafe { _mm_blend_epi32(a.0, b.0, IMM) })
 }
 
@@ -1211,12 +1212,13 @@ pub fn extract_i8_as_i32_m256i<const LANE: i32>(a: m256i) -> i32 {
 /// assert_eq!(extract_m128i_m256i::<0>(a), m128i::from(5_u128));
 /// assert_eq!(extract_m128i_m256i::<1>(a), m128i::from(6_u128));
 /// ```
-/// * **Intrinsic:** [`_mm256_extract_epi8`]
+/// * **Intrinsic:** [`_mm256_extracti128_si256`]
 /// * **Assembly:** `vextracti128 xmm, ymm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
 pub fn extract_m128i_m256i<const LANE: i32>(a: m256i) -> m128i {
+  let () = ImmBits::<LANE, 1>::OK;
   m128i(unsafe { _mm256_extracti128_si256(a.0, LANE) })
 }
 
@@ -1289,6 +1291,24 @@ pub fn add_horizontal_i32_m256i(a: m256i, b: m256i) -> m256i {
   m256i(unsafe { _mm256_hadd_epi32(a.0, b.0) })
 }
 
+/// Adds all eight `i32` lanes of `a` together, wrapping on overflow.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([1, 2, 3, 4, 5, 6, 7, i32::MAX]);
+/// assert_eq!(reduce_add_i32_m256i(a), i32::MAX.wrapping_add(28));
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn reduce_add_i32_m256i(a: m256i) -> i32 {
+  let low = cast_to_m128i_from_m256i(a);
+  let high = extract_m128i_m256i::<1>(a);
+  let quad = add_i32_m128i(low, high);
+  let pairs = add_i32_m128i(quad, shuffle_ai_f32_all_m128i::<0b01_00_11_10>(quad));
+  let one = shuffle_ai_f32_all_m128i::<0b10_11_00_01>(pairs);
+  get_i32_from_m128i_s(add_i32_m128i(pairs, one))
+}
+
 /// Horizontal `a - b` with lanes as `i16`.
 ///
 /// * The results are interleaved 128-bits at a time: a.low, b.low, a.high,
@@ -1376,6 +1396,23 @@ pub fn mul_i16_horizontal_add_m256i(a: m256i, b: m256i) -> m256i {
   m256i(unsafe { _mm256_madd_epi16(a.0, b.0) })
 }
 
+/// The dot product of the `i16` lanes of `a` and `b`, as an `i32`.
+///
+/// The sum wraps on overflow, which needs most of the lanes to be near
+/// `i16::MIN` or `i16::MAX`.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([1_i16, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+/// let b = m256i::from([2_i16; 16]);
+/// assert_eq!(dot_product_i16_m256i(a, b), 272);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn dot_product_i16_m256i(a: m256i, b: m256i) -> i32 {
+  reduce_add_i32_m256i(mul_i16_horizontal_add_m256i(a, b))
+}
+
 /// This is dumb and weird.
 ///
 /// * Vertically multiplies each `u8` lane from `a` with an `i8` lane from `b`,
@@ -1447,6 +1484,48 @@ pub fn load_masked_i64_m256i(a: &m256i, mask: m256i) -> m256i {
   m256i(unsafe { _mm256_maskload_epi64(a as *const m256i as *const i64, mask.0) })
 }
 
+/// Loads up to 8 `i32` lanes from the slice, zeroing any lanes past the end.
+///
+/// Only the elements that are actually in the slice are read, so this is safe
+/// to use on the tail of a buffer.
+/// ```
+/// # use safe_arch::*;
+/// let data = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+/// let a = load_partial_i32_m256i(&data[..]);
+/// assert_eq!(<[i32; 8]>::from(a), [1, 2, 3, 4, 5, 6, 7, 8]);
+/// let b = load_partial_i32_m256i(&data[8..]);
+/// assert_eq!(<[i32; 8]>::from(b), [9, 10, 0, 0, 0, 0, 0, 0]);
+/// ```
+/// * **Intrinsic:** [`_mm256_maskload_epi32`]
+/// * **Assembly:** `vpmaskmovd ymm, ymm, m256`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn load_partial_i32_m256i(a: &[i32]) -> m256i {
+  let mask = partial_mask_i32_m256i(a.len());
+  m256i(unsafe { _mm256_maskload_epi32(a.as_ptr(), mask.0) })
+}
+
+/// Loads up to 4 `i64` lanes from the slice, zeroing any lanes past the end.
+///
+/// Only the elements that are actually in the slice are read, so this is safe
+/// to use on the tail of a buffer.
+/// ```
+/// # use safe_arch::*;
+/// let data = [1_i64, 2, 3];
+/// let a = load_partial_i64_m256i(&data[..]);
+/// assert_eq!(<[i64; 4]>::from(a), [1, 2, 3, 0]);
+/// ```
+/// * **Intrinsic:** [`_mm256_maskload_epi64`]
+/// * **Assembly:** `vpmaskmovq ymm, ymm, m256`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn load_partial_i64_m256i(a: &[i64]) -> m256i {
+  let mask = partial_mask_i64_m256i(a.len());
+  m256i(unsafe { _mm256_maskload_epi64(a.as_ptr(), mask.0) })
+}
+
 /// Stores the `i32` masked lanes given to the reference.
 ///
 /// * A lane is "in" the mask if that lane's mask value is set in the high bit
@@ -1489,6 +1568,44 @@ pub fn store_masked_i64_m256i(addr: &mut m256i, mask: m256i, a: m256i) {
   unsafe { _mm256_maskstore_epi64(addr as *mut m256i as *mut i64, mask.0, a.0) };
 }
 
+/// Stores the low `i32` lanes of the register into the slice, up to 8 lanes.
+///
+/// If the slice is shorter than the register, lanes past the end of the slice
+/// are not written.
+/// ```
+/// # use safe_arch::*;
+/// let mut data = [0_i32; 10];
+/// store_partial_i32_m256i(&mut data[5..], m256i::from([1, 2, 3, 4, 5, 6, 7, 8]));
+/// assert_eq!(data, [0, 0, 0, 0, 0, 1, 2, 3, 4, 5]);
+/// ```
+/// * **Intrinsic:** [`_mm256_maskstore_epi32`]
+/// * **Assembly:** `vpmaskmovd m256, ymm, ymm`
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn store_partial_i32_m256i(addr: &mut [i32], a: m256i) {
+  let mask = partial_mask_i32_m256i(addr.len());
+  unsafe { _mm256_maskstore_epi32(addr.as_mut_ptr(), mask.0, a.0) };
+}
+
+/// Stores the low `i64` lanes of the register into the slice, up to 4 lanes.
+///
+/// If the slice is shorter than the register, lanes past the end of the slice
+/// are not written.
+/// ```
+/// # use safe_arch::*;
+/// let mut data = [0_i64; 3];
+/// store_partial_i64_m256i(&mut data[1..], m256i::from([1_i64, 2, 3, 4]));
+/// assert_eq!(data, [0, 1, 2]);
+/// ```
+/// * **Intrinsic:** [`_mm256_maskstore_epi64`]
+/// * **Assembly:** `vpmaskmovq m256, ymm, ymm`
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn store_partial_i64_m256i(addr: &mut [i64], a: m256i) {
+  let mask = partial_mask_i64_m256i(addr.len());
+  unsafe { _mm256_maskstore_epi64(addr.as_mut_ptr(), mask.0, a.0) };
+}
+
 /// Inserts an `m128i` to an `m256i` at the high or low position.
 ///
 /// ```
@@ -1503,6 +1620,7 @@ pub fn store_masked_i64_m256i(addr: &mut m256i, mask: m256i, a: m256i) {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
 pub fn insert_m128i_to_m256i<const LANE: i32>(a: m256i, b: m128i) -> m256i {
+  let () = ImmBits::<LANE, 1>::OK;
   m256i(unsafe { _mm256_inserti128_si256(a.0, b.0, LANE) })
 }
 
@@ -1661,6 +1779,41 @@ pub fn min_i8_m256i(a: m256i, b: m256i) -> m256i {
   m256i(unsafe { _mm256_min_epi8(a.0, b.0) })
 }
 
+/// Lanewise `x` clamped to the range `lo..=hi`, as `i8` values.
+///
+/// When `lo > hi` the output is `hi`.
+/// ```
+/// # use safe_arch::*;
+/// let x = m256i::from([-100_i8, -3, 0, 3, 7, 100, -100, -3, 0, 3, 7, 100, -100, -3, 0, 3, 7, 100, -100, -3, 0, 3, 7, 100, -100, -3, 0, 3, 7, 100, -100, -3]);
+/// let c: [i8; 32] = clamp_i8_m256i(x, set_splat_i8_m256i(-3), set_splat_i8_m256i(7)).into();
+/// assert_eq!(c, [-3, -3, 0, 3, 7, 7, -3, -3, 0, 3, 7, 7, -3, -3, 0, 3, 7, 7, -3, -3, 0, 3, 7, 7, -3, -3, 0, 3, 7, 7, -3, -3]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn clamp_i8_m256i(x: m256i, lo: m256i, hi: m256i) -> m256i {
+  min_i8_m256i(max_i8_m256i(x, lo), hi)
+}
+
+/// Lanewise `|a - b|` of `i8` values.
+///
+/// The difference can be bigger than `i8::MAX`, so the output lanes are
+/// `u8` values, as with [`i8::abs_diff`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([-128_i8, 127, -5, 9, -128, 127, -5, 9, -128, 127, -5, 9, -128, 127, -5, 9, -128, 127, -5, 9, -128, 127, -5, 9, -128, 127, -5, 9, -128, 127, -5, 9]);
+/// let b = m256i::from([127_i8, -128, 9, -5, 127, -128, 9, -5, 127, -128, 9, -5, 127, -128, 9, -5, 127, -128, 9, -5, 127, -128, 9, -5, 127, -128, 9, -5, 127, -128, 9, -5]);
+/// let c: [u8; 32] = abs_diff_i8_m256i(a, b).into();
+/// assert_eq!(c, [255, 255, 14, 14, 255, 255, 14, 14, 255, 255, 14, 14, 255, 255, 14, 14, 255, 255, 14, 14, 255, 255, 14, 14, 255, 255, 14, 14, 255, 255, 14, 14]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn abs_diff_i8_m256i(a: m256i, b: m256i) -> m256i {
+  // The wrapping subtraction gives the right bits once read as unsigned.
+  sub_i8_m256i(max_i8_m256i(a, b), min_i8_m256i(a, b))
+}
+
 /// Lanewise `min(a, b)` with lanes as `i16`.
 /// ```
 /// # use safe_arch::*;
@@ -1678,6 +1831,41 @@ pub fn min_i16_m256i(a: m256i, b: m256i) -> m256i {
   m256i(unsafe { _mm256_min_epi16(a.0, b.0) })
 }
 
+/// Lanewise `x` clamped to the range `lo..=hi`, as `i16` values.
+///
+/// When `lo > hi` the output is `hi`.
+/// ```
+/// # use safe_arch::*;
+/// let x = m256i::from([-100_i16, -3, 0, 3, 7, 100, -100, -3, 0, 3, 7, 100, -100, -3, 0, 3]);
+/// let c: [i16; 16] = clamp_i16_m256i(x, set_splat_i16_m256i(-3), set_splat_i16_m256i(7)).into();
+/// assert_eq!(c, [-3, -3, 0, 3, 7, 7, -3, -3, 0, 3, 7, 7, -3, -3, 0, 3]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn clamp_i16_m256i(x: m256i, lo: m256i, hi: m256i) -> m256i {
+  min_i16_m256i(max_i16_m256i(x, lo), hi)
+}
+
+/// Lanewise `|a - b|` of `i16` values.
+///
+/// The difference can be bigger than `i16::MAX`, so the output lanes are
+/// `u16` values, as with [`i16::abs_diff`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([-32768_i16, 32767, -5, 9, -32768, 32767, -5, 9, -32768, 32767, -5, 9, -32768, 32767, -5, 9]);
+/// let b = m256i::from([32767_i16, -32768, 9, -5, 32767, -32768, 9, -5, 32767, -32768, 9, -5, 32767, -32768, 9, -5]);
+/// let c: [u16; 16] = abs_diff_i16_m256i(a, b).into();
+/// assert_eq!(c, [65535, 65535, 14, 14, 65535, 65535, 14, 14, 65535, 65535, 14, 14, 65535, 65535, 14, 14]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn abs_diff_i16_m256i(a: m256i, b: m256i) -> m256i {
+  // The wrapping subtraction gives the right bits once read as unsigned.
+  sub_i16_m256i(max_i16_m256i(a, b), min_i16_m256i(a, b))
+}
+
 /// Lanewise `min(a, b)` with lanes as `i32`.
 /// ```
 /// # use safe_arch::*;
@@ -1695,6 +1883,41 @@ pub fn min_i32_m256i(a: m256i, b: m256i) -> m256i {
   m256i(unsafe { _mm256_min_epi32(a.0, b.0) })
 }
 
+/// Lanewise `x` clamped to the range `lo..=hi`, as `i32` values.
+///
+/// When `lo > hi` the output is `hi`.
+/// ```
+/// # use safe_arch::*;
+/// let x = m256i::from([-100_i32, -3, 0, 3, 7, 100, -100, -3]);
+/// let c: [i32; 8] = clamp_i32_m256i(x, set_splat_i32_m256i(-3), set_splat_i32_m256i(7)).into();
+/// assert_eq!(c, [-3, -3, 0, 3, 7, 7, -3, -3]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn clamp_i32_m256i(x: m256i, lo: m256i, hi: m256i) -> m256i {
+  min_i32_m256i(max_i32_m256i(x, lo), hi)
+}
+
+/// Lanewise `|a - b|` of `i32` values.
+///
+/// The difference can be bigger than `i32::MAX`, so the output lanes are
+/// `u32` values, as with [`i32::abs_diff`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([-2_000_000_000_i32, 2_000_000_000, -5, 9, -2_000_000_000, 2_000_000_000, -5, 9]);
+/// let b = m256i::from([2_000_000_000_i32, -2_000_000_000, 9, -5, 2_000_000_000, -2_000_000_000, 9, -5]);
+/// let c: [u32; 8] = abs_diff_i32_m256i(a, b).into();
+/// assert_eq!(c, [4_000_000_000, 4_000_000_000, 14, 14, 4_000_000_000, 4_000_000_000, 14, 14]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn abs_diff_i32_m256i(a: m256i, b: m256i) -> m256i {
+  // The wrapping subtraction gives the right bits once read as unsigned.
+  sub_i32_m256i(max_i32_m256i(a, b), min_i32_m256i(a, b))
+}
+
 /// Lanewise `min(a, b)` with lanes as `u8`.
 /// ```
 /// # use safe_arch::*;
@@ -1724,6 +1947,40 @@ pub fn min_u8_m256i(a: m256i, b: m256i) -> m256i {
   m256i(unsafe { _mm256_min_epu8(a.0, b.0) })
 }
 
+/// Lanewise `x` clamped to the range `lo..=hi`, as `u8` values.
+///
+/// When `lo > hi` the output is `hi`.
+/// ```
+/// # use safe_arch::*;
+/// let x = m256i::from([0_u8, 2, 3, 7, 8, 200, 0, 2, 3, 7, 8, 200, 0, 2, 3, 7, 8, 200, 0, 2, 3, 7, 8, 200, 0, 2, 3, 7, 8, 200, 0, 2]);
+/// let c: [u8; 32] = clamp_u8_m256i(x, set_splat_i8_m256i(3), set_splat_i8_m256i(7)).into();
+/// assert_eq!(c, [3, 3, 3, 7, 7, 7, 3, 3, 3, 7, 7, 7, 3, 3, 3, 7, 7, 7, 3, 3, 3, 7, 7, 7, 3, 3, 3, 7, 7, 7, 3, 3]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn clamp_u8_m256i(x: m256i, lo: m256i, hi: m256i) -> m256i {
+  min_u8_m256i(max_u8_m256i(x, lo), hi)
+}
+
+/// Lanewise `|a - b|` of `u8` values.
+///
+/// To add up the differences, [`sum_of_u8_abs_diff_m256i`] does that directly.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([0_u8, 250, 5, 9, 0, 250, 5, 9, 0, 250, 5, 9, 0, 250, 5, 9, 0, 250, 5, 9, 0, 250, 5, 9, 0, 250, 5, 9, 0, 250, 5, 9]);
+/// let b = m256i::from([250_u8, 0, 9, 5, 250, 0, 9, 5, 250, 0, 9, 5, 250, 0, 9, 5, 250, 0, 9, 5, 250, 0, 9, 5, 250, 0, 9, 5, 250, 0, 9, 5]);
+/// let c: [u8; 32] = abs_diff_u8_m256i(a, b).into();
+/// assert_eq!(c, [250, 250, 4, 4, 250, 250, 4, 4, 250, 250, 4, 4, 250, 250, 4, 4, 250, 250, 4, 4, 250, 250, 4, 4, 250, 250, 4, 4, 250, 250, 4, 4]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn abs_diff_u8_m256i(a: m256i, b: m256i) -> m256i {
+  // One of the two saturating subtractions is always 0.
+  bitor_m256i(sub_saturating_u8_m256i(a, b), sub_saturating_u8_m256i(b, a))
+}
+
 /// Lanewise `min(a, b)` with lanes as `u16`.
 /// ```
 /// # use safe_arch::*;
@@ -1741,6 +1998,38 @@ pub fn min_u16_m256i(a: m256i, b: m256i) -> m256i {
   m256i(unsafe { _mm256_min_epu16(a.0, b.0) })
 }
 
+/// Lanewise `x` clamped to the range `lo..=hi`, as `u16` values.
+///
+/// When `lo > hi` the output is `hi`.
+/// ```
+/// # use safe_arch::*;
+/// let x = m256i::from([0_u16, 2, 3, 7, 8, 60000, 0, 2, 3, 7, 8, 200, 0, 2, 3, 7]);
+/// let c: [u16; 16] = clamp_u16_m256i(x, set_splat_i16_m256i(3), set_splat_i16_m256i(7)).into();
+/// assert_eq!(c, [3, 3, 3, 7, 7, 7, 3, 3, 3, 7, 7, 7, 3, 3, 3, 7]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn clamp_u16_m256i(x: m256i, lo: m256i, hi: m256i) -> m256i {
+  min_u16_m256i(max_u16_m256i(x, lo), hi)
+}
+
+/// Lanewise `|a - b|` of `u16` values.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([0_u16, 60000, 5, 9, 0, 60000, 5, 9, 0, 60000, 5, 9, 0, 60000, 5, 9]);
+/// let b = m256i::from([60000_u16, 0, 9, 5, 60000, 0, 9, 5, 60000, 0, 9, 5, 60000, 0, 9, 5]);
+/// let c: [u16; 16] = abs_diff_u16_m256i(a, b).into();
+/// assert_eq!(c, [60000, 60000, 4, 4, 60000, 60000, 4, 4, 60000, 60000, 4, 4, 60000, 60000, 4, 4]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn abs_diff_u16_m256i(a: m256i, b: m256i) -> m256i {
+  // One of the two saturating subtractions is always 0.
+  bitor_m256i(sub_saturating_u16_m256i(a, b), sub_saturating_u16_m256i(b, a))
+}
+
 /// Lanewise `min(a, b)` with lanes as `u32`.
 /// ```
 /// # use safe_arch::*;
@@ -1758,6 +2047,37 @@ pub fn min_u32_m256i(a: m256i, b: m256i) -> m256i {
   m256i(unsafe { _mm256_min_epu32(a.0, b.0) })
 }
 
+/// Lanewise `x` clamped to the range `lo..=hi`, as `u32` values.
+///
+/// When `lo > hi` the output is `hi`.
+/// ```
+/// # use safe_arch::*;
+/// let x = m256i::from([0_u32, 2, 3, 7, 8, 4_000_000_000, 0, 2]);
+/// let c: [u32; 8] = clamp_u32_m256i(x, set_splat_i32_m256i(3), set_splat_i32_m256i(7)).into();
+/// assert_eq!(c, [3, 3, 3, 7, 7, 7, 3, 3]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn clamp_u32_m256i(x: m256i, lo: m256i, hi: m256i) -> m256i {
+  min_u32_m256i(max_u32_m256i(x, lo), hi)
+}
+
+/// Lanewise `|a - b|` of `u32` values.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([0_u32, 4_000_000_000, 5, 9, 0, 4_000_000_000, 5, 9]);
+/// let b = m256i::from([4_000_000_000_u32, 0, 9, 5, 4_000_000_000, 0, 9, 5]);
+/// let c: [u32; 8] = abs_diff_u32_m256i(a, b).into();
+/// assert_eq!(c, [4_000_000_000, 4_000_000_000, 4, 4, 4_000_000_000, 4_000_000_000, 4, 4]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn abs_diff_u32_m256i(a: m256i, b: m256i) -> m256i {
+  sub_i32_m256i(max_u32_m256i(a, b), min_u32_m256i(a, b))
+}
+
 /// Create an `i32` mask of each sign bit in the `i8` lanes.
 /// ```
 /// # use safe_arch::*;
@@ -1767,6 +2087,21 @@ pub fn min_u32_m256i(a: m256i, b: m256i) -> m256i {
 /// ]);
 /// assert_eq!(0b01000001110000100010010110001000, move_mask_i8_m256i(a));
 /// ```
+/// There's no mask instruction for wider integer lanes, but:
+/// * For `i32` or `i64` lanes, cast to `m256` or `m256d` and use
+///   [`move_mask_m256`] or [`move_mask_m256d`]. The cast is free, and the
+///   result has one bit per lane.
+/// * For `i16` lanes, every odd bit of this mask is the sign of one lane, so
+///   take those bits (`population_extract_u32(mask, 0xAAAA_AAAA)` with
+///   `bmi2`). If you only need to test the mask (such as `mask != 0`), using it
+///   as is works fine.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([0_i32, -1, 2, -3, 4, 5, 6, i32::MIN]);
+/// assert_eq!(move_mask_m256(cast_to_m256_from_m256i(a)), 0b1000_1010);
+/// let a = m256i::from([-1_i64, 0, 0, -5]);
+/// assert_eq!(move_mask_m256d(cast_to_m256d_from_m256i(a)), 0b1001);
+/// ```
 /// * **Intrinsic:** [`_mm256_movemask_epi8`]
 /// * **Assembly:** `vpmovmskb r32, ymm`
 #[must_use]
@@ -1776,6 +2111,196 @@ pub fn move_mask_i8_m256i(a: m256i) -> i32 {
   unsafe { _mm256_movemask_epi8(a.0) }
 }
 
+/// If any lane of a comparison mask is true.
+///
+/// A byte counts as true if its sign bit is set. With a mask from any of
+/// the integer comparison functions, every byte of a lane is the same, so this
+/// works for lanes of any size.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([1, 2, 3, 4, 5, 6, 7, 8]);
+/// let m = cmp_eq_mask_i32_m256i(a, set_splat_i32_m256i(3));
+/// assert!(any_lane_true_m256i(m));
+/// assert!(!all_lanes_true_m256i(m));
+/// assert!(!any_lane_true_m256i(zeroed_m256i()));
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn any_lane_true_m256i(mask: m256i) -> bool {
+  move_mask_i8_m256i(mask) != 0
+}
+
+/// If every lane of a comparison mask is true.
+///
+/// See [`any_lane_true_m256i`] for what counts as true.
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn all_lanes_true_m256i(mask: m256i) -> bool {
+  move_mask_i8_m256i(mask) == -1
+}
+
+/// Checks the whole 32 byte chunks of `haystack` for the first byte where `f`
+/// gives a set mask lane.
+///
+/// This is `Ok` with the index if one was found, otherwise it's `Err` with the
+/// index where the unchecked tail starts.
+#[inline(always)]
+pub(crate) fn find_first_m256i(
+  haystack: &[u8], f: impl Fn(m256i) -> m256i,
+) -> Result<usize, usize> {
+  let mut chunks = haystack.chunks_exact(32);
+  for (i, chunk) in (&mut chunks).enumerate() {
+    let x = m256i(unsafe { _mm256_loadu_si256(chunk.as_ptr() as *const __m256i) });
+    let mask = move_mask_i8_m256i(f(x));
+    if mask != 0 {
+      return Ok(i * 32 + mask.trailing_zeros() as usize);
+    }
+  }
+  Err(haystack.len() - chunks.remainder().len())
+}
+
+/// Compares the whole 32 byte chunks of two equal length slices.
+///
+/// This is `Ok` with the index of the first difference if one was found,
+/// otherwise it's `Err` with the index where the unchecked tail starts.
+#[inline(always)]
+pub(crate) fn first_mismatch_m256i(a: &[u8], b: &[u8]) -> Result<usize, usize> {
+  let mut a_chunks = a.chunks_exact(32);
+  let b_chunks = b.chunks_exact(32);
+  for (i, (x, y)) in (&mut a_chunks).zip(b_chunks).enumerate() {
+    let x = m256i(unsafe { _mm256_loadu_si256(x.as_ptr() as *const __m256i) });
+    let y = m256i(unsafe { _mm256_loadu_si256(y.as_ptr() as *const __m256i) });
+    let mask = !move_mask_i8_m256i(cmp_eq_mask_i8_m256i(x, y));
+    if mask != 0 {
+      return Ok(i * 32 + mask.trailing_zeros() as usize);
+    }
+  }
+  Err(a.len() - a_chunks.remainder().len())
+}
+
+/// Counts the set bits in the whole 32 byte chunks of `bytes`.
+///
+/// Gives the index where the uncounted tail starts, and the count.
+#[inline(always)]
+pub(crate) fn count_ones_m256i(bytes: &[u8]) -> (usize, u64) {
+  let mut sums = zeroed_m256i();
+  let mut chunks = bytes.chunks_exact(32);
+  for chunk in &mut chunks {
+    let x = m256i(unsafe { _mm256_loadu_si256(chunk.as_ptr() as *const __m256i) });
+    sums = add_i64_m256i(sums, population_count_u64_lanes_m256i(x));
+  }
+  let sums: [u64; 4] = sums.into();
+  (bytes.len() - chunks.remainder().len(), sums.iter().sum())
+}
+
+/// Counts the `byte` lanes in the whole 32 byte chunks of `haystack`.
+///
+/// Gives the index where the uncounted tail starts, and the count.
+#[inline(always)]
+pub(crate) fn count_byte_m256i(haystack: &[u8], byte: u8) -> (usize, usize) {
+  let needle = set_splat_i8_m256i(byte as i8);
+  let flush = |counts: m256i| -> usize {
+    let sums: [u64; 4] = sum_of_u8_abs_diff_m256i(counts, zeroed_m256i()).into();
+    sums.iter().sum::<u64>() as usize
+  };
+  let mut total = 0;
+  let mut counts = zeroed_m256i();
+  let mut chunks = haystack.chunks_exact(32);
+  for (i, chunk) in (&mut chunks).enumerate() {
+    let x = m256i(unsafe { _mm256_loadu_si256(chunk.as_ptr() as *const __m256i) });
+    // A match is -1, so subtracting it adds 1 to that lane's count.
+    counts = sub_i8_m256i(counts, cmp_eq_mask_i8_m256i(x, needle));
+    if i % 255 == 254 {
+      total += flush(counts);
+      counts = zeroed_m256i();
+    }
+  }
+  (haystack.len() - chunks.remainder().len(), total + flush(counts))
+}
+
+/// Flips the case bit (`0x20`) of the bytes in `first..=last` within the whole
+/// 32 byte chunks of `bytes`.
+///
+/// Gives the index where the unchanged tail starts.
+#[inline(always)]
+pub(crate) fn flip_ascii_case_m256i(bytes: &mut [u8], first: u8, last: u8) -> usize {
+  let below = set_splat_i8_m256i(first as i8 - 1);
+  let above = set_splat_i8_m256i(last as i8 + 1);
+  let case_bit = set_splat_i8_m256i(0x20);
+  let len = bytes.len();
+  let mut chunks = bytes.chunks_exact_mut(32);
+  for chunk in &mut chunks {
+    let x = m256i(unsafe { _mm256_loadu_si256(chunk.as_ptr() as *const __m256i) });
+    let in_range = bitand_m256i(cmp_gt_mask_i8_m256i(x, below), cmp_gt_mask_i8_m256i(above, x));
+    let y = bitxor_m256i(x, bitand_m256i(in_range, case_bit));
+    unsafe { _mm256_storeu_si256(chunk.as_mut_ptr() as *mut __m256i, y.0) };
+  }
+  len - chunks.into_remainder().len()
+}
+
+/// Writes the hex digits of the whole 32 byte chunks of `bytes` into `out`.
+///
+/// Gives the index where the unwritten tail of `bytes` starts.
+#[inline(always)]
+pub(crate) fn hex_encode_m256i(bytes: &[u8], out: &mut [u8]) -> usize {
+  let table = nibble_table_m256i(*b"0123456789abcdef");
+  let low_nibble = set_splat_i8_m256i(0x0F);
+  let mut chunks = bytes.chunks_exact(32);
+  for (chunk, out) in (&mut chunks).zip(out.chunks_exact_mut(64)) {
+    let x = m256i(unsafe { _mm256_loadu_si256(chunk.as_ptr() as *const __m256i) });
+    // The unpacks work within each 128-bit half, so first put bytes 0..8 and
+    // 16..24 in the low half, and bytes 8..16 and 24..32 in the high half.
+    let x = shuffle_ai_i64_all_m256i::<0b11_01_10_00>(x);
+    let high = lookup_nibbles_m256i(table, bitand_m256i(shr_imm_u16_m256i::<4>(x), low_nibble));
+    let low = lookup_nibbles_m256i(table, bitand_m256i(x, low_nibble));
+    unsafe {
+      _mm256_storeu_si256(out.as_mut_ptr() as *mut __m256i, unpack_low_i8_m256i(high, low).0);
+      _mm256_storeu_si256(out.as_mut_ptr().add(32) as *mut __m256i, unpack_high_i8_m256i(high, low).0);
+    }
+  }
+  bytes.len() - chunks.remainder().len()
+}
+
+/// Reads the hex digits of the whole 64 digit chunks of `hex` into `out`.
+///
+/// This is `Ok` with the index where the unwritten tail of `out` starts, or
+/// `Err` with the index of the first byte that isn't a hex digit.
+#[inline(always)]
+pub(crate) fn hex_decode_m256i(hex: &[u8], out: &mut [u8]) -> Result<usize, usize> {
+  let values = |x: m256i| -> (m256i, u32) {
+    let is_digit = bitand_m256i(
+      cmp_gt_mask_i8_m256i(x, set_splat_i8_m256i(b'0' as i8 - 1)),
+      cmp_gt_mask_i8_m256i(set_splat_i8_m256i(b'9' as i8 + 1), x),
+    );
+    let lower = bitor_m256i(x, set_splat_i8_m256i(0x20));
+    let is_letter = bitand_m256i(
+      cmp_gt_mask_i8_m256i(lower, set_splat_i8_m256i(b'a' as i8 - 1)),
+      cmp_gt_mask_i8_m256i(set_splat_i8_m256i(b'f' as i8 + 1), lower),
+    );
+    let digit = bitand_m256i(is_digit, sub_i8_m256i(x, set_splat_i8_m256i(b'0' as i8)));
+    let letter = bitand_m256i(is_letter, sub_i8_m256i(lower, set_splat_i8_m256i(b'a' as i8 - 10)));
+    (bitor_m256i(digit, letter), move_mask_i8_m256i(bitor_m256i(is_digit, is_letter)) as u32)
+  };
+  let weights = set_splat_i16_m256i(0x01_10);
+  let mut chunks = hex.chunks_exact(64);
+  for (i, (chunk, out)) in (&mut chunks).zip(out.chunks_exact_mut(32)).enumerate() {
+    let (a, a_ok) = values(m256i(unsafe { _mm256_loadu_si256(chunk.as_ptr() as *const __m256i) }));
+    let (b, b_ok) = values(m256i(unsafe { _mm256_loadu_si256(chunk.as_ptr().add(32) as *const __m256i) }));
+    let bad = !(u64::from(a_ok) | (u64::from(b_ok) << 32));
+    if bad != 0 {
+      return Err(i * 64 + bad.trailing_zeros() as usize);
+    }
+    let a = mul_u8i8_add_horizontal_saturating_m256i(a, weights);
+    let b = mul_u8i8_add_horizontal_saturating_m256i(b, weights);
+    // The pack also works within each half, so put the quarters back in order.
+    let bytes = shuffle_ai_i64_all_m256i::<0b11_01_10_00>(pack_i16_to_u8_m256i(a, b));
+    unsafe { _mm256_storeu_si256(out.as_mut_ptr() as *mut __m256i, bytes.0) };
+  }
+  Ok((hex.len() - chunks.remainder().len()) / 2)
+}
+
 /// Computes eight `u16` "sum of absolute difference" values according to the
 /// bytes selected.
 ///
@@ -1942,6 +2467,25 @@ pub fn bitor_m256i(a: m256i, b: m256i) -> m256i {
   m256i(unsafe { _mm256_or_si256(a.0, b.0) })
 }
 
+/// Lanewise select: lanes of `if_true` where `mask` is set, otherwise the lanes
+/// of `if_false`.
+///
+/// Each lane of `mask` should be all 1s (true) or all 0s (false), as the
+/// `cmp_*_mask` functions give.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([1, 5, 3, 8, 0, 9, -1, 2]);
+/// let b = m256i::from([4, 2, 6, 7, 1, 1, 1, 1]);
+/// let c: [i32; 8] = select_m256i(cmp_gt_mask_i32_m256i(a, b), a, b).into();
+/// assert_eq!(c, [4, 5, 6, 8, 1, 9, 1, 2]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn select_m256i(mask: m256i, if_true: m256i, if_false: m256i) -> m256i {
+  bitor_m256i(bitand_m256i(mask, if_true), bitandnot_m256i(mask, if_false))
+}
+
 /// Saturating convert `i16` to `i8`, and pack the values.
 ///
 /// * The values are packed 128 bits at a time: `a_low`, `b_low`, `a_high`,
@@ -2034,6 +2578,144 @@ pub fn pack_i32_to_u16_m256i(a: m256i, b: m256i) -> m256i {
   m256i(unsafe { _mm256_packs_epi32(a.0, b.0) })
 }
 
+/// Widens 32 `u8` values to 32 `f32` values, multiplying each by `scale`.
+///
+/// This works like [`widen_u8_to_m128`], but twice as wide. See
+/// [`narrow_m256_to_u8_saturating`] for the reverse.
+/// ```
+/// # use safe_arch::*;
+/// let mut bytes = [0_u8; 32];
+/// for (i, b) in bytes.iter_mut().enumerate() {
+///   *b = i as u8 * 8;
+/// }
+/// let w = widen_u8_to_m256(bytes, 0.5);
+/// for (i, x) in w.iter().flat_map(|m| m.to_array()).enumerate() {
+///   assert_eq!(x, (i * 4) as f32);
+/// }
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn widen_u8_to_m256(bytes: [u8; 32], scale: f32) -> [m256; 4] {
+  let a = m256i::from(bytes);
+  let low = cast_to_m128i_from_m256i(a);
+  let high = extract_m128i_m256i::<1>(a);
+  let scale = set_splat_m256(scale);
+  let widen = |x: m128i| {
+    let i = convert_to_i16_m256i_from_lower8_u8_m128i(x);
+    mul_m256(convert_to_m256_from_i32_m256i(i), scale)
+  };
+  [
+    widen(low),
+    widen(byte_shr_imm_u128_m128i::<8>(low)),
+    widen(high),
+    widen(byte_shr_imm_u128_m128i::<8>(high)),
+  ]
+}
+
+/// Narrows 32 `f32` values to 32 `u8` values, multiplying each by `scale`
+/// first.
+///
+/// This works like [`narrow_m128_to_u8_saturating`], but twice as wide.
+/// ```
+/// # use safe_arch::*;
+/// let mut floats = [0.0_f32; 32];
+/// for (i, f) in floats.iter_mut().enumerate() {
+///   *f = i as f32 * 10.0;
+/// }
+/// let mut a = [zeroed_m256(); 4];
+/// for (m, chunk) in a.iter_mut().zip(floats.chunks_exact(8)) {
+///   *m = load_unaligned_m256(core::convert::TryInto::try_into(chunk).unwrap());
+/// }
+/// let c = narrow_m256_to_u8_saturating(a, 1.0);
+/// for (i, b) in c.iter().enumerate() {
+///   assert_eq!(*b, (i * 10).min(255) as u8);
+/// }
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn narrow_m256_to_u8_saturating(a: [m256; 4], scale: f32) -> [u8; 32] {
+  let scale = set_splat_m256(scale);
+  let max = set_splat_m256(255.0);
+  // `max_m256` gives the second argument for NaN lanes.
+  let narrow = |x: m256| {
+    let clamped = min_m256(max_m256(mul_m256(x, scale), zeroed_m256()), max);
+    convert_to_i32_m256i_from_m256(clamped)
+  };
+  narrow_i32_to_u8_saturating_m256i(narrow(a[0]), narrow(a[1]), narrow(a[2]), narrow(a[3]))
+    .into()
+}
+
+/// Narrows the `i32` lanes of `a` then `b` to `i16` lanes, saturating.
+///
+/// Unlike [`pack_i32_to_i16_m256i`] the output lanes are in order: all of `a`,
+/// then all of `b`.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([1, -70_000, 70_000, 4, 5, 6, 7, 8]);
+/// let b = m256i::from([9, 10, 11, 12, 13, 14, 15, 16]);
+/// let c: [i16; 16] = narrow_i32_to_i16_saturating_m256i(a, b).into();
+/// assert_eq!(c, [1, i16::MIN, i16::MAX, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn narrow_i32_to_i16_saturating_m256i(a: m256i, b: m256i) -> m256i {
+  shuffle_ai_i64_all_m256i::<0b11_01_10_00>(pack_i32_to_i16_m256i(a, b))
+}
+
+/// Narrows the `i16` lanes of `a` then `b` to `u8` lanes, saturating.
+///
+/// Unlike [`pack_i16_to_u8_m256i`] the output lanes are in order: all of `a`,
+/// then all of `b`.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([1_i16, -2, 300, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+/// let b = m256i::from([17_i16; 16]);
+/// let c: [u8; 32] = narrow_i16_to_u8_saturating_m256i(a, b).into();
+/// assert_eq!(&c[..16], &[1, 0, 255, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+/// assert_eq!(&c[16..], &[17; 16]);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn narrow_i16_to_u8_saturating_m256i(a: m256i, b: m256i) -> m256i {
+  shuffle_ai_i64_all_m256i::<0b11_01_10_00>(pack_i16_to_u8_m256i(a, b))
+}
+
+/// Narrows the `i32` lanes of `a`, `b`, `c`, then `d` to `u8` lanes,
+/// saturating.
+///
+/// Unlike chaining [`pack_i32_to_i16_m256i`] and [`pack_i16_to_u8_m256i`], the
+/// output lanes are in order: all of `a`, then `b`, `c`, and `d`.
+/// ```
+/// # use safe_arch::*;
+/// let mut v = [m256i::default(); 4];
+/// for (n, m) in v.iter_mut().enumerate() {
+///   let mut lanes = [0_i32; 8];
+///   for (i, x) in lanes.iter_mut().enumerate() {
+///     *x = (n * 8 + i) as i32 * 20 - 100;
+///   }
+///   *m = m256i::from(lanes);
+/// }
+/// let c: [u8; 32] = narrow_i32_to_u8_saturating_m256i(v[0], v[1], v[2], v[3]).into();
+/// for (i, b) in c.iter().enumerate() {
+///   assert_eq!(*b, (i as i32 * 20 - 100).clamp(0, 255) as u8);
+/// }
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn narrow_i32_to_u8_saturating_m256i(
+  a: m256i, b: m256i, c: m256i, d: m256i,
+) -> m256i {
+  let ab = pack_i32_to_i16_m256i(a, b);
+  let cd = pack_i32_to_i16_m256i(c, d);
+  let bytes = pack_i16_to_u8_m256i(ab, cd);
+  shuffle_av_i32_all_m256i(bytes, m256i::from([0, 4, 1, 5, 2, 6, 3, 7]))
+}
+
 /// Shuffle 128 bits of integer data from `$a` and `$b` using an immediate
 /// control value.
 ///
@@ -2202,6 +2884,264 @@ pub fn shuffle_av_i8z_half_m256i(a: m256i, v: m256i) -> m256i {
   m256i(unsafe { _mm256_shuffle_epi8(a.0, v.0) })
 }
 
+/// Builds the table register for [`lookup_nibbles_m256i`].
+///
+/// The table is copied into both 128 bit halves, since each half does its own
+/// lookup.
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn nibble_table_m256i(table: [u8; 16]) -> m256i {
+  let mut both = [0_u8; 32];
+  both[..16].copy_from_slice(&table);
+  both[16..].copy_from_slice(&table);
+  m256i::from(both)
+}
+
+/// Looks up each `u8` lane of `indices` in a 16 entry table.
+///
+/// This works like [`lookup_nibbles_m128i`], twice. Each 128 bit half of
+/// `indices` looks up in the matching half of `table`, so build the table
+/// with [`nibble_table_m256i`] to get the same 16 entries in both.
+/// ```
+/// # use safe_arch::*;
+/// let hex = nibble_table_m256i(*b"0123456789abcdef");
+/// let mut bytes = [0_u8; 32];
+/// for (i, b) in bytes.iter_mut().enumerate() {
+///   *b = i as u8;
+/// }
+/// let low = bitand_m256i(m256i::from(bytes), set_splat_i8_m256i(0x0F));
+/// let c: [u8; 32] = lookup_nibbles_m256i(hex, low).into();
+/// assert_eq!(&c, b"0123456789abcdef0123456789abcdef");
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn lookup_nibbles_m256i(table: m256i, indices: m256i) -> m256i {
+  shuffle_av_i8z_half_m256i(table, indices)
+}
+
+/// Counts the set bits in each `u8` lane.
+///
+/// This works like [`population_count_u8_lanes_m128i`], but twice as wide.
+/// ```
+/// # use safe_arch::*;
+/// let mut bytes = [0_u8; 32];
+/// for (i, b) in bytes.iter_mut().enumerate() {
+///   *b = (i * 9) as u8;
+/// }
+/// let c: [u8; 32] = population_count_u8_lanes_m256i(m256i::from(bytes)).into();
+/// for (b, count) in bytes.iter().zip(c.iter()) {
+///   assert_eq!(b.count_ones(), u32::from(*count));
+/// }
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn population_count_u8_lanes_m256i(a: m256i) -> m256i {
+  let table = nibble_table_m256i([0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4]);
+  let low_mask = set_splat_i8_m256i(0x0F);
+  let low = bitand_m256i(a, low_mask);
+  let high = bitand_m256i(shr_imm_u16_m256i::<4>(a), low_mask);
+  add_i8_m256i(lookup_nibbles_m256i(table, low), lookup_nibbles_m256i(table, high))
+}
+
+/// Counts the set bits in each `u64` lane.
+///
+/// This works like [`population_count_u64_lanes_m128i`], but twice as wide.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([u64::MAX, 0x0F0F_0000_0000_0001, 0, 1 << 63]);
+/// let c: [u64; 4] = population_count_u64_lanes_m256i(a).into();
+/// assert_eq!(c, [64, 9, 0, 1]);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn population_count_u64_lanes_m256i(a: m256i) -> m256i {
+  sum_of_u8_abs_diff_m256i(population_count_u8_lanes_m256i(a), zeroed_m256i())
+}
+
+/// Swaps the bytes within each `i16` lane.
+///
+/// This works like [`byte_swap_i16_m128i`], but twice as wide.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([0x0102_i16; 16]);
+/// let c: [i16; 16] = byte_swap_i16_m256i(a).into();
+/// assert_eq!(c, [0x0201; 16]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn byte_swap_i16_m256i(a: m256i) -> m256i {
+  let v = m256i::from([
+    1_u8, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14, 1, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14,
+  ]);
+  shuffle_av_i8z_half_m256i(a, v)
+}
+
+/// Swaps the bytes within each `i32` lane.
+///
+/// This works like [`byte_swap_i32_m128i`], but twice as wide.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([0x0A123456, 1, -1, 0x01020304, 5, 6, 7, 8]);
+/// let c: [i32; 8] = byte_swap_i32_m256i(a).into();
+/// assert_eq!(
+///   c,
+///   [0x5634120A, 0x01000000, -1, 0x04030201, 0x05000000, 0x06000000, 0x07000000, 0x08000000]
+/// );
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn byte_swap_i32_m256i(a: m256i) -> m256i {
+  let v = m256i::from([
+    3_u8, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12, 3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12,
+  ]);
+  shuffle_av_i8z_half_m256i(a, v)
+}
+
+/// Swaps the bytes within each `i64` lane.
+///
+/// This works like [`byte_swap_i64_m128i`], but twice as wide.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([0x0A123456_789ABC01_i64, 1, 2, 3]);
+/// let c: [i64; 4] = byte_swap_i64_m256i(a).into();
+/// assert_eq!(c, [0x01BC9A78_5634120A, 1 << 56, 2 << 56, 3 << 56]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn byte_swap_i64_m256i(a: m256i) -> m256i {
+  let v = m256i::from([
+    7_u8, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8,
+  ]);
+  shuffle_av_i8z_half_m256i(a, v)
+}
+
+/// Splits 32 pixels of 3 `u8` channels each (such as RGB) into one register
+/// per channel.
+///
+/// This is [`deinterleave_3_u8_m128i`] at twice the width. The halves are
+/// first rearranged so that each 128-bit half holds 16 whole pixels, and then
+/// both halves do the same shuffles.
+/// ```
+/// # use safe_arch::*;
+/// let mut rgb = [[0_u8; 32]; 3];
+/// for i in 0..96 {
+///   rgb[i / 32][i % 32] = ((i % 3) * 40 + i / 3) as u8;
+/// }
+/// let aos = rgb.map(m256i::from);
+/// let soa = deinterleave_3_u8_m256i(aos);
+/// for (channel, s) in soa.iter().enumerate() {
+///   let s: [u8; 32] = (*s).into();
+///   assert!(s.iter().enumerate().all(|(i, b)| usize::from(*b) == channel * 40 + i));
+/// }
+/// assert_eq!(interleave_3_u8_m256i(soa).map(<[u8; 32]>::from), aos.map(<[u8; 32]>::from));
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn deinterleave_3_u8_m256i(aos: [m256i; 3]) -> [m256i; 3] {
+  let [a, b, c] = aos;
+  // Pixels 0..16 go in the low halves, and pixels 16..32 in the high halves.
+  let aos = [permute2z_m256i::<0x30>(a, b), permute2z_m256i::<0x21>(a, c), permute2z_m256i::<0x30>(b, c)];
+  let gather = |channel: usize| {
+    let mut out = zeroed_m256i();
+    for (register, a) in aos.iter().enumerate() {
+      let v = m128i::from(deinterleave_3_u8_control(channel, register));
+      out = bitor_m256i(out, shuffle_av_i8z_half_m256i(*a, set_m128i_m256i(v, v)));
+    }
+    out
+  };
+  [gather(0), gather(1), gather(2)]
+}
+
+/// Merges one register per channel into 32 pixels of 3 `u8` channels each.
+///
+/// This undoes [`deinterleave_3_u8_m256i`].
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn interleave_3_u8_m256i(soa: [m256i; 3]) -> [m256i; 3] {
+  let scatter = |register: usize| {
+    let mut out = zeroed_m256i();
+    for (channel, a) in soa.iter().enumerate() {
+      let v = m128i::from(interleave_3_u8_control(channel, register));
+      out = bitor_m256i(out, shuffle_av_i8z_half_m256i(*a, set_m128i_m256i(v, v)));
+    }
+    out
+  };
+  let [a, b, c] = [scatter(0), scatter(1), scatter(2)];
+  // Each half holds 16 whole pixels, so put them back in memory order.
+  [permute2z_m256i::<0x20>(a, b), permute2z_m256i::<0x30>(c, a), permute2z_m256i::<0x31>(b, c)]
+}
+
+/// Splits 32 pixels of 4 `u8` channels each (such as RGBA) into one register
+/// per channel.
+///
+/// This is [`deinterleave_4_u8_m128i`] at twice the width, with one more
+/// shuffle at the end to put the 4 byte groups of each channel in order.
+/// ```
+/// # use safe_arch::*;
+/// let mut rgba = [[0_u8; 32]; 4];
+/// for i in 0..128 {
+///   rgba[i / 32][i % 32] = ((i % 4) * 32 + i / 4) as u8;
+/// }
+/// let aos = rgba.map(m256i::from);
+/// let soa = deinterleave_4_u8_m256i(aos);
+/// for (channel, s) in soa.iter().enumerate() {
+///   let s: [u8; 32] = (*s).into();
+///   assert!(s.iter().enumerate().all(|(i, b)| usize::from(*b) == channel * 32 + i));
+/// }
+/// assert_eq!(interleave_4_u8_m256i(soa).map(<[u8; 32]>::from), aos.map(<[u8; 32]>::from));
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn deinterleave_4_u8_m256i(aos: [m256i; 4]) -> [m256i; 4] {
+  let v = m128i::from(TRANSPOSE_4X4_U8);
+  let v = set_m128i_m256i(v, v);
+  let [a, b, c, d] = aos.map(|a| shuffle_av_i8z_half_m256i(a, v));
+  // The transpose works within each half, so the 4 byte groups of each
+  // channel come out as 0, 2, 4, 6, 1, 3, 5, 7.
+  let order = m256i::from([0, 4, 1, 5, 2, 6, 3, 7]);
+  transpose_4x4_i32_m256i(a, b, c, d).map(|x| shuffle_av_i32_all_m256i(x, order))
+}
+
+/// Merges one register per channel into 32 pixels of 4 `u8` channels each.
+///
+/// This undoes [`deinterleave_4_u8_m256i`].
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn interleave_4_u8_m256i(soa: [m256i; 4]) -> [m256i; 4] {
+  let v = m128i::from(TRANSPOSE_4X4_U8);
+  let v = set_m128i_m256i(v, v);
+  let order = m256i::from([0, 2, 4, 6, 1, 3, 5, 7]);
+  let [r, g, b, a] = soa.map(|x| shuffle_av_i32_all_m256i(x, order));
+  transpose_4x4_i32_m256i(r, g, b, a).map(|x| shuffle_av_i8z_half_m256i(x, v))
+}
+
+/// Transposes four registers of `i32` lanes as a 4x4 matrix within each
+/// 128-bit half.
+#[inline(always)]
+fn transpose_4x4_i32_m256i(a: m256i, b: m256i, c: m256i, d: m256i) -> [m256i; 4] {
+  let t0 = unpack_low_i32_m256i(a, b);
+  let t1 = unpack_high_i32_m256i(a, b);
+  let t2 = unpack_low_i32_m256i(c, d);
+  let t3 = unpack_high_i32_m256i(c, d);
+  [
+    unpack_low_i64_m256i(t0, t2),
+    unpack_high_i64_m256i(t0, t2),
+    unpack_low_i64_m256i(t1, t3),
+    unpack_high_i64_m256i(t1, t3),
+  ]
+}
+
 /// Shuffle the high `i16` lanes in `$a` using an immediate control value.
 ///
 /// The lower 128 bits and upper 128 bits have this performed separately.
@@ -2330,6 +3270,30 @@ pub fn shl_all_u16_m256i(a: m256i, count: m128i) -> m256i {
   m256i(unsafe { _mm256_sll_epi16(a.0, count.0) })
 }
 
+/// Shift all `u16` lanes to the left by `count`, giving 0 if `count` is
+/// 16 or more.
+///
+/// See [`shl_all_checked_u16_m128i`] for how this differs from
+/// [`shl_all_u16_m256i`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([1_u16, 100, 1, 100, 1, 100, 1, 100, 1, 100, 1, 100, 1, 100, 1, 100]);
+/// let b: [u16; 16] = shl_all_checked_u16_m256i(a, 2).into();
+/// assert_eq!(b, [1 << 2, 100 << 2, 1 << 2, 100 << 2, 1 << 2, 100 << 2, 1 << 2, 100 << 2, 1 << 2, 100 << 2, 1 << 2, 100 << 2, 1 << 2, 100 << 2, 1 << 2, 100 << 2]);
+/// let c: [u16; 16] = shl_all_checked_u16_m256i(a, 16).into();
+/// assert_eq!(c, [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn shl_all_checked_u16_m256i(a: m256i, count: u32) -> m256i {
+  if count < 16 {
+    shl_all_u16_m256i(a, set_i32_m128i_s(count as i32))
+  } else {
+    zeroed_m256i()
+  }
+}
+
 /// Shift all `u32` lanes left by the lower `u64` lane of `count`.
 /// ```
 /// # use safe_arch::*;
@@ -2347,6 +3311,30 @@ pub fn shl_all_u32_m256i(a: m256i, count: m128i) -> m256i {
   m256i(unsafe { _mm256_sll_epi32(a.0, count.0) })
 }
 
+/// Shift all `u32` lanes to the left by `count`, giving 0 if `count` is
+/// 32 or more.
+///
+/// See [`shl_all_checked_u16_m128i`] for how this differs from
+/// [`shl_all_u32_m256i`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([1_u32, 100, 1, 100, 1, 100, 1, 100]);
+/// let b: [u32; 8] = shl_all_checked_u32_m256i(a, 2).into();
+/// assert_eq!(b, [1 << 2, 100 << 2, 1 << 2, 100 << 2, 1 << 2, 100 << 2, 1 << 2, 100 << 2]);
+/// let c: [u32; 8] = shl_all_checked_u32_m256i(a, 32).into();
+/// assert_eq!(c, [0, 0, 0, 0, 0, 0, 0, 0]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn shl_all_checked_u32_m256i(a: m256i, count: u32) -> m256i {
+  if count < 32 {
+    shl_all_u32_m256i(a, set_i32_m128i_s(count as i32))
+  } else {
+    zeroed_m256i()
+  }
+}
+
 /// Shift all `u64` lanes left by the lower `u64` lane of `count`.
 /// ```
 /// # use safe_arch::*;
@@ -2364,6 +3352,30 @@ pub fn shl_all_u64_m256i(a: m256i, count: m128i) -> m256i {
   m256i(unsafe { _mm256_sll_epi64(a.0, count.0) })
 }
 
+/// Shift all `u64` lanes to the left by `count`, giving 0 if `count` is
+/// 64 or more.
+///
+/// See [`shl_all_checked_u16_m128i`] for how this differs from
+/// [`shl_all_u64_m256i`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([1_u64, 100, 1, 100]);
+/// let b: [u64; 4] = shl_all_checked_u64_m256i(a, 2).into();
+/// assert_eq!(b, [1 << 2, 100 << 2, 1 << 2, 100 << 2]);
+/// let c: [u64; 4] = shl_all_checked_u64_m256i(a, 64).into();
+/// assert_eq!(c, [0, 0, 0, 0]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn shl_all_checked_u64_m256i(a: m256i, count: u32) -> m256i {
+  if count < 64 {
+    shl_all_u64_m256i(a, set_i32_m128i_s(count as i32))
+  } else {
+    zeroed_m256i()
+  }
+}
+
 /// Shifts all `u16` lanes left by an immediate.
 ///
 /// ```
@@ -2466,6 +3478,26 @@ pub fn shr_all_i16_m256i(a: m256i, count: m128i) -> m256i {
   m256i(unsafe { _mm256_sra_epi16(a.0, count.0) })
 }
 
+/// Shift all `i16` lanes to the right by `count`, filling each lane with
+/// its sign bit if `count` is 16 or more.
+///
+/// See [`shl_all_checked_u16_m128i`] for how this differs from
+/// [`shr_all_i16_m256i`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([1_i16, -100, 1, -100, 1, -100, 1, -100, 1, -100, 1, -100, 1, -100, 1, -100]);
+/// let b: [i16; 16] = shr_all_checked_i16_m256i(a, 2).into();
+/// assert_eq!(b, [1 >> 2, -100 >> 2, 1 >> 2, -100 >> 2, 1 >> 2, -100 >> 2, 1 >> 2, -100 >> 2, 1 >> 2, -100 >> 2, 1 >> 2, -100 >> 2, 1 >> 2, -100 >> 2, 1 >> 2, -100 >> 2]);
+/// let c: [i16; 16] = shr_all_checked_i16_m256i(a, 16).into();
+/// assert_eq!(c, [0, -1, 0, -1, 0, -1, 0, -1, 0, -1, 0, -1, 0, -1, 0, -1]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn shr_all_checked_i16_m256i(a: m256i, count: u32) -> m256i {
+  shr_all_i16_m256i(a, set_i32_m128i_s(count.min(15) as i32))
+}
+
 /// Lanewise `i32` shift right by the lower `i64` lane of `count`.
 /// ```
 /// # use safe_arch::*;
@@ -2483,6 +3515,26 @@ pub fn shr_all_i32_m256i(a: m256i, count: m128i) -> m256i {
   m256i(unsafe { _mm256_sra_epi32(a.0, count.0) })
 }
 
+/// Shift all `i32` lanes to the right by `count`, filling each lane with
+/// its sign bit if `count` is 32 or more.
+///
+/// See [`shl_all_checked_u16_m128i`] for how this differs from
+/// [`shr_all_i32_m256i`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([1_i32, -100, 1, -100, 1, -100, 1, -100]);
+/// let b: [i32; 8] = shr_all_checked_i32_m256i(a, 2).into();
+/// assert_eq!(b, [1 >> 2, -100 >> 2, 1 >> 2, -100 >> 2, 1 >> 2, -100 >> 2, 1 >> 2, -100 >> 2]);
+/// let c: [i32; 8] = shr_all_checked_i32_m256i(a, 32).into();
+/// assert_eq!(c, [0, -1, 0, -1, 0, -1, 0, -1]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn shr_all_checked_i32_m256i(a: m256i, count: u32) -> m256i {
+  shr_all_i32_m256i(a, set_i32_m128i_s(count.min(31) as i32))
+}
+
 /// Shifts all `i16` lanes left by an immediate.
 ///
 /// ```
@@ -2551,6 +3603,30 @@ pub fn shr_all_u16_m256i(a: m256i, count: m128i) -> m256i {
   m256i(unsafe { _mm256_srl_epi16(a.0, count.0) })
 }
 
+/// Shift all `u16` lanes to the right by `count`, giving 0 if `count` is
+/// 16 or more.
+///
+/// See [`shl_all_checked_u16_m128i`] for how this differs from
+/// [`shr_all_u16_m256i`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([1_u16, 100, 1, 100, 1, 100, 1, 100, 1, 100, 1, 100, 1, 100, 1, 100]);
+/// let b: [u16; 16] = shr_all_checked_u16_m256i(a, 2).into();
+/// assert_eq!(b, [1 >> 2, 100 >> 2, 1 >> 2, 100 >> 2, 1 >> 2, 100 >> 2, 1 >> 2, 100 >> 2, 1 >> 2, 100 >> 2, 1 >> 2, 100 >> 2, 1 >> 2, 100 >> 2, 1 >> 2, 100 >> 2]);
+/// let c: [u16; 16] = shr_all_checked_u16_m256i(a, 16).into();
+/// assert_eq!(c, [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn shr_all_checked_u16_m256i(a: m256i, count: u32) -> m256i {
+  if count < 16 {
+    shr_all_u16_m256i(a, set_i32_m128i_s(count as i32))
+  } else {
+    zeroed_m256i()
+  }
+}
+
 /// Lanewise `u32` shift right by the lower `u64` lane of `count`.
 /// ```
 /// # use safe_arch::*;
@@ -2568,6 +3644,30 @@ pub fn shr_all_u32_m256i(a: m256i, count: m128i) -> m256i {
   m256i(unsafe { _mm256_srl_epi32(a.0, count.0) })
 }
 
+/// Shift all `u32` lanes to the right by `count`, giving 0 if `count` is
+/// 32 or more.
+///
+/// See [`shl_all_checked_u16_m128i`] for how this differs from
+/// [`shr_all_u32_m256i`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([1_u32, 100, 1, 100, 1, 100, 1, 100]);
+/// let b: [u32; 8] = shr_all_checked_u32_m256i(a, 2).into();
+/// assert_eq!(b, [1 >> 2, 100 >> 2, 1 >> 2, 100 >> 2, 1 >> 2, 100 >> 2, 1 >> 2, 100 >> 2]);
+/// let c: [u32; 8] = shr_all_checked_u32_m256i(a, 32).into();
+/// assert_eq!(c, [0, 0, 0, 0, 0, 0, 0, 0]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn shr_all_checked_u32_m256i(a: m256i, count: u32) -> m256i {
+  if count < 32 {
+    shr_all_u32_m256i(a, set_i32_m128i_s(count as i32))
+  } else {
+    zeroed_m256i()
+  }
+}
+
 /// Lanewise `u64` shift right by the lower `u64` lane of `count`.
 /// ```
 /// # use safe_arch::*;
@@ -2585,6 +3685,30 @@ pub fn shr_all_u64_m256i(a: m256i, count: m128i) -> m256i {
   m256i(unsafe { _mm256_srl_epi64(a.0, count.0) })
 }
 
+/// Shift all `u64` lanes to the right by `count`, giving 0 if `count` is
+/// 64 or more.
+///
+/// See [`shl_all_checked_u16_m128i`] for how this differs from
+/// [`shr_all_u64_m256i`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m256i::from([1_u64, 100, 1, 100]);
+/// let b: [u64; 4] = shr_all_checked_u64_m256i(a, 2).into();
+/// assert_eq!(b, [1 >> 2, 100 >> 2, 1 >> 2, 100 >> 2]);
+/// let c: [u64; 4] = shr_all_checked_u64_m256i(a, 64).into();
+/// assert_eq!(c, [0, 0, 0, 0]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub fn shr_all_checked_u64_m256i(a: m256i, count: u32) -> m256i {
+  if count < 64 {
+    shr_all_u64_m256i(a, set_i32_m128i_s(count as i32))
+  } else {
+    zeroed_m256i()
+  }
+}
+
 /// Shifts all `u16` lanes right by an immediate.
 ///
 /// ```
@@ -2670,6 +3794,24 @@ pub fn shr_each_u64_m256i(a: m256i, count: m256i) -> m256i {
   m256i(unsafe { _mm256_srlv_epi64(a.0, count.0) })
 }
 
+impl<'a> StreamingLoads<'a> {
+  /// Loads the value from the reference given, as a streaming load.
+  /// ```
+  /// # use safe_arch::*;
+  /// let r = set_splat_i32_m256i(7);
+  /// let a = streaming_loads(|l| l.load_nontemporal_m256i(&r));
+  /// assert_eq!(<[i32; 8]>::from(a), [7; 8]);
+  /// ```
+  /// * **Intrinsic:** [`_mm256_stream_load_si256`]
+  /// * **Assembly:** `vmovntdqa ymm, m256`
+  #[must_use]
+  #[inline(always)]
+  #[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+  pub fn load_nontemporal_m256i(&self, r: &'a m256i) -> m256i {
+    m256i(unsafe { _mm256_stream_load_si256(r as *const m256i as *const __m256i) })
+  }
+}
+
 /// Lanewise `a - b` with lanes as `i8`.
 /// ```
 /// # use safe_arch::*;
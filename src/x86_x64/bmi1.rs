@@ -197,3 +197,42 @@ pub fn trailing_zero_count_u64(a: u64) -> u64 {
   unsafe { _tzcnt_u64(a) }
 }
 
+/// An iterator over the indexes of the set bits of a `u64`, lowest first.
+///
+/// Each step is a trailing zero count and then a reset of the lowest set bit,
+/// which is a `tzcnt` and a `blsr` with this feature enabled. This is the
+/// usual way to walk the bits of a `move_mask` result.
+/// ```
+/// # use safe_arch::*;
+/// let bits = SetBits(0b1010_0001);
+/// assert_eq!(bits.len(), 3);
+/// let mut out = [0_u32; 3];
+/// for (o, i) in out.iter_mut().zip(bits) {
+///   *o = i;
+/// }
+/// assert_eq!(out, [0, 5, 7]);
+/// assert_eq!(SetBits(0).next(), None);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "bmi1")))]
+pub struct SetBits(pub u64);
+impl Iterator for SetBits {
+  type Item = u32;
+  #[inline(always)]
+  fn next(&mut self) -> Option<u32> {
+    if self.0 == 0 {
+      None
+    } else {
+      let i = self.0.trailing_zeros();
+      self.0 &= self.0 - 1;
+      Some(i)
+    }
+  }
+  #[inline(always)]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let n = self.0.count_ones() as usize;
+    (n, Some(n))
+  }
+}
+impl ExactSizeIterator for SetBits {}
+impl core::iter::FusedIterator for SetBits {}
@@ -104,3 +104,107 @@ pub fn population_extract_u64(a: u64, index: u64) -> u64 {
   unsafe { _pext_u64(a, index) }
 }
 
+/// Deposits the bits of every `u64` in the slice according to one mask.
+///
+/// This is [`population_deposit_u64`] on each element, in place.
+/// ```
+/// # use safe_arch::*;
+/// let mut data = [0b11_u64, 0b10, 0b01];
+/// population_deposit_u64_slice(&mut data, 0b1010);
+/// assert_eq!(data, [0b1010, 0b1000, 0b0010]);
+/// ```
+#[inline]
+#[cfg(target_arch = "x86_64")]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "bmi2")))]
+pub fn population_deposit_u64_slice(data: &mut [u64], mask: u64) {
+  for x in data.iter_mut() {
+    *x = population_deposit_u64(*x, mask);
+  }
+}
+
+/// Extracts the bits of every `u64` in the slice according to one mask.
+///
+/// This is [`population_extract_u64`] on each element, in place.
+/// ```
+/// # use safe_arch::*;
+/// let mut data = [0b1010_u64, 0b1000, 0b0111];
+/// population_extract_u64_slice(&mut data, 0b1010);
+/// assert_eq!(data, [0b11, 0b10, 0b01]);
+/// ```
+#[inline]
+#[cfg(target_arch = "x86_64")]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "bmi2")))]
+pub fn population_extract_u64_slice(data: &mut [u64], mask: u64) {
+  for x in data.iter_mut() {
+    *x = population_extract_u64(*x, mask);
+  }
+}
+
+/// Interleaves the bits of `x` and `y`, giving their Morton (Z-order) code.
+///
+/// Bit `i` of `x` goes to bit `2 * i` of the output, and bit `i` of `y` goes
+/// to bit `2 * i + 1`. [`deinterleave_bits_u64`] undoes this.
+/// ```
+/// # use safe_arch::*;
+/// assert_eq!(interleave_bits_u32(0b11, 0b00), 0b0101);
+/// assert_eq!(interleave_bits_u32(0b00, 0b11), 0b1010);
+/// assert_eq!(interleave_bits_u32(u32::MAX, 0), 0x5555_5555_5555_5555);
+/// ```
+/// * **Intrinsic:** one of
+///   * `_pdep_u64` on x86_64
+///   * [`_pdep_u32`] on x86
+/// * **Assembly:** one of
+///   * `pdep r64, r64, r64` on x86_64
+///   * `pdep r32, r32, r32` on x86
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "bmi2")))]
+pub fn interleave_bits_u32(x: u32, y: u32) -> u64 {
+  #[cfg(target_arch = "x86_64")]
+  {
+    population_deposit_u64(u64::from(x), 0x5555_5555_5555_5555)
+      | population_deposit_u64(u64::from(y), 0xAAAA_AAAA_AAAA_AAAA)
+  }
+  #[cfg(not(target_arch = "x86_64"))]
+  {
+    let half = |x: u32, y: u32| {
+      population_deposit_u32(x & 0xFFFF, 0x5555_5555) | population_deposit_u32(y & 0xFFFF, 0xAAAA_AAAA)
+    };
+    u64::from(half(x, y)) | (u64::from(half(x >> 16, y >> 16)) << 32)
+  }
+}
+
+/// Splits a Morton (Z-order) code back into its `(x, y)` parts.
+///
+/// This undoes [`interleave_bits_u32`].
+/// ```
+/// # use safe_arch::*;
+/// assert_eq!(deinterleave_bits_u64(0b0110), (0b10, 0b01));
+/// let m = interleave_bits_u32(12345, 67890);
+/// assert_eq!(deinterleave_bits_u64(m), (12345, 67890));
+/// ```
+/// * **Intrinsic:** one of
+///   * `_pext_u64` on x86_64
+///   * [`_pext_u32`] on x86
+/// * **Assembly:** one of
+///   * `pext r64, r64, r64` on x86_64
+///   * `pext r32, r32, r32` on x86
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "bmi2")))]
+pub fn deinterleave_bits_u64(m: u64) -> (u32, u32) {
+  #[cfg(target_arch = "x86_64")]
+  {
+    (
+      population_extract_u64(m, 0x5555_5555_5555_5555) as u32,
+      population_extract_u64(m, 0xAAAA_AAAA_AAAA_AAAA) as u32,
+    )
+  }
+  #[cfg(not(target_arch = "x86_64"))]
+  {
+    let (low, high) = (m as u32, (m >> 32) as u32);
+    let x = population_extract_u32(low, 0x5555_5555) | (population_extract_u32(high, 0x5555_5555) << 16);
+    let y = population_extract_u32(low, 0xAAAA_AAAA) | (population_extract_u32(high, 0xAAAA_AAAA) << 16);
+    (x, y)
+  }
+}
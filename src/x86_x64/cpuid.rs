@@ -0,0 +1,750 @@
+//! Safe access to the `cpuid` instruction.
+//!
+//! Unlike the other modules, this one isn't gated on a target feature. Every
+//! `x86_64` CPU has `cpuid`, and so does any `x86` CPU new enough to run the
+//! rest of this crate, so it's always available.
+
+use super::*;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+#[cfg(target_arch = "x86")]
+pub use core::arch::x86::CpuidResult;
+#[cfg(target_arch = "x86_64")]
+pub use core::arch::x86_64::CpuidResult;
+
+/// Runs `cpuid` with the given leaf (and a sub-leaf of 0).
+///
+/// Leaves above the CPU's maximum supported leaf (see [`cpuid_max_leaf`]) give
+/// back whatever the CPU feels like, so check that first.
+///
+/// * **Intrinsic:** [`__cpuid`]
+/// * **Assembly:** `cpuid`
+#[must_use]
+#[inline(always)]
+pub fn cpuid(leaf: u32) -> CpuidResult {
+  // Note: newer compilers consider this intrinsic safe to call.
+  #[allow(unused_unsafe)]
+  unsafe {
+    __cpuid(leaf)
+  }
+}
+
+/// Runs `cpuid` with the given leaf and sub-leaf.
+///
+/// * **Intrinsic:** [`__cpuid_count`]
+/// * **Assembly:** `cpuid`
+#[must_use]
+#[inline(always)]
+pub fn cpuid_count(leaf: u32, sub_leaf: u32) -> CpuidResult {
+  // Note: newer compilers consider this intrinsic safe to call.
+  #[allow(unused_unsafe)]
+  unsafe {
+    __cpuid_count(leaf, sub_leaf)
+  }
+}
+
+/// The highest basic leaf that the CPU supports.
+#[must_use]
+#[inline]
+pub fn cpuid_max_leaf() -> u32 {
+  cpuid(0).eax
+}
+
+/// The highest extended leaf (`0x8000_0000` and up) that the CPU supports.
+#[must_use]
+#[inline]
+pub fn cpuid_max_extended_leaf() -> u32 {
+  cpuid(0x8000_0000).eax
+}
+
+/// The 12 byte vendor string, such as `b"GenuineIntel"` or `b"AuthenticAMD"`.
+/// ```
+/// # use safe_arch::*;
+/// let vendor = cpu_vendor_string();
+/// assert!(vendor.iter().all(|b| b.is_ascii()));
+/// ```
+#[must_use]
+#[inline]
+pub fn cpu_vendor_string() -> [u8; 12] {
+  let CpuidResult { ebx, ecx, edx, .. } = cpuid(0);
+  let mut out = [0_u8; 12];
+  out[0..4].copy_from_slice(&ebx.to_le_bytes());
+  out[4..8].copy_from_slice(&edx.to_le_bytes());
+  out[8..12].copy_from_slice(&ecx.to_le_bytes());
+  out
+}
+
+/// The 48 byte processor brand string, if the CPU has one.
+///
+/// The string is padded with trailing zero bytes, and some CPUs also add
+/// leading spaces.
+#[must_use]
+#[inline]
+pub fn cpu_brand_string() -> Option<[u8; 48]> {
+  if cpuid_max_extended_leaf() < 0x8000_0004 {
+    return None;
+  }
+  let mut out = [0_u8; 48];
+  for (i, chunk) in out.chunks_exact_mut(16).enumerate() {
+    let CpuidResult { eax, ebx, ecx, edx } = cpuid(0x8000_0002 + i as u32);
+    chunk[0..4].copy_from_slice(&eax.to_le_bytes());
+    chunk[4..8].copy_from_slice(&ebx.to_le_bytes());
+    chunk[8..12].copy_from_slice(&ecx.to_le_bytes());
+    chunk[12..16].copy_from_slice(&edx.to_le_bytes());
+  }
+  Some(out)
+}
+
+/// The family, model, and stepping of a CPU.
+///
+/// The family and model values already have the "extended" bits folded in the
+/// way that both Intel and AMD document.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CpuVersion {
+  /// The CPU family.
+  pub family: u32,
+  /// The model within the family.
+  pub model: u32,
+  /// The stepping (revision) of the model.
+  pub stepping: u32,
+}
+
+/// Reads the family, model, and stepping of the CPU.
+#[must_use]
+#[inline]
+pub fn cpu_version() -> CpuVersion {
+  let eax = cpuid(1).eax;
+  let stepping = eax & 0xF;
+  let base_model = (eax >> 4) & 0xF;
+  let base_family = (eax >> 8) & 0xF;
+  let ext_model = (eax >> 16) & 0xF;
+  let ext_family = (eax >> 20) & 0xFF;
+  let family =
+    if base_family == 0xF { base_family + ext_family } else { base_family };
+  let model = if base_family == 0x6 || base_family == 0xF {
+    (ext_model << 4) | base_model
+  } else {
+    base_model
+  };
+  CpuVersion { family, model, stepping }
+}
+
+/// The size of a cache line, in bytes, as reported by `clflush`.
+/// ```
+/// # use safe_arch::*;
+/// let line = cpu_cache_line_size();
+/// assert!(line == 0 || line.is_power_of_two());
+/// ```
+#[must_use]
+#[inline]
+pub fn cpu_cache_line_size() -> u32 {
+  ((cpuid(1).ebx >> 8) & 0xFF) * 8
+}
+
+/// The CPU features that the `cpuid` instruction reports.
+///
+/// Each field has the same name as the `target_feature` it corresponds to
+/// (with `.` changed to `_`). The exception is `osxsave`, which says if the OS
+/// has turned on `xgetbv`. Its `Display` lists the features that are set, which
+/// is handy for logs.
+///
+/// **Note:** `cpuid` only says what the CPU can do. For the `avx` family the OS
+/// also has to enable the wider register state, so use [`os_supports_avx`] and
+/// [`os_supports_avx512`] for those.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+pub struct CpuFeatures {
+  pub sse: bool,
+  pub sse2: bool,
+  pub sse3: bool,
+  pub ssse3: bool,
+  pub sse4_1: bool,
+  pub sse4_2: bool,
+  pub popcnt: bool,
+  pub aes: bool,
+  pub pclmulqdq: bool,
+  pub rdrand: bool,
+  pub f16c: bool,
+  pub fma: bool,
+  pub xsave: bool,
+  pub osxsave: bool,
+  pub avx: bool,
+  pub avx2: bool,
+  pub bmi1: bool,
+  pub bmi2: bool,
+  pub adx: bool,
+  pub rdseed: bool,
+  pub sha: bool,
+  pub lzcnt: bool,
+  pub avx512f: bool,
+  pub avx512cd: bool,
+  pub avx512dq: bool,
+  pub avx512bw: bool,
+  pub avx512vl: bool,
+  pub avx512ifma: bool,
+  pub avx512vbmi: bool,
+  pub avx512vbmi2: bool,
+  pub avx512vnni: bool,
+  pub avx512bitalg: bool,
+  pub avx512vpopcntdq: bool,
+  pub gfni: bool,
+  pub vaes: bool,
+  pub vpclmulqdq: bool,
+  pub movbe: bool,
+  pub sse4a: bool,
+  pub cmpxchg16b: bool,
+  pub lahfsahf: bool,
+}
+
+impl CpuFeatures {
+  /// Decodes the feature bits that `cpuid` reports for this CPU.
+  ///
+  /// This is the same as [`cpu_features`], so it's also cached.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Self {
+    cpu_features()
+  }
+
+  /// No features at all.
+  ///
+  /// Unlike `Default::default()` this is a constant, so it works with struct
+  /// update syntax in a `const` or `static`.
+  pub const NONE: Self = Self::from_bits(0);
+
+  /// If every feature set in `other` is also set in `self`.
+  /// ```
+  /// # use safe_arch::*;
+  /// let sse = CpuFeatures { sse: true, sse2: true, ..CpuFeatures::NONE };
+  /// let more = CpuFeatures { sse4_1: true, ..sse };
+  /// assert!(more.contains(sse));
+  /// assert!(!sse.contains(more));
+  /// ```
+  #[must_use]
+  pub const fn contains(self, other: Self) -> bool {
+    other.to_bits() & !self.to_bits() == 0
+  }
+
+  /// Packs the fields into bits, in the order of [`as_list`](Self::as_list).
+  #[must_use]
+  const fn to_bits(self) -> u64 {
+    let list = self.as_list();
+    let mut bits = 0;
+    let mut i = 0;
+    while i < list.len() {
+      bits |= (list[i].1 as u64) << i;
+      i += 1;
+    }
+    bits
+  }
+
+  /// The reverse of `to_bits`.
+  #[must_use]
+  const fn from_bits(bits: u64) -> Self {
+    Self {
+      sse: bits & (1 << 0) != 0,
+      sse2: bits & (1 << 1) != 0,
+      sse3: bits & (1 << 2) != 0,
+      ssse3: bits & (1 << 3) != 0,
+      sse4_1: bits & (1 << 4) != 0,
+      sse4_2: bits & (1 << 5) != 0,
+      sse4a: bits & (1 << 6) != 0,
+      popcnt: bits & (1 << 7) != 0,
+      aes: bits & (1 << 8) != 0,
+      pclmulqdq: bits & (1 << 9) != 0,
+      rdrand: bits & (1 << 10) != 0,
+      f16c: bits & (1 << 11) != 0,
+      fma: bits & (1 << 12) != 0,
+      movbe: bits & (1 << 13) != 0,
+      xsave: bits & (1 << 14) != 0,
+      osxsave: bits & (1 << 15) != 0,
+      avx: bits & (1 << 16) != 0,
+      avx2: bits & (1 << 17) != 0,
+      bmi1: bits & (1 << 18) != 0,
+      bmi2: bits & (1 << 19) != 0,
+      adx: bits & (1 << 20) != 0,
+      rdseed: bits & (1 << 21) != 0,
+      sha: bits & (1 << 22) != 0,
+      lzcnt: bits & (1 << 23) != 0,
+      gfni: bits & (1 << 24) != 0,
+      vaes: bits & (1 << 25) != 0,
+      vpclmulqdq: bits & (1 << 26) != 0,
+      avx512f: bits & (1 << 27) != 0,
+      avx512cd: bits & (1 << 28) != 0,
+      avx512dq: bits & (1 << 29) != 0,
+      avx512bw: bits & (1 << 30) != 0,
+      avx512vl: bits & (1 << 31) != 0,
+      avx512ifma: bits & (1 << 32) != 0,
+      avx512vbmi: bits & (1 << 33) != 0,
+      avx512vbmi2: bits & (1 << 34) != 0,
+      avx512vnni: bits & (1 << 35) != 0,
+      avx512bitalg: bits & (1 << 36) != 0,
+      avx512vpopcntdq: bits & (1 << 37) != 0,
+      cmpxchg16b: bits & (1 << 38) != 0,
+      lahfsahf: bits & (1 << 39) != 0,
+    }
+  }
+
+  /// Each feature's `target_feature` name, along with if it's set.
+  #[must_use]
+  pub const fn as_list(&self) -> [(&'static str, bool); 40] {
+    [
+      ("sse", self.sse),
+      ("sse2", self.sse2),
+      ("sse3", self.sse3),
+      ("ssse3", self.ssse3),
+      ("sse4.1", self.sse4_1),
+      ("sse4.2", self.sse4_2),
+      ("sse4a", self.sse4a),
+      ("popcnt", self.popcnt),
+      ("aes", self.aes),
+      ("pclmulqdq", self.pclmulqdq),
+      ("rdrand", self.rdrand),
+      ("f16c", self.f16c),
+      ("fma", self.fma),
+      ("movbe", self.movbe),
+      ("xsave", self.xsave),
+      ("osxsave", self.osxsave),
+      ("avx", self.avx),
+      ("avx2", self.avx2),
+      ("bmi1", self.bmi1),
+      ("bmi2", self.bmi2),
+      ("adx", self.adx),
+      ("rdseed", self.rdseed),
+      ("sha", self.sha),
+      ("lzcnt", self.lzcnt),
+      ("gfni", self.gfni),
+      ("vaes", self.vaes),
+      ("vpclmulqdq", self.vpclmulqdq),
+      ("avx512f", self.avx512f),
+      ("avx512cd", self.avx512cd),
+      ("avx512dq", self.avx512dq),
+      ("avx512bw", self.avx512bw),
+      ("avx512vl", self.avx512vl),
+      ("avx512ifma", self.avx512ifma),
+      ("avx512vbmi", self.avx512vbmi),
+      ("avx512vbmi2", self.avx512vbmi2),
+      ("avx512vnni", self.avx512vnni),
+      ("avx512bitalg", self.avx512bitalg),
+      ("avx512vpopcntdq", self.avx512vpopcntdq),
+      ("cmpxchg16b", self.cmpxchg16b),
+      ("lahfsahf", self.lahfsahf),
+    ]
+  }
+
+  /// Looks up a feature by its `target_feature` name, such as `"sse4.1"`.
+  ///
+  /// Gives `None` if the name isn't one of the fields.
+  /// ```
+  /// # use safe_arch::*;
+  /// let f = CpuFeatures::detect();
+  /// assert_eq!(f.has("sse4.1"), Some(f.sse4_1));
+  /// assert_eq!(f.has("not_a_feature"), None);
+  /// ```
+  #[must_use]
+  pub fn has(&self, name: &str) -> Option<bool> {
+    self.as_list().iter().find(|(n, _)| *n == name).map(|(_, b)| *b)
+  }
+}
+
+/// Lists the features that are set, separated by spaces.
+/// ```
+/// # use safe_arch::*;
+/// let f = CpuFeatures { sse: true, sse2: true, sse4_1: true, ..Default::default() };
+/// assert_eq!(format!("{}", f), "sse sse2 sse4.1");
+/// ```
+impl Display for CpuFeatures {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    let mut first = true;
+    for (name, _) in self.as_list().iter().filter(|(_, b)| *b) {
+      if !first {
+        f.write_str(" ")?;
+      }
+      f.write_str(name)?;
+      first = false;
+    }
+    Ok(())
+  }
+}
+
+/// The detected features, packed by `CpuFeatures::to_bits`, along with the
+/// flags below. Zero means that detection hasn't run yet.
+static DETECTED: AtomicU64 = AtomicU64::new(0);
+const DETECTED_READY: u64 = 1 << 63;
+const DETECTED_OS_AVX: u64 = 1 << 62;
+const DETECTED_OS_AVX512: u64 = 1 << 61;
+
+/// Gets the detected bits, running detection if this is the first call.
+///
+/// If two threads race on the first call they both run detection, which is
+/// fine because they both get the same answer.
+#[inline]
+fn detected() -> u64 {
+  let bits = DETECTED.load(Ordering::Relaxed);
+  if bits & DETECTED_READY != 0 {
+    return bits;
+  }
+  let features = detect_cpu_features();
+  let xcr0 = read_xcr0().unwrap_or(0);
+  // SSE state (bit 1) and AVX state (bit 2).
+  const AVX_STATE: u64 = 0b110;
+  // SSE, AVX, opmask (bit 5), and the two halves of ZMM state (bits 6 and 7).
+  const AVX512_STATE: u64 = 0b1110_0110;
+  let mut bits = features.to_bits() | DETECTED_READY;
+  if features.avx && xcr0 & AVX_STATE == AVX_STATE {
+    bits |= DETECTED_OS_AVX;
+  }
+  if features.avx512f && xcr0 & AVX512_STATE == AVX512_STATE {
+    bits |= DETECTED_OS_AVX512;
+  }
+  DETECTED.store(bits, Ordering::Relaxed);
+  bits
+}
+
+/// Decodes the feature bits that `cpuid` reports for this CPU.
+///
+/// Detection only runs on the first call, after which the result is cached in
+/// a static, so it's cheap enough to check on every call of a hot function.
+/// ```
+/// # use safe_arch::*;
+/// let features = cpu_features();
+/// if cfg!(target_feature = "sse2") {
+///   assert!(features.sse2);
+/// }
+/// ```
+#[must_use]
+#[inline]
+pub fn cpu_features() -> CpuFeatures {
+  CpuFeatures::from_bits(detected())
+}
+
+#[inline(never)]
+fn detect_cpu_features() -> CpuFeatures {
+  #[inline(always)]
+  const fn bit(x: u32, b: u32) -> bool {
+    (x >> b) & 1 != 0
+  }
+  let max_leaf = cpuid_max_leaf();
+  let leaf1 = if max_leaf >= 1 { cpuid(1) } else { cpuid_zero() };
+  let leaf7 = if max_leaf >= 7 { cpuid_count(7, 0) } else { cpuid_zero() };
+  let ext1 = if cpuid_max_extended_leaf() >= 0x8000_0001 {
+    cpuid(0x8000_0001)
+  } else {
+    cpuid_zero()
+  };
+  CpuFeatures {
+    sse: bit(leaf1.edx, 25),
+    sse2: bit(leaf1.edx, 26),
+    sse3: bit(leaf1.ecx, 0),
+    ssse3: bit(leaf1.ecx, 9),
+    sse4_1: bit(leaf1.ecx, 19),
+    sse4_2: bit(leaf1.ecx, 20),
+    popcnt: bit(leaf1.ecx, 23),
+    aes: bit(leaf1.ecx, 25),
+    pclmulqdq: bit(leaf1.ecx, 1),
+    rdrand: bit(leaf1.ecx, 30),
+    f16c: bit(leaf1.ecx, 29),
+    fma: bit(leaf1.ecx, 12),
+    xsave: bit(leaf1.ecx, 26),
+    osxsave: bit(leaf1.ecx, 27),
+    avx: bit(leaf1.ecx, 28),
+    avx2: bit(leaf7.ebx, 5),
+    bmi1: bit(leaf7.ebx, 3),
+    bmi2: bit(leaf7.ebx, 8),
+    adx: bit(leaf7.ebx, 19),
+    rdseed: bit(leaf7.ebx, 18),
+    sha: bit(leaf7.ebx, 29),
+    lzcnt: bit(ext1.ecx, 5),
+    avx512f: bit(leaf7.ebx, 16),
+    avx512cd: bit(leaf7.ebx, 28),
+    avx512dq: bit(leaf7.ebx, 17),
+    avx512bw: bit(leaf7.ebx, 30),
+    avx512vl: bit(leaf7.ebx, 31),
+    avx512ifma: bit(leaf7.ebx, 21),
+    avx512vbmi: bit(leaf7.ecx, 1),
+    avx512vbmi2: bit(leaf7.ecx, 6),
+    avx512vnni: bit(leaf7.ecx, 11),
+    avx512bitalg: bit(leaf7.ecx, 12),
+    avx512vpopcntdq: bit(leaf7.ecx, 14),
+    gfni: bit(leaf7.ecx, 8),
+    vaes: bit(leaf7.ecx, 9),
+    vpclmulqdq: bit(leaf7.ecx, 10),
+    movbe: bit(leaf1.ecx, 22),
+    sse4a: bit(ext1.ecx, 6),
+    cmpxchg16b: bit(leaf1.ecx, 13),
+    lahfsahf: bit(ext1.ecx, 0),
+  }
+}
+
+#[inline(always)]
+const fn cpuid_zero() -> CpuidResult {
+  CpuidResult { eax: 0, ebx: 0, ecx: 0, edx: 0 }
+}
+
+/// Reads the `XCR0` register, which says what register state the OS saves.
+///
+/// This is `None` if the OS hasn't enabled `xgetbv` (`osxsave` is unset), in
+/// which case none of the extended register state should be assumed usable.
+/// ```
+/// # use safe_arch::*;
+/// if let Some(xcr0) = read_xcr0() {
+///   // x87 state is always enabled.
+///   assert_eq!(xcr0 & 1, 1);
+/// }
+/// ```
+/// * **Intrinsic:** [`_xgetbv`]
+/// * **Assembly:** `xgetbv`
+#[must_use]
+#[inline]
+pub fn read_xcr0() -> Option<u64> {
+  #[target_feature(enable = "xsave")]
+  unsafe fn xgetbv0() -> u64 {
+    _xgetbv(0)
+  }
+  if cpuid_max_leaf() >= 1 && (cpuid(1).ecx >> 27) & 1 != 0 {
+    // Safety: `osxsave` being set means that `xgetbv` is available.
+    Some(unsafe { xgetbv0() })
+  } else {
+    None
+  }
+}
+
+/// If the CPU has `avx` *and* the OS has enabled the `ymm` register state.
+///
+/// The `cpuid` bit alone isn't enough to use `avx`, because an OS that doesn't
+/// save the upper register halves on a context switch will corrupt them. As
+/// with [`cpu_features`], the answer is cached after the first call.
+#[must_use]
+#[inline]
+pub fn os_supports_avx() -> bool {
+  detected() & DETECTED_OS_AVX != 0
+}
+
+/// If the CPU has `avx512f` *and* the OS has enabled the `zmm` register state.
+///
+/// As with [`os_supports_avx`], the `cpuid` bit alone isn't enough.
+#[must_use]
+#[inline]
+pub fn os_supports_avx512() -> bool {
+  detected() & DETECTED_OS_AVX512 != 0
+}
+
+/// If the CPU (and OS) meet the `x86-64-v2` level of the `x86_64` psABI.
+///
+/// That's `cmpxchg16b`, `lahfsahf`, `popcnt`, `sse3`, `ssse3`, `sse4.1`, and
+/// `sse4.2`, on top of the baseline.
+#[cfg(target_arch = "x86_64")]
+#[must_use]
+#[inline]
+pub fn supports_x86_64_v2() -> bool {
+  let f = cpu_features();
+  f.cmpxchg16b && f.lahfsahf && f.popcnt && f.sse3 && f.ssse3 && f.sse4_1 && f.sse4_2
+}
+
+/// If the CPU (and OS) meet the `x86-64-v3` level of the `x86_64` psABI.
+///
+/// That's `avx`, `avx2`, `bmi1`, `bmi2`, `f16c`, `fma`, `lzcnt`, `movbe`, and
+/// OS support for the `ymm` registers, on top of `x86-64-v2`.
+#[cfg(target_arch = "x86_64")]
+#[must_use]
+#[inline]
+pub fn supports_x86_64_v3() -> bool {
+  let f = cpu_features();
+  supports_x86_64_v2()
+    && os_supports_avx()
+    && f.avx2
+    && f.bmi1
+    && f.bmi2
+    && f.f16c
+    && f.fma
+    && f.lzcnt
+    && f.movbe
+}
+
+/// If the CPU (and OS) meet the `x86-64-v4` level of the `x86_64` psABI.
+///
+/// That's `avx512f`, `avx512bw`, `avx512cd`, `avx512dq`, `avx512vl`, and OS
+/// support for the `zmm` registers, on top of `x86-64-v3`.
+/// ```
+/// # use safe_arch::*;
+/// if supports_x86_64_v4() {
+///   assert!(supports_x86_64_v3() && supports_x86_64_v2());
+/// }
+/// if COMPILED_FOR_X86_64_V3 {
+///   assert!(supports_x86_64_v3());
+/// }
+/// ```
+#[cfg(target_arch = "x86_64")]
+#[must_use]
+#[inline]
+pub fn supports_x86_64_v4() -> bool {
+  let f = cpu_features();
+  supports_x86_64_v3() && os_supports_avx512() && f.avx512bw && f.avx512cd && f.avx512dq && f.avx512vl
+}
+
+/// If the build's target features include all of `x86-64-v2`.
+///
+/// The level also includes `lahfsahf`, which is left out here on purpose.
+/// That target feature isn't stable, so the compiler never sets
+/// `cfg(target_feature = "lahfsahf")`, not even with `-Ctarget-cpu=x86-64-v2`,
+/// and checking for it would make this always `false`. No function of this
+/// crate uses `lahf` or `sahf`, so the other features are enough to know
+/// that everything here that `x86-64-v2` allows is in the build. Only
+/// [`supports_x86_64_v2`] checks for `lahfsahf`, at runtime.
+#[cfg(target_arch = "x86_64")]
+pub const COMPILED_FOR_X86_64_V2: bool = cfg!(all(
+  target_feature = "cmpxchg16b",
+  target_feature = "popcnt",
+  target_feature = "sse3",
+  target_feature = "ssse3",
+  target_feature = "sse4.1",
+  target_feature = "sse4.2",
+));
+
+/// If the build's target features include all of `x86-64-v3`.
+#[cfg(target_arch = "x86_64")]
+pub const COMPILED_FOR_X86_64_V3: bool = COMPILED_FOR_X86_64_V2
+  && cfg!(all(
+    target_feature = "avx",
+    target_feature = "avx2",
+    target_feature = "bmi1",
+    target_feature = "bmi2",
+    target_feature = "f16c",
+    target_feature = "fma",
+    target_feature = "lzcnt",
+    target_feature = "movbe",
+  ));
+
+/// If the build's target features include all of `x86-64-v4`.
+#[cfg(target_arch = "x86_64")]
+pub const COMPILED_FOR_X86_64_V4: bool = COMPILED_FOR_X86_64_V3
+  && cfg!(all(
+    target_feature = "avx512f",
+    target_feature = "avx512bw",
+    target_feature = "avx512cd",
+    target_feature = "avx512dq",
+    target_feature = "avx512vl",
+  ));
+
+/// The features that the CPU has *and* that the OS lets programs use.
+///
+/// This is [`cpu_features`] with the `avx` family cleared if
+/// [`os_supports_avx`] is false, and the `avx512` family cleared if
+/// [`os_supports_avx512`] is false.
+#[must_use]
+#[inline]
+pub fn usable_cpu_features() -> CpuFeatures {
+  let mut have = cpu_features();
+  if !os_supports_avx() {
+    have = CpuFeatures { avx: false, avx2: false, fma: false, f16c: false, vaes: false, vpclmulqdq: false, ..have };
+  }
+  if !os_supports_avx512() {
+    have = CpuFeatures {
+      avx512f: false,
+      avx512cd: false,
+      avx512dq: false,
+      avx512bw: false,
+      avx512vl: false,
+      avx512ifma: false,
+      avx512vbmi: false,
+      avx512vbmi2: false,
+      avx512vnni: false,
+      avx512bitalg: false,
+      avx512vpopcntdq: false,
+      ..have
+    };
+  }
+  have
+}
+
+/// The features that this build's target features rely on.
+pub(crate) const COMPILED_FEATURES: CpuFeatures = CpuFeatures {
+  sse: cfg!(target_feature = "sse"),
+  sse2: cfg!(target_feature = "sse2"),
+  sse3: cfg!(target_feature = "sse3"),
+  ssse3: cfg!(target_feature = "ssse3"),
+  sse4_1: cfg!(target_feature = "sse4.1"),
+  sse4_2: cfg!(target_feature = "sse4.2"),
+  sse4a: cfg!(target_feature = "sse4a"),
+  popcnt: cfg!(target_feature = "popcnt"),
+  aes: cfg!(target_feature = "aes"),
+  pclmulqdq: cfg!(target_feature = "pclmulqdq"),
+  rdrand: cfg!(target_feature = "rdrand"),
+  f16c: cfg!(target_feature = "f16c"),
+  fma: cfg!(target_feature = "fma"),
+  movbe: cfg!(target_feature = "movbe"),
+  xsave: cfg!(target_feature = "xsave"),
+  // this one is OS state, not a target feature
+  osxsave: false,
+  avx: cfg!(target_feature = "avx"),
+  avx2: cfg!(target_feature = "avx2"),
+  bmi1: cfg!(target_feature = "bmi1"),
+  bmi2: cfg!(target_feature = "bmi2"),
+  adx: cfg!(target_feature = "adx"),
+  rdseed: cfg!(target_feature = "rdseed"),
+  sha: cfg!(target_feature = "sha"),
+  lzcnt: cfg!(target_feature = "lzcnt"),
+  gfni: cfg!(target_feature = "gfni"),
+  vaes: cfg!(target_feature = "vaes"),
+  vpclmulqdq: cfg!(target_feature = "vpclmulqdq"),
+  avx512f: cfg!(target_feature = "avx512f"),
+  avx512cd: cfg!(target_feature = "avx512cd"),
+  avx512dq: cfg!(target_feature = "avx512dq"),
+  avx512bw: cfg!(target_feature = "avx512bw"),
+  avx512vl: cfg!(target_feature = "avx512vl"),
+  avx512ifma: cfg!(target_feature = "avx512ifma"),
+  avx512vbmi: cfg!(target_feature = "avx512vbmi"),
+  avx512vbmi2: cfg!(target_feature = "avx512vbmi2"),
+  avx512vnni: cfg!(target_feature = "avx512vnni"),
+  avx512bitalg: cfg!(target_feature = "avx512bitalg"),
+  avx512vpopcntdq: cfg!(target_feature = "avx512vpopcntdq"),
+  cmpxchg16b: cfg!(target_feature = "cmpxchg16b"),
+  lahfsahf: cfg!(target_feature = "lahfsahf"),
+};
+
+/// The target features that a build relies on but the CPU doesn't have.
+///
+/// This is the error of [`ensure_cpu_compatible`]. Its `Display` gives a
+/// message listing each missing feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MissingCpuFeatures {
+  /// Set for each feature that was enabled at compile time but is missing.
+  pub missing: CpuFeatures,
+}
+
+impl Display for MissingCpuFeatures {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    write!(
+      f,
+      "this program was built for CPU features that this CPU doesn't have: {} (they were enabled at compile time with `-C target-feature` or `-C target-cpu`)",
+      self.missing
+    )
+  }
+}
+
+/// Checks that the CPU has every target feature that the build was compiled
+/// with.
+///
+/// Running code built for features that the CPU doesn't have is Undefined
+/// Behavior, so a program built with extra target features should call this
+/// first thing in `main`, before any of that code runs. Reporting the error
+/// gives users a clear message instead of a crash (or worse).
+///
+/// For `avx` and above, this also checks that the OS has enabled the wider
+/// registers.
+/// ```
+/// # use safe_arch::*;
+/// if let Err(e) = ensure_cpu_compatible() {
+///   panic!("{}", e);
+/// }
+/// ```
+#[inline]
+pub fn ensure_cpu_compatible() -> Result<(), MissingCpuFeatures> {
+  let have = usable_cpu_features();
+  let missing = COMPILED_FEATURES.to_bits() & !have.to_bits();
+  if missing == 0 {
+    Ok(())
+  } else {
+    Err(MissingCpuFeatures { missing: CpuFeatures::from_bits(missing) })
+  }
+}
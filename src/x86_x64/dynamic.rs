@@ -0,0 +1,81 @@
+#![cfg(feature = "runtime")]
+// The copied function bodies keep their `unsafe` blocks, which aren't needed
+// within an `unsafe fn`.
+#![allow(unused_unsafe)]
+//! Functions compiled with `#[target_feature]` instead of picked with `cfg`.
+//!
+//! Everything here exists in any build (with the `runtime` feature), whatever
+//! the target features are, so a program built for a baseline CPU can still
+//! reach newer instructions once it has checked that the CPU has them. Each
+//! function has the same name and does the same thing as the `cfg` version,
+//! but it's `unsafe` to call, because calling it on a CPU without the feature
+//! is Undefined Behavior. The token types in the crate root (such as
+//! `Avx2Token`) are the safe way to call these: you get a token by checking the
+//! CPU, and its methods call into this module.
+//!
+//! Mark your own functions with the same `#[target_feature(enable = ..)]` to
+//! let these inline into them.
+//!
+//! Each CPU feature module (`sse` through `avx2`, and `adx` through `rdseed`)
+//! is copied here by `build.rs`, as long as its Cargo feature is on, and its
+//! token has a method for each of its public functions. A few things are left
+//! out:
+//! * The `const fn` constructors (such as `zeroed_m256i`) don't use any
+//!   feature, so they're copied as safe functions, and the tokens don't have
+//!   methods for them.
+//! * Types with methods stay in the `cfg` modules. That's `StreamingStores`,
+//!   `StreamingLoads`, `Backoff`, `CycleTimer`, and `FlushDenormalsGuard`, so
+//!   `streaming_stores` and `streaming_loads` aren't here either. The
+//!   `CmpOp` and `RoundMode` enums, and the `STR_CMP_*` constants, are copied.
+//! * The `math` module isn't a CPU feature, so it isn't copied.
+//! ```
+//! # use safe_arch::*;
+//! let a = m256::from_array([2.0; 8]);
+//! if cpu_features().fma && os_supports_avx() {
+//!   // Safety: we just checked for the feature.
+//!   let c = unsafe { dynamic::fused_mul_add_m256(a, a, a) };
+//!   assert_eq!(c.to_array(), [6.0; 8]);
+//! }
+//! ```
+
+use super::*;
+
+#[cfg(feature = "sse")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_sse.rs"));
+#[cfg(feature = "sse2")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_sse2.rs"));
+#[cfg(feature = "sse3")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_sse3.rs"));
+#[cfg(feature = "ssse3")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_ssse3.rs"));
+#[cfg(feature = "sse4_1")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_sse4_1.rs"));
+#[cfg(feature = "sse4_2")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_sse4_2.rs"));
+#[cfg(feature = "avx")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_avx.rs"));
+#[cfg(feature = "avx2")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_avx2.rs"));
+
+#[cfg(feature = "adx")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_adx.rs"));
+#[cfg(feature = "aes")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_aes.rs"));
+#[cfg(feature = "bmi1")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_bmi1.rs"));
+#[cfg(feature = "bmi2")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_bmi2.rs"));
+#[cfg(feature = "f16c")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_f16c.rs"));
+#[cfg(feature = "fma")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_fma.rs"));
+#[cfg(feature = "lzcnt")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_lzcnt.rs"));
+#[cfg(feature = "pclmulqdq")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_pclmulqdq.rs"));
+#[cfg(feature = "popcnt")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_popcnt.rs"));
+#[cfg(feature = "rdrand")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_rdrand.rs"));
+#[cfg(feature = "rdseed")]
+include!(concat!(env!("OUT_DIR"), "/dynamic_rdseed.rs"));
@@ -0,0 +1,211 @@
+#![cfg(target_feature = "f16c")]
+
+use super::*;
+
+/// Convert the lower four `f16` lanes (as `u16` bits) to `f32` lanes.
+///
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([0x3C00_u16, 0xC000, 0x3800, 0x7C00, 1, 2, 3, 4]);
+/// let b = convert_to_m128_from_lower4_f16_m128i(a).to_array();
+/// assert_eq!(b, [1.0, -2.0, 0.5, f32::INFINITY]);
+/// ```
+/// * **Intrinsic:** [`_mm_cvtph_ps`]
+/// * **Assembly:** `vcvtph2ps xmm, xmm`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "f16c")))]
+pub fn convert_to_m128_from_lower4_f16_m128i(a: m128i) -> m128 {
+  m128(unsafe { _mm_cvtph_ps(a.0) })
+}
+
+/// Convert eight `f16` lanes (as `u16` bits) to `f32` lanes.
+///
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([0x3C00_u16, 0xC000, 0x3800, 0x7C00, 0, 0x8000, 0x4200, 0x5640]);
+/// let b = convert_to_m256_from_f16_m128i(a).to_array();
+/// assert_eq!(b, [1.0, -2.0, 0.5, f32::INFINITY, 0.0, -0.0, 3.0, 100.0]);
+/// ```
+/// * **Intrinsic:** [`_mm256_cvtph_ps`]
+/// * **Assembly:** `vcvtph2ps ymm, xmm`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "f16c")))]
+pub fn convert_to_m256_from_f16_m128i(a: m128i) -> m256 {
+  m256(unsafe { _mm256_cvtph_ps(a.0) })
+}
+
+/// Convert `f32` lanes to `f16` lanes (as `u16` bits) in the low half of the
+/// output.
+///
+/// Rounds to nearest (ties to even). The upper four `u16` lanes are zeroed.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128::from_array([1.0, -2.0, 0.5, 65536.0]);
+/// let b: [u16; 8] = convert_to_f16_m128i_from_m128(a).into();
+/// assert_eq!(b, [0x3C00, 0xC000, 0x3800, 0x7C00, 0, 0, 0, 0]);
+/// ```
+/// * **Intrinsic:** [`_mm_cvtps_ph`]
+/// * **Assembly:** `vcvtps2ph xmm, xmm, imm8`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "f16c")))]
+pub fn convert_to_f16_m128i_from_m128(a: m128) -> m128i {
+  m128i(unsafe { _mm_cvtps_ph(a.0, _MM_FROUND_TO_NEAREST_INT) })
+}
+
+/// Convert `f32` lanes to `f16` lanes (as `u16` bits).
+///
+/// Rounds to nearest (ties to even).
+/// ```
+/// # use safe_arch::*;
+/// let a = m256::from_array([1.0, -2.0, 0.5, 65536.0, 0.0, -0.0, 3.0, 100.0]);
+/// let b: [u16; 8] = convert_to_f16_m128i_from_m256(a).into();
+/// assert_eq!(b, [0x3C00, 0xC000, 0x3800, 0x7C00, 0, 0x8000, 0x4200, 0x5640]);
+/// ```
+/// * **Intrinsic:** [`_mm256_cvtps_ph`]
+/// * **Assembly:** `vcvtps2ph xmm, ymm, imm8`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "f16c")))]
+pub fn convert_to_f16_m128i_from_m256(a: m256) -> m128i {
+  m128i(unsafe { _mm256_cvtps_ph(a.0, _MM_FROUND_TO_NEAREST_INT) })
+}
+
+/// Converts a slice of `f32` values into `f16` values (as `u16` bits).
+///
+/// This works 8 lanes at a time with [`convert_to_f16_m128i_from_m256`]. The
+/// tail is padded out to a full register, so every value is rounded the same
+/// way (to nearest, ties to even).
+///
+/// Panics if the slices aren't the same length.
+/// ```
+/// # use safe_arch::*;
+/// let src: Vec<f32> = (0..21).map(|i| i as f32 * 0.5).collect();
+/// let mut dst = vec![0_u16; 21];
+/// convert_f32_slice_to_f16(&src, &mut dst);
+/// assert_eq!(&dst[..4], &[0x0000, 0x3800, 0x3C00, 0x3E00]);
+/// assert_eq!(dst[20], 0x4900);
+/// ```
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "f16c")))]
+pub fn convert_f32_slice_to_f16(src: &[f32], dst: &mut [u16]) {
+  assert_eq!(src.len(), dst.len(), "convert_f32_slice_to_f16: slices must be the same length");
+  let mut src_chunks = src.chunks_exact(8);
+  let mut dst_chunks = dst.chunks_exact_mut(8);
+  for (s, d) in (&mut src_chunks).zip(&mut dst_chunks) {
+    let a = m256::from_array(core::convert::TryInto::try_into(s).unwrap());
+    let bits: [u16; 8] = convert_to_f16_m128i_from_m256(a).into();
+    d.copy_from_slice(&bits);
+  }
+  let (s, d) = (src_chunks.remainder(), dst_chunks.into_remainder());
+  if !s.is_empty() {
+    let mut padded = [0.0; 8];
+    padded[..s.len()].copy_from_slice(s);
+    let bits: [u16; 8] = convert_to_f16_m128i_from_m256(m256::from_array(padded)).into();
+    d.copy_from_slice(&bits[..d.len()]);
+  }
+}
+
+/// Converts a slice of `f16` values (as `u16` bits) into `f32` values.
+///
+/// This works 8 lanes at a time with [`convert_to_m256_from_f16_m128i`], and
+/// is exact.
+///
+/// Panics if the slices aren't the same length.
+/// ```
+/// # use safe_arch::*;
+/// let src: Vec<u16> = (0..21).map(|i| 0x3C00 + i).collect();
+/// let mut dst = vec![0.0; 21];
+/// convert_f16_slice_to_f32(&src, &mut dst);
+/// for (i, d) in dst.iter().enumerate() {
+///   assert_eq!(*d, 1.0 + i as f32 / 1024.0);
+/// }
+/// ```
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "f16c")))]
+pub fn convert_f16_slice_to_f32(src: &[u16], dst: &mut [f32]) {
+  assert_eq!(src.len(), dst.len(), "convert_f16_slice_to_f32: slices must be the same length");
+  let mut src_chunks = src.chunks_exact(8);
+  let mut dst_chunks = dst.chunks_exact_mut(8);
+  for (s, d) in (&mut src_chunks).zip(&mut dst_chunks) {
+    let bits: [u16; 8] = core::convert::TryInto::try_into(s).unwrap();
+    d.copy_from_slice(&convert_to_m256_from_f16_m128i(m128i::from(bits)).to_array());
+  }
+  let (s, d) = (src_chunks.remainder(), dst_chunks.into_remainder());
+  if !s.is_empty() {
+    let mut padded = [0_u16; 8];
+    padded[..s.len()].copy_from_slice(s);
+    let floats = convert_to_m256_from_f16_m128i(m128i::from(padded)).to_array();
+    d.copy_from_slice(&floats[..d.len()]);
+  }
+}
+
+/// Convert an array of [`half::f16`] values to `f32` lanes.
+///
+/// ```
+/// # use safe_arch::*;
+/// use half::f16;
+/// let a = [f16::ONE, f16::NEG_ONE, f16::from_f32(0.25), f16::INFINITY];
+/// assert_eq!(convert_to_m128_from_f16_array(a).to_array(), [1.0, -1.0, 0.25, f32::INFINITY]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg(feature = "half")]
+#[cfg_attr(docsrs, doc(cfg(all(target_feature = "f16c", feature = "half"))))]
+pub fn convert_to_m128_from_f16_array(a: [half::f16; 4]) -> m128 {
+  let bits = a.map(half::f16::to_bits);
+  convert_to_m128_from_lower4_f16_m128i(m128i::from([bits[0], bits[1], bits[2], bits[3], 0, 0, 0, 0]))
+}
+
+/// Convert an array of [`half::f16`] values to `f32` lanes.
+///
+/// ```
+/// # use safe_arch::*;
+/// use half::f16;
+/// let a = [f16::from_f32(3.0); 8];
+/// assert_eq!(convert_to_m256_from_f16_array(a).to_array(), [3.0; 8]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg(feature = "half")]
+#[cfg_attr(docsrs, doc(cfg(all(target_feature = "f16c", feature = "half"))))]
+pub fn convert_to_m256_from_f16_array(a: [half::f16; 8]) -> m256 {
+  convert_to_m256_from_f16_m128i(m128i::from(a.map(half::f16::to_bits)))
+}
+
+/// Convert `f32` lanes to an array of [`half::f16`] values.
+///
+/// Rounds to nearest (ties to even).
+/// ```
+/// # use safe_arch::*;
+/// use half::f16;
+/// let a = m128::from_array([1.0, -1.0, 0.25, 1.0e9]);
+/// assert_eq!(convert_to_f16_array_from_m128(a), [f16::ONE, f16::NEG_ONE, f16::from_f32(0.25), f16::INFINITY]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg(feature = "half")]
+#[cfg_attr(docsrs, doc(cfg(all(target_feature = "f16c", feature = "half"))))]
+pub fn convert_to_f16_array_from_m128(a: m128) -> [half::f16; 4] {
+  let bits: [u16; 8] = convert_to_f16_m128i_from_m128(a).into();
+  [half::f16::from_bits(bits[0]), half::f16::from_bits(bits[1]), half::f16::from_bits(bits[2]), half::f16::from_bits(bits[3])]
+}
+
+/// Convert `f32` lanes to an array of [`half::f16`] values.
+///
+/// Rounds to nearest (ties to even).
+/// ```
+/// # use safe_arch::*;
+/// use half::f16;
+/// let a = m256::from_array([3.0; 8]);
+/// assert_eq!(convert_to_f16_array_from_m256(a), [f16::from_f32(3.0); 8]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg(feature = "half")]
+#[cfg_attr(docsrs, doc(cfg(all(target_feature = "f16c", feature = "half"))))]
+pub fn convert_to_f16_array_from_m256(a: m256) -> [half::f16; 8] {
+  <[u16; 8]>::from(convert_to_f16_m128i_from_m256(a)).map(half::f16::from_bits)
+}
@@ -28,8 +28,8 @@ impl m128d {
   /// Same as `m.into()`, just lets you be more explicit about what's happening.
   #[must_use]
   #[inline(always)]
-  pub fn to_array(self) -> [f64; 2] {
-    self.into()
+  pub const fn to_array(self) -> [f64; 2] {
+    unsafe { core::mem::transmute(self) }
   }
 
   /// Transmutes an array into `m128d`.
@@ -38,8 +38,8 @@ impl m128d {
   /// happening.
   #[must_use]
   #[inline(always)]
-  pub fn from_array(f: [f64; 2]) -> Self {
-    f.into()
+  pub const fn from_array(f: [f64; 2]) -> Self {
+    unsafe { core::mem::transmute(f) }
   }
 
   //
@@ -49,7 +49,7 @@ impl m128d {
   /// Like [`f64::to_bits`](f64::to_bits), but both lanes at once.
   #[must_use]
   #[inline(always)]
-  pub fn to_bits(self) -> [u64; 2] {
+  pub const fn to_bits(self) -> [u64; 2] {
     unsafe { core::mem::transmute(self) }
   }
 
@@ -58,9 +58,86 @@ impl m128d {
   /// Like [`f64::from_bits`](f64::from_bits), but both lanes at once.
   #[must_use]
   #[inline(always)]
-  pub fn from_bits(bits: [u64; 2]) -> Self {
+  pub const fn from_bits(bits: [u64; 2]) -> Self {
     unsafe { core::mem::transmute(bits) }
   }
+
+  //
+
+  /// Views the register as its bytes, without copying.
+  #[must_use]
+  #[inline(always)]
+  pub fn as_bytes(&self) -> &[u8; 16] {
+    // Safety: the type is `repr(transparent)` over the arch type, which is
+    // 16 bytes of plain data, and `u8` has no alignment requirement.
+    unsafe { &*(self as *const Self as *const [u8; 16]) }
+  }
+
+  /// Views the register as its bytes, mutably and without copying.
+  ///
+  /// Any bit pattern is a valid register value, so you can write any bytes.
+  #[inline(always)]
+  pub fn as_bytes_mut(&mut self) -> &mut [u8; 16] {
+    unsafe { &mut *(self as *mut Self as *mut [u8; 16]) }
+  }
+
+  /// Views the register as an array of its lanes, without copying.
+  /// ```
+  /// # use safe_arch::*;
+  /// let m = m128d::from_array([1.0; 2]);
+  /// assert_eq!(m.as_array_ref()[1], 1.0);
+  /// ```
+  #[must_use]
+  #[inline(always)]
+  pub fn as_array_ref(&self) -> &[f64; 2] {
+    // Safety: the register is aligned at least as much as the array needs.
+    unsafe { &*(self as *const Self as *const [f64; 2]) }
+  }
+
+  /// Views the register as an array of its lanes, mutably and without copying.
+  /// ```
+  /// # use safe_arch::*;
+  /// let mut m = m128d::from_array([1.0; 2]);
+  /// m.as_array_mut()[1] = 2.0;
+  /// assert_eq!(m.to_array()[1], 2.0);
+  /// ```
+  #[inline(always)]
+  pub fn as_array_mut(&mut self) -> &mut [f64; 2] {
+    unsafe { &mut *(self as *mut Self as *mut [f64; 2]) }
+  }
+
+  /// Gets the value of lane `L`.
+  ///
+  /// The index is checked at compile time, so this can't panic.
+  /// ```
+  /// # use safe_arch::*;
+  /// let m = m128d::from_array([1.0, 2.0]);
+  /// assert_eq!(m.lane::<1>(), 2.0);
+  /// ```
+  #[must_use]
+  #[inline(always)]
+  pub const fn lane<const L: i32>(self) -> f64 {
+    let () = ImmBits::<L, 1>::OK;
+    self.to_array()[L as usize]
+  }
+
+  /// Copies the register with lane `L` replaced by `x`.
+  ///
+  /// The index is checked at compile time, so this can't panic.
+  /// ```
+  /// # use safe_arch::*;
+  /// let m = m128d::from_array([1.0, 2.0]).with_lane::<0>(-1.0);
+  /// assert_eq!(m.lane::<0>(), -1.0);
+  /// assert_eq!(m.lane::<1>(), 2.0);
+  /// ```
+  #[must_use]
+  #[inline(always)]
+  pub const fn with_lane<const L: i32>(self, x: f64) -> Self {
+    let () = ImmBits::<L, 1>::OK;
+    let mut a = self.to_array();
+    a[L as usize] = x;
+    Self::from_array(a)
+  }
 }
 
 impl Clone for m128d {
@@ -100,6 +177,40 @@ impl From<m128d> for [f64; 2] {
   }
 }
 
+// portable SIMD
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<f64, 2>> for m128d {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<f64, 2>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m128d> for core::simd::Simd<f64, 2> {
+  #[inline(always)]
+  fn from(m: m128d) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+// core::arch
+
+impl From<__m128d> for m128d {
+  #[inline(always)]
+  fn from(raw: __m128d) -> Self {
+    Self(raw)
+  }
+}
+
+impl From<m128d> for __m128d {
+  #[inline(always)]
+  fn from(m: m128d) -> Self {
+    m.0
+  }
+}
+
 //
 // PLEASE KEEP ALL THE FORMAT IMPL JUNK AT THE END OF THE FILE
 //
@@ -10,7 +10,7 @@ use super::*;
 ///
 /// * The exact layout to view the type as depends on the operation used.
 /// * `From` and `Into` impls are provided for all the relevant signed integer
-///   array types.
+///   array types, and for the `core::arch` type that it wraps.
 /// * Formatting impls print as four `i32` values just because they have to pick
 ///   something. If you want an alternative you can turn it into an array and
 ///   print as you like.
@@ -25,6 +25,25 @@ unsafe impl bytemuck::Pod for m128i {}
 #[cfg(feature = "bytemuck")]
 unsafe impl bytemuck::TransparentWrapper<__m128i> for m128i {}
 
+impl m128i {
+  /// Views the register as its bytes, without copying.
+  #[must_use]
+  #[inline(always)]
+  pub fn as_bytes(&self) -> &[u8; 16] {
+    // Safety: the type is `repr(transparent)` over the arch type, which is
+    // 16 bytes of plain data, and `u8` has no alignment requirement.
+    unsafe { &*(self as *const Self as *const [u8; 16]) }
+  }
+
+  /// Views the register as its bytes, mutably and without copying.
+  ///
+  /// Any bit pattern is a valid register value, so you can write any bytes.
+  #[inline(always)]
+  pub fn as_bytes_mut(&mut self) -> &mut [u8; 16] {
+    unsafe { &mut *(self as *mut Self as *mut [u8; 16]) }
+  }
+}
+
 impl Clone for m128i {
   #[must_use]
   #[inline(always)]
@@ -212,6 +231,152 @@ impl From<m128i> for u128 {
   }
 }
 
+// portable SIMD
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<i8, 16>> for m128i {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<i8, 16>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m128i> for core::simd::Simd<i8, 16> {
+  #[inline(always)]
+  fn from(m: m128i) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<u8, 16>> for m128i {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<u8, 16>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m128i> for core::simd::Simd<u8, 16> {
+  #[inline(always)]
+  fn from(m: m128i) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<i16, 8>> for m128i {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<i16, 8>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m128i> for core::simd::Simd<i16, 8> {
+  #[inline(always)]
+  fn from(m: m128i) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<u16, 8>> for m128i {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<u16, 8>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m128i> for core::simd::Simd<u16, 8> {
+  #[inline(always)]
+  fn from(m: m128i) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<i32, 4>> for m128i {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<i32, 4>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m128i> for core::simd::Simd<i32, 4> {
+  #[inline(always)]
+  fn from(m: m128i) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<u32, 4>> for m128i {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<u32, 4>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m128i> for core::simd::Simd<u32, 4> {
+  #[inline(always)]
+  fn from(m: m128i) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<i64, 2>> for m128i {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<i64, 2>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m128i> for core::simd::Simd<i64, 2> {
+  #[inline(always)]
+  fn from(m: m128i) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<u64, 2>> for m128i {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<u64, 2>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m128i> for core::simd::Simd<u64, 2> {
+  #[inline(always)]
+  fn from(m: m128i) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+// core::arch
+
+impl From<__m128i> for m128i {
+  #[inline(always)]
+  fn from(raw: __m128i) -> Self {
+    Self(raw)
+  }
+}
+
+impl From<m128i> for __m128i {
+  #[inline(always)]
+  fn from(m: m128i) -> Self {
+    m.0
+  }
+}
+
 //
 // PLEASE KEEP ALL THE FORMAT IMPL JUNK AT THE END OF THE FILE
 //
@@ -28,8 +28,8 @@ impl m256 {
   /// Same as `m.into()`, just lets you be more explicit about what's happening.
   #[must_use]
   #[inline(always)]
-  pub fn to_array(self) -> [f32; 8] {
-    self.into()
+  pub const fn to_array(self) -> [f32; 8] {
+    unsafe { core::mem::transmute(self) }
   }
 
   /// Transmutes an array into `m256`.
@@ -38,8 +38,8 @@ impl m256 {
   /// happening.
   #[must_use]
   #[inline(always)]
-  pub fn from_array(f: [f32; 8]) -> Self {
-    f.into()
+  pub const fn from_array(f: [f32; 8]) -> Self {
+    unsafe { core::mem::transmute(f) }
   }
 
   /// Converts into the bit patterns of these floats (`[u32;8]`).
@@ -47,7 +47,7 @@ impl m256 {
   /// Like [`f32::to_bits`](f32::to_bits), but all eight lanes at once.
   #[must_use]
   #[inline(always)]
-  pub fn to_bits(self) -> [u32; 8] {
+  pub const fn to_bits(self) -> [u32; 8] {
     unsafe { core::mem::transmute(self) }
   }
 
@@ -56,9 +56,86 @@ impl m256 {
   /// Like [`f32::from_bits`](f32::from_bits), but all eight lanes at once.
   #[must_use]
   #[inline(always)]
-  pub fn from_bits(bits: [u32; 8]) -> Self {
+  pub const fn from_bits(bits: [u32; 8]) -> Self {
     unsafe { core::mem::transmute(bits) }
   }
+
+  //
+
+  /// Views the register as its bytes, without copying.
+  #[must_use]
+  #[inline(always)]
+  pub fn as_bytes(&self) -> &[u8; 32] {
+    // Safety: the type is `repr(transparent)` over the arch type, which is
+    // 32 bytes of plain data, and `u8` has no alignment requirement.
+    unsafe { &*(self as *const Self as *const [u8; 32]) }
+  }
+
+  /// Views the register as its bytes, mutably and without copying.
+  ///
+  /// Any bit pattern is a valid register value, so you can write any bytes.
+  #[inline(always)]
+  pub fn as_bytes_mut(&mut self) -> &mut [u8; 32] {
+    unsafe { &mut *(self as *mut Self as *mut [u8; 32]) }
+  }
+
+  /// Views the register as an array of its lanes, without copying.
+  /// ```
+  /// # use safe_arch::*;
+  /// let m = m256::from_array([1.0; 8]);
+  /// assert_eq!(m.as_array_ref()[7], 1.0);
+  /// ```
+  #[must_use]
+  #[inline(always)]
+  pub fn as_array_ref(&self) -> &[f32; 8] {
+    // Safety: the register is aligned at least as much as the array needs.
+    unsafe { &*(self as *const Self as *const [f32; 8]) }
+  }
+
+  /// Views the register as an array of its lanes, mutably and without copying.
+  /// ```
+  /// # use safe_arch::*;
+  /// let mut m = m256::from_array([1.0; 8]);
+  /// m.as_array_mut()[7] = 2.0;
+  /// assert_eq!(m.to_array()[7], 2.0);
+  /// ```
+  #[inline(always)]
+  pub fn as_array_mut(&mut self) -> &mut [f32; 8] {
+    unsafe { &mut *(self as *mut Self as *mut [f32; 8]) }
+  }
+
+  /// Gets the value of lane `L`.
+  ///
+  /// The index is checked at compile time, so this can't panic.
+  /// ```
+  /// # use safe_arch::*;
+  /// let m = m256::from_array([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+  /// assert_eq!(m.lane::<7>(), 8.0);
+  /// ```
+  #[must_use]
+  #[inline(always)]
+  pub const fn lane<const L: i32>(self) -> f32 {
+    let () = ImmBits::<L, 3>::OK;
+    self.to_array()[L as usize]
+  }
+
+  /// Copies the register with lane `L` replaced by `x`.
+  ///
+  /// The index is checked at compile time, so this can't panic.
+  /// ```
+  /// # use safe_arch::*;
+  /// let m = m256::from_array([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]).with_lane::<0>(-1.0);
+  /// assert_eq!(m.lane::<0>(), -1.0);
+  /// assert_eq!(m.lane::<1>(), 2.0);
+  /// ```
+  #[must_use]
+  #[inline(always)]
+  pub const fn with_lane<const L: i32>(self, x: f32) -> Self {
+    let () = ImmBits::<L, 3>::OK;
+    let mut a = self.to_array();
+    a[L as usize] = x;
+    Self::from_array(a)
+  }
 }
 
 impl Clone for m256 {
@@ -98,6 +175,40 @@ impl From<m256> for [f32; 8] {
   }
 }
 
+// portable SIMD
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<f32, 8>> for m256 {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<f32, 8>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m256> for core::simd::Simd<f32, 8> {
+  #[inline(always)]
+  fn from(m: m256) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+// core::arch
+
+impl From<__m256> for m256 {
+  #[inline(always)]
+  fn from(raw: __m256) -> Self {
+    Self(raw)
+  }
+}
+
+impl From<m256> for __m256 {
+  #[inline(always)]
+  fn from(m: m256) -> Self {
+    m.0
+  }
+}
+
 //
 // PLEASE KEEP ALL THE FORMAT IMPL JUNK AT THE END OF THE FILE
 //
@@ -28,8 +28,8 @@ impl m256d {
   /// Same as `m.into()`, just lets you be more explicit about what's happening.
   #[must_use]
   #[inline(always)]
-  pub fn to_array(self) -> [f64; 4] {
-    self.into()
+  pub const fn to_array(self) -> [f64; 4] {
+    unsafe { core::mem::transmute(self) }
   }
 
   /// Transmutes an array into `m256d`.
@@ -38,8 +38,8 @@ impl m256d {
   /// happening.
   #[must_use]
   #[inline(always)]
-  pub fn from_array(f: [f64; 4]) -> Self {
-    f.into()
+  pub const fn from_array(f: [f64; 4]) -> Self {
+    unsafe { core::mem::transmute(f) }
   }
 
   //
@@ -49,7 +49,7 @@ impl m256d {
   /// Like [`f64::to_bits`](f64::to_bits), but both lanes at once.
   #[must_use]
   #[inline(always)]
-  pub fn to_bits(self) -> [u64; 4] {
+  pub const fn to_bits(self) -> [u64; 4] {
     unsafe { core::mem::transmute(self) }
   }
 
@@ -58,9 +58,86 @@ impl m256d {
   /// Like [`f64::from_bits`](f64::from_bits), but both lanes at once.
   #[must_use]
   #[inline(always)]
-  pub fn from_bits(bits: [u64; 4]) -> Self {
+  pub const fn from_bits(bits: [u64; 4]) -> Self {
     unsafe { core::mem::transmute(bits) }
   }
+
+  //
+
+  /// Views the register as its bytes, without copying.
+  #[must_use]
+  #[inline(always)]
+  pub fn as_bytes(&self) -> &[u8; 32] {
+    // Safety: the type is `repr(transparent)` over the arch type, which is
+    // 32 bytes of plain data, and `u8` has no alignment requirement.
+    unsafe { &*(self as *const Self as *const [u8; 32]) }
+  }
+
+  /// Views the register as its bytes, mutably and without copying.
+  ///
+  /// Any bit pattern is a valid register value, so you can write any bytes.
+  #[inline(always)]
+  pub fn as_bytes_mut(&mut self) -> &mut [u8; 32] {
+    unsafe { &mut *(self as *mut Self as *mut [u8; 32]) }
+  }
+
+  /// Views the register as an array of its lanes, without copying.
+  /// ```
+  /// # use safe_arch::*;
+  /// let m = m256d::from_array([1.0; 4]);
+  /// assert_eq!(m.as_array_ref()[3], 1.0);
+  /// ```
+  #[must_use]
+  #[inline(always)]
+  pub fn as_array_ref(&self) -> &[f64; 4] {
+    // Safety: the register is aligned at least as much as the array needs.
+    unsafe { &*(self as *const Self as *const [f64; 4]) }
+  }
+
+  /// Views the register as an array of its lanes, mutably and without copying.
+  /// ```
+  /// # use safe_arch::*;
+  /// let mut m = m256d::from_array([1.0; 4]);
+  /// m.as_array_mut()[3] = 2.0;
+  /// assert_eq!(m.to_array()[3], 2.0);
+  /// ```
+  #[inline(always)]
+  pub fn as_array_mut(&mut self) -> &mut [f64; 4] {
+    unsafe { &mut *(self as *mut Self as *mut [f64; 4]) }
+  }
+
+  /// Gets the value of lane `L`.
+  ///
+  /// The index is checked at compile time, so this can't panic.
+  /// ```
+  /// # use safe_arch::*;
+  /// let m = m256d::from_array([1.0, 2.0, 3.0, 4.0]);
+  /// assert_eq!(m.lane::<3>(), 4.0);
+  /// ```
+  #[must_use]
+  #[inline(always)]
+  pub const fn lane<const L: i32>(self) -> f64 {
+    let () = ImmBits::<L, 2>::OK;
+    self.to_array()[L as usize]
+  }
+
+  /// Copies the register with lane `L` replaced by `x`.
+  ///
+  /// The index is checked at compile time, so this can't panic.
+  /// ```
+  /// # use safe_arch::*;
+  /// let m = m256d::from_array([1.0, 2.0, 3.0, 4.0]).with_lane::<0>(-1.0);
+  /// assert_eq!(m.lane::<0>(), -1.0);
+  /// assert_eq!(m.lane::<1>(), 2.0);
+  /// ```
+  #[must_use]
+  #[inline(always)]
+  pub const fn with_lane<const L: i32>(self, x: f64) -> Self {
+    let () = ImmBits::<L, 2>::OK;
+    let mut a = self.to_array();
+    a[L as usize] = x;
+    Self::from_array(a)
+  }
 }
 
 impl Clone for m256d {
@@ -100,6 +177,40 @@ impl From<m256d> for [f64; 4] {
   }
 }
 
+// portable SIMD
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<f64, 4>> for m256d {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<f64, 4>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m256d> for core::simd::Simd<f64, 4> {
+  #[inline(always)]
+  fn from(m: m256d) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+// core::arch
+
+impl From<__m256d> for m256d {
+  #[inline(always)]
+  fn from(raw: __m256d) -> Self {
+    Self(raw)
+  }
+}
+
+impl From<m256d> for __m256d {
+  #[inline(always)]
+  fn from(m: m256d) -> Self {
+    m.0
+  }
+}
+
 //
 // PLEASE KEEP ALL THE FORMAT IMPL JUNK AT THE END OF THE FILE
 //
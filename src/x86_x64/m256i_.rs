@@ -10,7 +10,7 @@ use super::*;
 ///
 /// * The exact layout to view the type as depends on the operation used.
 /// * `From` and `Into` impls are provided for all the relevant signed integer
-///   array types.
+///   array types, and for the `core::arch` type that it wraps.
 /// * Formatting impls print as four `i32` values just because they have to pick
 ///   something. If you want an alternative you can turn it into an array and
 ///   print as you like.
@@ -25,6 +25,25 @@ unsafe impl bytemuck::Pod for m256i {}
 #[cfg(feature = "bytemuck")]
 unsafe impl bytemuck::TransparentWrapper<__m256i> for m256i {}
 
+impl m256i {
+  /// Views the register as its bytes, without copying.
+  #[must_use]
+  #[inline(always)]
+  pub fn as_bytes(&self) -> &[u8; 32] {
+    // Safety: the type is `repr(transparent)` over the arch type, which is
+    // 32 bytes of plain data, and `u8` has no alignment requirement.
+    unsafe { &*(self as *const Self as *const [u8; 32]) }
+  }
+
+  /// Views the register as its bytes, mutably and without copying.
+  ///
+  /// Any bit pattern is a valid register value, so you can write any bytes.
+  #[inline(always)]
+  pub fn as_bytes_mut(&mut self) -> &mut [u8; 32] {
+    unsafe { &mut *(self as *mut Self as *mut [u8; 32]) }
+  }
+}
+
 impl Clone for m256i {
   #[must_use]
   #[inline(always)]
@@ -212,6 +231,152 @@ impl From<m256i> for [u128; 2] {
   }
 }
 
+// portable SIMD
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<i8, 32>> for m256i {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<i8, 32>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m256i> for core::simd::Simd<i8, 32> {
+  #[inline(always)]
+  fn from(m: m256i) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<u8, 32>> for m256i {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<u8, 32>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m256i> for core::simd::Simd<u8, 32> {
+  #[inline(always)]
+  fn from(m: m256i) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<i16, 16>> for m256i {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<i16, 16>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m256i> for core::simd::Simd<i16, 16> {
+  #[inline(always)]
+  fn from(m: m256i) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<u16, 16>> for m256i {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<u16, 16>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m256i> for core::simd::Simd<u16, 16> {
+  #[inline(always)]
+  fn from(m: m256i) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<i32, 8>> for m256i {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<i32, 8>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m256i> for core::simd::Simd<i32, 8> {
+  #[inline(always)]
+  fn from(m: m256i) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<u32, 8>> for m256i {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<u32, 8>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m256i> for core::simd::Simd<u32, 8> {
+  #[inline(always)]
+  fn from(m: m256i) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<i64, 4>> for m256i {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<i64, 4>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m256i> for core::simd::Simd<i64, 4> {
+  #[inline(always)]
+  fn from(m: m256i) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<core::simd::Simd<u64, 4>> for m256i {
+  #[inline(always)]
+  fn from(v: core::simd::Simd<u64, 4>) -> Self {
+    unsafe { core::mem::transmute(v) }
+  }
+}
+
+#[cfg(feature = "portable_simd")]
+impl From<m256i> for core::simd::Simd<u64, 4> {
+  #[inline(always)]
+  fn from(m: m256i) -> Self {
+    unsafe { core::mem::transmute(m) }
+  }
+}
+
+// core::arch
+
+impl From<__m256i> for m256i {
+  #[inline(always)]
+  fn from(raw: __m256i) -> Self {
+    Self(raw)
+  }
+}
+
+impl From<m256i> for __m256i {
+  #[inline(always)]
+  fn from(m: m256i) -> Self {
+    m.0
+  }
+}
+
 //
 // PLEASE KEEP ALL THE FORMAT IMPL JUNK AT THE END OF THE FILE
 //
@@ -0,0 +1,206 @@
+#![cfg(target_feature = "avx2")]
+//! Polynomial approximations of elementary functions on `m256` lanes.
+//!
+//! These are the usual Cephes style kernels: reduce the range, evaluate a
+//! short polynomial, then rebuild the result. They're built only from the
+//! other functions of this crate, so they follow the same rounding mode as
+//! everything else (round to nearest, unless you've changed it).
+//!
+//! Each function documents the maximum error seen when checking a dense sweep
+//! of its accurate range against the correctly rounded result, in "units in
+//! the last place" (ULP). The tests check the same bounds.
+
+use super::*;
+
+#[inline(always)]
+fn mul_add(a: m256, b: m256, c: m256) -> m256 {
+  add_m256(mul_m256(a, b), c)
+}
+
+#[inline(always)]
+fn splat(f: f32) -> m256 {
+  set_splat_m256(f)
+}
+
+/// Lanewise `e^x`.
+///
+/// * The maximum error is 1 ULP.
+/// * Large inputs go to infinity and large negative inputs go to `0.0`, as
+///   with `f32::exp` from `std`. Results in the subnormal range are less
+///   accurate.
+/// * NaN lanes stay NaN.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256::from_array([0.0, 1.0, -1.0, 10.0, -10.0, 88.0, -80.0, 0.5]);
+/// let e = exp_m256(a).to_array();
+/// for (x, y) in a.to_array().iter().zip(e.iter()) {
+///   assert!((x.exp() - y).abs() <= x.exp() * 1.0e-6);
+/// }
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(all(target_feature = "avx2", feature = "math"))))]
+pub fn exp_m256(x: m256) -> m256 {
+  // Past these the result is 0.0 or infinity anyway. The argument order keeps
+  // NaN lanes as NaN.
+  let x = min_m256(splat(89.0), max_m256(splat(-104.0), x));
+
+  // x = n * ln(2) + r, with |r| <= ln(2) / 2
+  let n_i = convert_to_i32_m256i_from_m256(mul_m256(x, splat(core::f32::consts::LOG2_E)));
+  let n = convert_to_m256_from_i32_m256i(n_i);
+  let r = sub_m256(x, mul_m256(n, splat(0.693_359_4)));
+  let r = sub_m256(r, mul_m256(n, splat(-2.121_944_4e-4)));
+
+  // e^r
+  let mut p = splat(1.987_569_1e-4);
+  p = mul_add(p, r, splat(1.398_199_9e-3));
+  p = mul_add(p, r, splat(8.333_452e-3));
+  p = mul_add(p, r, splat(4.166_579_6e-2));
+  p = mul_add(p, r, splat(1.666_666_5e-1));
+  p = mul_add(p, r, splat(0.5));
+  p = mul_add(p, mul_m256(r, r), add_m256(r, splat(1.0)));
+
+  // 2^n, built in two halves so that every `n` in range has an exponent that
+  // fits a normal `f32`.
+  let n_low = shr_imm_i32_m256i::<1>(n_i);
+  let n_high = sub_i32_m256i(n_i, n_low);
+  let bias = set_splat_i32_m256i(127);
+  let pow_low = cast_to_m256_from_m256i(shl_imm_u32_m256i::<23>(add_i32_m256i(n_low, bias)));
+  let pow_high = cast_to_m256_from_m256i(shl_imm_u32_m256i::<23>(add_i32_m256i(n_high, bias)));
+  mul_m256(mul_m256(p, pow_low), pow_high)
+}
+
+/// Lanewise natural logarithm.
+///
+/// * The maximum error is 1 ULP.
+/// * `0.0` gives negative infinity, negative lanes give NaN, and positive
+///   infinity stays as is, as with `f32::ln` from `std`.
+/// * NaN lanes stay NaN.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256::from_array([1.0, 2.0, 0.5, 10.0, 1.0e-40, 1.0e30, 0.0, -1.0]);
+/// let l = ln_m256(a).to_array();
+/// for (x, y) in a.to_array().iter().zip(l.iter()).take(6) {
+///   assert!((x.ln() - y).abs() <= x.ln().abs() * 1.0e-6);
+/// }
+/// assert_eq!(l[6], f32::NEG_INFINITY);
+/// assert!(l[7].is_nan());
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(all(target_feature = "avx2", feature = "math"))))]
+pub fn ln_m256(x: m256) -> m256 {
+  let input = x;
+
+  // Scale subnormals up into the normal range first.
+  let subnormal = cmp_op_mask_m256::<{ CmpOp::LessThanOrdered.imm() }>(x, splat(f32::MIN_POSITIVE));
+  let x = select_m256(subnormal, mul_m256(x, splat(8_388_608.0)), x);
+  let e_adjust = select_m256(subnormal, splat(-23.0), zeroed_m256());
+
+  // x = m * 2^e, with m in [0.5, 1)
+  let bits = cast_to_m256i_from_m256(x);
+  let e_i = sub_i32_m256i(shr_imm_u32_m256i::<23>(bits), set_splat_i32_m256i(126));
+  let m_bits = bitor_m256i(
+    bitand_m256i(bits, set_splat_i32_m256i(0x007F_FFFF)),
+    set_splat_i32_m256i(0x3F00_0000),
+  );
+  let m = cast_to_m256_from_m256i(m_bits);
+  let e = add_m256(convert_to_m256_from_i32_m256i(e_i), e_adjust);
+
+  // Shift m into [sqrt(0.5), sqrt(2)) and take away 1.
+  let small =
+    cmp_op_mask_m256::<{ CmpOp::LessThanOrdered.imm() }>(m, splat(core::f32::consts::FRAC_1_SQRT_2));
+  let e = sub_m256(e, bitand_m256(small, splat(1.0)));
+  let m = sub_m256(add_m256(m, bitand_m256(small, m)), splat(1.0));
+
+  // ln(1 + m)
+  let z = mul_m256(m, m);
+  let mut p = splat(7.037_683_6e-2);
+  p = mul_add(p, m, splat(-1.151_461e-1));
+  p = mul_add(p, m, splat(1.167_699_9e-1));
+  p = mul_add(p, m, splat(-1.242_014_1e-1));
+  p = mul_add(p, m, splat(1.424_932_3e-1));
+  p = mul_add(p, m, splat(-1.666_805_8e-1));
+  p = mul_add(p, m, splat(2.000_071_4e-1));
+  p = mul_add(p, m, splat(-2.499_999_4e-1));
+  p = mul_add(p, m, splat(3.333_333e-1));
+  let mut y = mul_m256(mul_m256(p, m), z);
+  y = mul_add(e, splat(-2.121_944_4e-4), y);
+  y = mul_add(z, splat(-0.5), y);
+  let result = mul_add(e, splat(0.693_359_4), add_m256(m, y));
+
+  // Special cases.
+  let zero = cmp_op_mask_m256::<{ CmpOp::EqualOrdered.imm() }>(input, zeroed_m256());
+  let negative = cmp_op_mask_m256::<{ CmpOp::LessThanOrdered.imm() }>(input, zeroed_m256());
+  let special = cmp_op_mask_m256::<{ CmpOp::NotLessThanUnordered.imm() }>(input, splat(f32::INFINITY));
+  let result = select_m256(special, input, result);
+  let result = select_m256(negative, splat(f32::NAN), result);
+  select_m256(zero, splat(f32::NEG_INFINITY), result)
+}
+
+/// Lanewise sine and cosine, as `(sin, cos)`.
+///
+/// * For `|x| <= PI` the maximum error is 1 ULP.
+/// * For `|x| <= 8192.0` the maximum absolute error is `1.0e-7`. Near the
+///   zeros of each function that can be many ULP. Beyond that the range
+///   reduction loses accuracy, and by `|x| >= 2.0^24` the output is
+///   meaningless.
+/// * Infinite and NaN lanes give NaN.
+/// ```
+/// # use safe_arch::*;
+/// let a = m256::from_array([0.0, 1.0, -1.0, 3.0, 100.0, -0.5, 1.0e-3, 6.0]);
+/// let (s, c) = sin_cos_m256(a);
+/// for ((x, s), c) in a.to_array().iter().zip(s.to_array().iter()).zip(c.to_array().iter()) {
+///   assert!((x.sin() - s).abs() < 1.0e-6);
+///   assert!((x.cos() - c).abs() < 1.0e-6);
+/// }
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(all(target_feature = "avx2", feature = "math"))))]
+pub fn sin_cos_m256(x: m256) -> (m256, m256) {
+  let sign_mask = cast_to_m256_from_m256i(set_splat_i32_m256i(i32::MIN));
+  let sin_sign = bitand_m256(x, sign_mask);
+  let x = bitandnot_m256(sign_mask, x);
+
+  // The octant, rounded up to even, so that x = j * (pi / 4) + r with
+  // |r| <= pi / 4.
+  let j = convert_truncate_to_i32_m256i_from_m256(mul_m256(x, splat(4.0 / core::f32::consts::PI)));
+  let j = bitand_m256i(add_i32_m256i(j, set_splat_i32_m256i(1)), set_splat_i32_m256i(!1));
+  let y = convert_to_m256_from_i32_m256i(j);
+
+  // pi / 4 split into three parts so the subtraction stays exact.
+  let r = sub_m256(x, mul_m256(y, splat(0.785_156_25)));
+  let r = sub_m256(r, mul_m256(y, splat(2.418_756_5e-4)));
+  let r = sub_m256(r, mul_m256(y, splat(3.774_895e-8)));
+  let z = mul_m256(r, r);
+
+  // cos(r)
+  let mut c = splat(2.443_315_7e-5);
+  c = mul_add(c, z, splat(-1.388_731_6e-3));
+  c = mul_add(c, z, splat(4.166_664_6e-2));
+  c = mul_m256(mul_m256(c, z), z);
+  c = add_m256(mul_add(z, splat(-0.5), c), splat(1.0));
+
+  // sin(r)
+  let mut s = splat(-1.951_529_6e-4);
+  s = mul_add(s, z, splat(8.332_161e-3));
+  s = mul_add(s, z, splat(-1.666_665_5e-1));
+  s = mul_add(mul_m256(s, z), r, r);
+
+  // Octants 2, 3, 6, and 7 swap which polynomial is which.
+  let swap = cast_to_m256_from_m256i(cmp_eq_mask_i32_m256i(
+    bitand_m256i(j, set_splat_i32_m256i(2)),
+    set_splat_i32_m256i(2),
+  ));
+  let sin = select_m256(swap, c, s);
+  let cos = select_m256(swap, s, c);
+
+  // Then the signs, which come from bit 2 of the octant.
+  let sin_flip = cast_to_m256_from_m256i(shl_imm_u32_m256i::<29>(bitand_m256i(j, set_splat_i32_m256i(4))));
+  let cos_flip = cast_to_m256_from_m256i(shl_imm_u32_m256i::<29>(bitandnot_m256i(
+    sub_i32_m256i(j, set_splat_i32_m256i(2)),
+    set_splat_i32_m256i(4),
+  )));
+  (bitxor_m256(sin, bitxor_m256(sin_sign, sin_flip)), bitxor_m256(cos, cos_flip))
+}
@@ -19,3 +19,74 @@ pub fn mul_i64_carryless_m128i<const IMM: i32>(a: m128i, b: m128i) -> m128i {
   m128i(unsafe { _mm_clmulepi64_si128(a.0, b.0, IMM) })
 }
 
+/// Performs a full "carryless" multiplication of two 128-bit values.
+///
+/// The output is the 256-bit product as `(low, high)`. This is the usual
+/// schoolbook sequence of four `pclmulqdq` with the middle terms folded into
+/// both halves.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from(0b11_u128 << 64);
+/// let b = m128i::from(0b11_u128 << 64);
+/// let (low, high) = mul_carryless_full_m128i(a, b);
+/// assert_eq!(u128::from(low), 0);
+/// assert_eq!(u128::from(high), 0b101); // not 9 like a normal mul would be!
+/// ```
+/// * **Intrinsic:** [`_mm_clmulepi64_si128`]
+/// * **Assembly:** `pclmulqdq xmm, xmm, imm8` (four times)
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "pclmulqdq")))]
+pub fn mul_carryless_full_m128i(a: m128i, b: m128i) -> (m128i, m128i) {
+  unsafe {
+    let low = _mm_clmulepi64_si128(a.0, b.0, 0x00);
+    let mid = _mm_xor_si128(_mm_clmulepi64_si128(a.0, b.0, 0x10), _mm_clmulepi64_si128(a.0, b.0, 0x01));
+    let high = _mm_clmulepi64_si128(a.0, b.0, 0x11);
+    let low = _mm_xor_si128(low, _mm_slli_si128::<8>(mid));
+    let high = _mm_xor_si128(high, _mm_srli_si128::<8>(mid));
+    (m128i(low), m128i(high))
+  }
+}
+
+/// Multiplies two elements of the GHASH field, GF(2^128), as used by AES-GCM.
+///
+/// GHASH numbers its bits backwards, so the operands and the output use the
+/// byte reversed layout that `pclmulqdq` code normally works with: a GHASH
+/// block `b` (16 bytes, in the order from the spec) is
+/// `m128i::from(u128::from_be_bytes(b))`. That makes the field's `1` element
+/// (the block `[0x80, 0, 0, ...]`) equal to `1 << 127`.
+///
+/// This is the four multiply and shift-based reduction sequence from Intel's
+/// "Carry-Less Multiplication and Its Usage for Computing the GCM Mode" paper.
+/// ```
+/// # use safe_arch::*;
+/// let one = m128i::from(1_u128 << 127);
+/// let x = m128i::from(0x0388DACE60B6A392F328C2B971B2FE78_u128);
+/// assert_eq!(u128::from(ghash_multiply(x, one)), u128::from(x));
+/// assert_eq!(u128::from(ghash_multiply(one, x)), u128::from(x));
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "pclmulqdq")))]
+pub fn ghash_multiply(x: m128i, h: m128i) -> m128i {
+  let (m128i(low), m128i(high)) = mul_carryless_full_m128i(x, h);
+  unsafe {
+    // With reflected bits the product comes out one bit short, so shift the
+    // whole 256 bits left by 1.
+    let low_carry = _mm_srli_epi32::<31>(low);
+    let high_carry = _mm_srli_epi32::<31>(high);
+    let low = _mm_or_si128(_mm_slli_epi32::<1>(low), _mm_slli_si128::<4>(low_carry));
+    let high = _mm_or_si128(_mm_slli_epi32::<1>(high), _mm_slli_si128::<4>(high_carry));
+    let high = _mm_or_si128(high, _mm_srli_si128::<12>(low_carry));
+
+    // Reduce modulo x^128 + x^7 + x^2 + x + 1, in two phases.
+    let t = _mm_xor_si128(_mm_slli_epi32::<31>(low), _mm_slli_epi32::<30>(low));
+    let t = _mm_xor_si128(t, _mm_slli_epi32::<25>(low));
+    let t_high = _mm_srli_si128::<4>(t);
+    let low = _mm_xor_si128(low, _mm_slli_si128::<12>(t));
+    let u = _mm_xor_si128(_mm_srli_epi32::<1>(low), _mm_srli_epi32::<2>(low));
+    let u = _mm_xor_si128(u, _mm_srli_epi32::<7>(low));
+    let u = _mm_xor_si128(u, t_high);
+    m128i(_mm_xor_si128(high, _mm_xor_si128(low, u)))
+  }
+}
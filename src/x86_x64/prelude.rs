@@ -0,0 +1,95 @@
+//! Preludes that only contain what's available at a given feature level.
+//!
+//! The crate root exports everything that the current build enables, which is
+//! handy, but it makes it easy to accidentally use something above the feature
+//! level that you meant to target. Each module here re-exports exactly the
+//! types and functions available at one level of the main `sse`/`avx`
+//! progression (along with the levels below it), so that reaching for
+//! something higher is a compile error.
+//!
+//! ```
+//! # #[cfg(all(target_feature = "sse4.2", feature = "sse4_2"))]
+//! # {
+//! use safe_arch::prelude::sse42::*;
+//! let a = m128i::from([1_i64, 2]);
+//! let b = m128i::from([1_i64, 0]);
+//! let c: [i64; 2] = cmp_gt_mask_i64_m128i(a, b).into();
+//! assert_eq!(c, [0, -1]);
+//! # }
+//! ```
+//!
+//! But an `avx2` function isn't there:
+//!
+//! ```compile_fail
+//! use safe_arch::prelude::sse42::*;
+//! let a = set_splat_i32_m256i(1);
+//! let b = add_i32_m256i(a, a);
+//! ```
+//!
+//! The smaller stand-alone features (`bmi1`, `popcnt`, `aes`, and so on) aren't
+//! part of any level here, import those from the crate root as needed.
+
+/// The types, plus the functions that don't need any particular feature.
+mod base {
+  pub use crate::x86_x64::{
+    byte_swap_i32, byte_swap_i64, cpuid::*, m128_::*, m128d_::*, m128i_::*,
+    m256_::*, m256d_::*, m256i_::*, read_timestamp_counter,
+    read_timestamp_counter_p, shuffle_control_1bit, shuffle_control_2bit,
+  };
+}
+
+/// Everything available with `sse2` (the `x86_64` baseline).
+#[cfg(all(target_feature = "sse2", feature = "sse2"))]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub mod sse2 {
+  pub use super::base::*;
+  pub use crate::x86_x64::{sse::*, sse2::*};
+}
+
+/// Everything available with `sse3`.
+#[cfg(all(target_feature = "sse3", feature = "sse3"))]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse3")))]
+pub mod sse3 {
+  pub use super::sse2::*;
+  pub use crate::x86_x64::sse3::*;
+}
+
+/// Everything available with `ssse3`.
+#[cfg(all(target_feature = "ssse3", feature = "ssse3"))]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "ssse3")))]
+pub mod ssse3 {
+  pub use super::sse3::*;
+  pub use crate::x86_x64::ssse3::*;
+}
+
+/// Everything available with `sse4.1`.
+#[cfg(all(target_feature = "sse4.1", feature = "sse4_1"))]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
+pub mod sse41 {
+  pub use super::ssse3::*;
+  pub use crate::x86_x64::sse4_1::*;
+}
+
+/// Everything available with `sse4.2`.
+#[cfg(all(target_feature = "sse4.2", feature = "sse4_2"))]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.2")))]
+pub mod sse42 {
+  pub use super::sse41::*;
+  pub use crate::x86_x64::sse4_2::*;
+}
+
+/// Everything available with `avx`.
+#[cfg(all(target_feature = "avx", feature = "avx"))]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx")))]
+pub mod avx {
+  pub use super::sse42::*;
+  pub use crate::x86_x64::avx::*;
+}
+
+/// Everything available with `avx2`.
+#[cfg(all(target_feature = "avx2", feature = "avx2"))]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "avx2")))]
+pub mod avx2 {
+  pub use super::avx::*;
+  pub use crate::x86_x64::avx2::*;
+}
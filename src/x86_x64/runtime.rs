@@ -0,0 +1,853 @@
+#![cfg(feature = "runtime")]
+//! Proof types for CPU features found at runtime, and functions that use them.
+//!
+//! The rest of the crate picks what exists with `cfg`, so a function is only
+//! there if its target feature was on at compile time. A token instead proves
+//! that the CPU that the program is *running on* has a feature. You can only
+//! get one by checking the CPU (or by promising with `unsafe`), and its methods
+//! use the feature through the `#[target_feature]` functions of the
+//! [`dynamic`] module. This lets a program
+//! built for a baseline CPU still reach the newer instructions when they're
+//! there.
+//!
+//! ```
+//! # use safe_arch::*;
+//! let a = m256i::from([1_i32; 8]);
+//! let sum: [i32; 8] = match Avx2Token::detect() {
+//!   Some(t) => t.add_i32_m256i(a, a).into(),
+//!   None => [2; 8],
+//! };
+//! assert_eq!(sum, [2; 8]);
+//! ```
+//!
+//! Each method has the same name and does the same thing as the `cfg` version
+//! of that function. Detection runs `cpuid`, so check once at startup and keep
+//! the token around. Since the methods are compiled with a feature that the
+//! caller might not have, they can't be inlined into the caller, unless the
+//! caller is also compiled with that feature (which is what
+//! [`simd_dispatch!`] arranges). Otherwise do work in bigger pieces when the
+//! cost of the call matters.
+
+use super::*;
+
+/// Proof that the CPU has `sse`.
+///
+/// The methods are generated from the `sse` module, see [`dynamic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SseToken(());
+
+/// Proof that the CPU has `sse2` (and so `sse` as well).
+///
+/// The methods are generated from the `sse2` module, see [`dynamic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sse2Token(());
+
+/// Proof that the CPU has `sse3` (and so everything below it).
+///
+/// The methods are generated from the `sse3` module, see [`dynamic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sse3Token(());
+
+/// Proof that the CPU has `ssse3` (and so everything below it).
+///
+/// The methods are generated from the `ssse3` module, see [`dynamic`].
+/// ```
+/// # use safe_arch::*;
+/// if let Some(t) = Ssse3Token::detect() {
+///   let a = m128i::from(*b"abcdefghijklmnop");
+///   let c: [u8; 16] = t.shuffle_av_i8z_all_m128i(a, m128i::from([15_u8; 16])).into();
+///   assert_eq!(c, [b'p'; 16]);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ssse3Token(());
+
+/// Proof that the CPU has `sse4.1` (and so everything below it).
+///
+/// The methods are generated from the `sse4_1` module, see [`dynamic`].
+/// ```
+/// # use safe_arch::*;
+/// if let Some(t) = Sse41Token::detect() {
+///   let a = m128::from_array([-0.1, 1.6, 3.3, 4.5]);
+///   let c = t.round_m128::<{ RoundMode::Floor.imm() }>(a).to_array();
+///   assert_eq!(c, [-1.0, 1.0, 3.0, 4.0]);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sse41Token(());
+
+/// Proof that the CPU has `sse4.2` (and so everything below it).
+///
+/// The methods are generated from the `sse4_2` module, see [`dynamic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sse42Token(());
+
+/// Proof that the CPU has `avx` *and* that the OS has enabled the `ymm`
+/// register state.
+///
+/// The methods are generated from the `avx` module, see [`dynamic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AvxToken(());
+
+/// Proof that the CPU has `avx2` *and* that the OS has enabled the `ymm`
+/// register state.
+///
+/// The methods are generated from the `avx2` module, see [`dynamic`].
+/// ```
+/// # use safe_arch::*;
+/// if let Some(t) = Avx2Token::detect() {
+///   let b = m256i::from(*b"safe_arch safe_arch safe_arch...");
+///   let eq = t.cmp_eq_mask_i8_m256i(b, m256i::from([b'a'; 32]));
+///   assert_eq!(t.move_mask_i8_m256i(eq), 0x0220_8822);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Avx2Token(());
+
+/// Proof that the CPU has `adx`.
+///
+/// The methods are generated from the `adx` module, see [`dynamic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AdxToken(());
+
+/// Proof that the CPU has `aes` (and so `sse2` as well).
+///
+/// The methods are generated from the `aes` module, see [`dynamic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AesToken(());
+
+/// Proof that the CPU has `bmi1`.
+///
+/// The methods are generated from the `bmi1` module, see [`dynamic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bmi1Token(());
+
+/// Proof that the CPU has `bmi2`.
+///
+/// The methods are generated from the `bmi2` module, see [`dynamic`].
+/// ```
+/// # use safe_arch::*;
+/// if let Some(t) = Bmi2Token::detect() {
+///   let m = t.interleave_bits_u32(12345, 67890);
+///   assert_eq!(t.deinterleave_bits_u64(m), (12345, 67890));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bmi2Token(());
+
+/// Proof that the CPU has `f16c` and `avx`, *and* that the OS has enabled the
+/// `ymm` register state.
+///
+/// The methods are generated from the `f16c` module, see [`dynamic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct F16cToken(());
+
+/// Proof that the CPU has `fma` and `avx`, *and* that the OS has enabled the
+/// `ymm` register state.
+///
+/// The methods are generated from the `fma` module, see [`dynamic`].
+/// ```
+/// # use safe_arch::*;
+/// if let Some(t) = FmaToken::detect() {
+///   let a = m128::from_array([2.0, 3.0, 4.0, 5.0]);
+///   let c = t.fused_mul_sub_m128(a, a, m128::from_array([1.0; 4])).to_array();
+///   assert_eq!(c, [3.0, 8.0, 15.0, 24.0]);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FmaToken(());
+
+/// Proof that the CPU has `lzcnt`.
+///
+/// The methods are generated from the `lzcnt` module, see [`dynamic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LzcntToken(());
+
+/// Proof that the CPU has `pclmulqdq` (and so `sse2` as well).
+///
+/// The methods are generated from the `pclmulqdq` module, see [`dynamic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PclmulqdqToken(());
+
+/// Proof that the CPU has `popcnt`.
+///
+/// The methods are generated from the `popcnt` module, see [`dynamic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PopcntToken(());
+
+/// Proof that the CPU has `rdrand`.
+///
+/// The methods are generated from the `rdrand` module, see [`dynamic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RdrandToken(());
+
+/// Proof that the CPU has `rdseed`.
+///
+/// The methods are generated from the `rdseed` module, see [`dynamic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RdseedToken(());
+
+impl SseToken {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "sse";
+
+  /// Checks the CPU, giving a token if `sse` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if cpu_features().sse {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `sse`.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+}
+
+impl Sse2Token {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "sse2";
+
+  /// Checks the CPU, giving a token if `sse2` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if SseToken::detect().is_some() && cpu_features().sse2 {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `sse2`.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+
+  /// The `sse` token that this token implies.
+  #[must_use]
+  #[inline(always)]
+  pub const fn sse(self) -> SseToken {
+    SseToken(())
+  }
+}
+
+impl Sse3Token {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "sse3";
+
+  /// Checks the CPU, giving a token if `sse3` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if Sse2Token::detect().is_some() && cpu_features().sse3 {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `sse3`.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+
+  /// The `sse2` token that this token implies.
+  #[must_use]
+  #[inline(always)]
+  pub const fn sse2(self) -> Sse2Token {
+    Sse2Token(())
+  }
+}
+
+impl Ssse3Token {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "ssse3";
+
+  /// Checks the CPU, giving a token if `ssse3` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if Sse3Token::detect().is_some() && cpu_features().ssse3 {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `ssse3`.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+
+  /// The `sse3` token that this token implies.
+  #[must_use]
+  #[inline(always)]
+  pub const fn sse3(self) -> Sse3Token {
+    Sse3Token(())
+  }
+}
+
+impl Sse41Token {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "sse4.1";
+
+  /// Checks the CPU, giving a token if `sse4.1` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if Ssse3Token::detect().is_some() && cpu_features().sse4_1 {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `sse4.1`.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+
+  /// The `ssse3` token that this token implies.
+  #[must_use]
+  #[inline(always)]
+  pub const fn ssse3(self) -> Ssse3Token {
+    Ssse3Token(())
+  }
+}
+
+impl Sse42Token {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "sse4.2";
+
+  /// Checks the CPU, giving a token if `sse4.2` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if Sse41Token::detect().is_some() && cpu_features().sse4_2 {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `sse4.2`.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+
+  /// The `sse4.1` token that this token implies.
+  #[must_use]
+  #[inline(always)]
+  pub const fn sse4_1(self) -> Sse41Token {
+    Sse41Token(())
+  }
+}
+
+impl AvxToken {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "avx";
+
+  /// Checks the CPU and OS, giving a token if `avx` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if Sse42Token::detect().is_some() && os_supports_avx() {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `avx`, and the OS must have enabled the `ymm`
+  /// register state.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+
+  /// The `sse4.2` token that this token implies.
+  #[must_use]
+  #[inline(always)]
+  pub const fn sse4_2(self) -> Sse42Token {
+    Sse42Token(())
+  }
+
+  /// The `sse4.1` token that this token implies.
+  #[must_use]
+  #[inline(always)]
+  pub const fn sse4_1(self) -> Sse41Token {
+    Sse41Token(())
+  }
+}
+
+impl Avx2Token {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "avx2";
+
+  /// Checks the CPU and OS, giving a token if `avx2` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if AvxToken::detect().is_some() && cpu_features().avx2 {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `avx2`, and the OS must have enabled the `ymm`
+  /// register state.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+
+  /// The `avx` token that this token implies.
+  #[must_use]
+  #[inline(always)]
+  pub const fn avx(self) -> AvxToken {
+    AvxToken(())
+  }
+}
+
+impl AdxToken {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "adx";
+
+  /// Checks the CPU, giving a token if `adx` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if cpu_features().adx {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `adx`.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+}
+
+impl AesToken {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "aes";
+
+  /// Checks the CPU, giving a token if `aes` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if Sse2Token::detect().is_some() && cpu_features().aes {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `aes` and `sse2`.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+
+  /// The `sse2` token that this token implies.
+  #[must_use]
+  #[inline(always)]
+  pub const fn sse2(self) -> Sse2Token {
+    Sse2Token(())
+  }
+}
+
+impl Bmi1Token {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "bmi1";
+
+  /// Checks the CPU, giving a token if `bmi1` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if cpu_features().bmi1 {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `bmi1`.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+}
+
+impl Bmi2Token {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "bmi2";
+
+  /// Checks the CPU, giving a token if `bmi2` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if cpu_features().bmi2 {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `bmi2`.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+}
+
+impl F16cToken {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "f16c";
+
+  /// Checks the CPU and OS, giving a token if `f16c` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if AvxToken::detect().is_some() && cpu_features().f16c {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `f16c` and `avx`, and the OS must have enabled the
+  /// `ymm` register state.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+
+  /// The `avx` token that this token implies.
+  #[must_use]
+  #[inline(always)]
+  pub const fn avx(self) -> AvxToken {
+    AvxToken(())
+  }
+}
+
+impl FmaToken {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "fma";
+
+  /// Checks the CPU and OS, giving a token if `fma` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if AvxToken::detect().is_some() && cpu_features().fma {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `fma` and `avx`, and the OS must have enabled the
+  /// `ymm` register state.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+
+  /// The `avx` token that this token implies.
+  #[must_use]
+  #[inline(always)]
+  pub const fn avx(self) -> AvxToken {
+    AvxToken(())
+  }
+}
+
+impl LzcntToken {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "lzcnt";
+
+  /// Checks the CPU, giving a token if `lzcnt` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if cpu_features().lzcnt {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `lzcnt`.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+}
+
+impl PclmulqdqToken {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "pclmulqdq";
+
+  /// Checks the CPU, giving a token if `pclmulqdq` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if Sse2Token::detect().is_some() && cpu_features().pclmulqdq {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `pclmulqdq` and `sse2`.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+
+  /// The `sse2` token that this token implies.
+  #[must_use]
+  #[inline(always)]
+  pub const fn sse2(self) -> Sse2Token {
+    Sse2Token(())
+  }
+}
+
+impl PopcntToken {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "popcnt";
+
+  /// Checks the CPU, giving a token if `popcnt` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if cpu_features().popcnt {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `popcnt`.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+}
+
+impl RdrandToken {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "rdrand";
+
+  /// Checks the CPU, giving a token if `rdrand` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if cpu_features().rdrand {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `rdrand`.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+}
+
+impl RdseedToken {
+  /// The `target_feature` name of the feature that this token proves.
+  pub const FEATURE: &'static str = "rdseed";
+
+  /// Checks the CPU, giving a token if `rdseed` is available.
+  #[must_use]
+  #[inline]
+  pub fn detect() -> Option<Self> {
+    if cpu_features().rdseed {
+      Some(Self(()))
+    } else {
+      None
+    }
+  }
+
+  /// Makes a token without checking.
+  ///
+  /// ## Safety
+  /// The CPU must support `rdseed`.
+  #[must_use]
+  #[inline(always)]
+  pub const unsafe fn new_unchecked() -> Self {
+    Self(())
+  }
+}
+/// A table of kernels, each with the features it needs, that picks one the
+/// first time it's used.
+///
+/// This is the data driven version of [`simd_dispatch!`], for when the choices
+/// are built up as values (such as by plugins) instead of written out in one
+/// place. Each entry pairs a kernel with the features it needs, and
+/// [`get`](Self::get) gives the first entry whose features are all in
+/// [`usable_cpu_features`], or the fallback if none are. That choice is worked
+/// out on the first call and then cached.
+///
+/// The kernels themselves must still be safe to call, so a kernel that uses
+/// newer features should get its token with `detect` (which is cheap after the
+/// first time) rather than relying on the table having checked.
+/// ```
+/// # use safe_arch::*;
+/// fn double_avx2(x: m256i) -> m256i {
+///   match Avx2Token::detect() {
+///     Some(t) => t.add_i32_m256i(x, x),
+///     None => double_scalar(x),
+///   }
+/// }
+/// fn double_scalar(x: m256i) -> m256i {
+///   m256i::from(<[i32; 8]>::from(x).map(|i| i.wrapping_mul(2)))
+/// }
+///
+/// static DOUBLE: DispatchTable<fn(m256i) -> m256i> = DispatchTable::new(
+///   &[(CpuFeatures { avx2: true, ..CpuFeatures::NONE }, double_avx2)],
+///   double_scalar,
+/// );
+///
+/// let x = m256i::from([1, 2, 3, 4, 5, 6, 7, 8]);
+/// assert_eq!(<[i32; 8]>::from((DOUBLE.get())(x)), [2, 4, 6, 8, 10, 12, 14, 16]);
+/// assert_eq!(DOUBLE.chosen_index(), if Avx2Token::detect().is_some() { Some(0) } else { None });
+/// ```
+#[derive(Debug)]
+pub struct DispatchTable<F: 'static> {
+  entries: &'static [(CpuFeatures, F)],
+  fallback: F,
+  /// The index of the chosen entry plus one, with `entries.len() + 1` for the
+  /// fallback, or zero before the first call.
+  chosen: core::sync::atomic::AtomicUsize,
+}
+
+impl<F: Copy> DispatchTable<F> {
+  /// Makes a table from entries in order from best to worst, and a fallback.
+  #[must_use]
+  #[inline]
+  pub const fn new(entries: &'static [(CpuFeatures, F)], fallback: F) -> Self {
+    Self { entries, fallback, chosen: core::sync::atomic::AtomicUsize::new(0) }
+  }
+
+  /// The index of the entry that this table uses, or `None` for the fallback.
+  #[must_use]
+  #[inline]
+  pub fn chosen_index(&self) -> Option<usize> {
+    use core::sync::atomic::Ordering;
+    let mut chosen = self.chosen.load(Ordering::Relaxed);
+    if chosen == 0 {
+      let usable = usable_cpu_features();
+      chosen = match self.entries.iter().position(|(needs, _)| usable.contains(*needs)) {
+        Some(i) => i + 1,
+        None => self.entries.len() + 1,
+      };
+      // Every thread picks the same entry, so a race here is harmless.
+      self.chosen.store(chosen, Ordering::Relaxed);
+    }
+    if chosen <= self.entries.len() {
+      Some(chosen - 1)
+    } else {
+      None
+    }
+  }
+
+  /// The kernel that this table uses.
+  #[must_use]
+  #[inline]
+  pub fn get(&self) -> F {
+    match self.chosen_index() {
+      Some(i) => self.entries[i].1,
+      None => self.fallback,
+    }
+  }
+}
@@ -0,0 +1,140 @@
+//! The [`SimdVector`] trait, for writing a kernel once for every register
+//! width.
+//!
+//! Each method is just the matching free function for that type, so a generic
+//! kernel compiles to the same code as one written out for each width.
+
+use super::*;
+
+/// The operations shared by the float register types, for generic kernels.
+///
+/// It's implemented for `m128`, `m128d`, `m256`, and `m256d` (when the
+/// modules of their functions are in the build). The integer registers don't
+/// have a single lane type, so they're left out.
+///
+/// These method names are the same as the [`core::ops`] trait methods, so if
+/// you import those traits too then call these by their full path (such as
+/// `SimdVector::add(a, b)`).
+/// ```
+/// # use safe_arch::*;
+/// // Lanewise sums of `a[i] * b[i]`, for any width.
+/// fn dot<V: SimdVector>(a: &[V::Lane], b: &[V::Lane]) -> V {
+///   let mut chunks = a.chunks_exact(V::LANES).zip(b.chunks_exact(V::LANES));
+///   let (a0, b0) = chunks.next().unwrap();
+///   let mut acc = V::load_from_slice(a0).mul(V::load_from_slice(b0));
+///   for (a, b) in chunks {
+///     acc = acc.add(V::load_from_slice(a).mul(V::load_from_slice(b)));
+///   }
+///   acc
+/// }
+/// # #[cfg(all(target_feature = "sse2", feature = "sse2"))]
+/// # {
+/// let a = [1.0_f32; 16];
+/// let b = [2.0_f32; 16];
+/// assert_eq!(dot::<m128>(&a, &b).to_array(), [8.0; 4]);
+/// let a = [1.0_f64; 16];
+/// let b = [2.0_f64; 16];
+/// assert_eq!(dot::<m128d>(&a, &b).to_array(), [16.0; 2]);
+/// let mut out = [0.0_f64; 2];
+/// m128d::splat(3.0).max(m128d::splat(-1.0)).store_to_slice(&mut out);
+/// assert_eq!(out, [3.0; 2]);
+/// # }
+/// ```
+pub trait SimdVector: Copy {
+  /// The type of each lane.
+  type Lane: Copy;
+
+  /// The number of lanes.
+  const LANES: usize;
+
+  /// Sets all lanes to the value given.
+  fn splat(x: Self::Lane) -> Self;
+
+  /// Loads the first [`LANES`](Self::LANES) values of the slice (unaligned).
+  ///
+  /// Panics if the slice is shorter than that.
+  fn load_from_slice(s: &[Self::Lane]) -> Self;
+
+  /// Stores to the first [`LANES`](Self::LANES) values of the slice
+  /// (unaligned).
+  ///
+  /// Panics if the slice is shorter than that.
+  fn store_to_slice(self, s: &mut [Self::Lane]);
+
+  /// Lanewise `a + b`.
+  fn add(self, b: Self) -> Self;
+
+  /// Lanewise `a * b`.
+  fn mul(self, b: Self) -> Self;
+
+  /// Lanewise `min(a, b)`, with the same NaN handling as the free functions.
+  fn min(self, b: Self) -> Self;
+
+  /// Lanewise `max(a, b)`, with the same NaN handling as the free functions.
+  fn max(self, b: Self) -> Self;
+}
+
+#[cfg(any(
+  all(target_feature = "sse", feature = "sse"),
+  all(target_feature = "avx", feature = "avx")
+))]
+macro_rules! impl_simd_vector {
+  (
+    $t:ty, $lane:ty, $n:literal, $splat:ident, $load:ident, $store:ident,
+    $add:ident, $mul:ident, $min:ident, $max:ident
+  ) => {
+    impl SimdVector for $t {
+      type Lane = $lane;
+      const LANES: usize = $n;
+      #[inline(always)]
+      fn splat(x: $lane) -> Self {
+        $splat(x)
+      }
+      #[inline(always)]
+      fn load_from_slice(s: &[$lane]) -> Self {
+        $load(core::convert::TryInto::try_into(&s[..$n]).unwrap())
+      }
+      #[inline(always)]
+      fn store_to_slice(self, s: &mut [$lane]) {
+        $store(core::convert::TryInto::try_into(&mut s[..$n]).unwrap(), self)
+      }
+      #[inline(always)]
+      fn add(self, b: Self) -> Self {
+        $add(self, b)
+      }
+      #[inline(always)]
+      fn mul(self, b: Self) -> Self {
+        $mul(self, b)
+      }
+      #[inline(always)]
+      fn min(self, b: Self) -> Self {
+        $min(self, b)
+      }
+      #[inline(always)]
+      fn max(self, b: Self) -> Self {
+        $max(self, b)
+      }
+    }
+  };
+}
+
+#[cfg(all(target_feature = "sse", feature = "sse"))]
+impl_simd_vector!(
+  m128, f32, 4, set_splat_m128, load_unaligned_m128, store_unaligned_m128, add_m128, mul_m128,
+  min_m128, max_m128
+);
+#[cfg(all(target_feature = "sse2", feature = "sse2"))]
+impl_simd_vector!(
+  m128d, f64, 2, set_splat_m128d, load_unaligned_m128d, store_unaligned_m128d, add_m128d,
+  mul_m128d, min_m128d, max_m128d
+);
+#[cfg(all(target_feature = "avx", feature = "avx"))]
+impl_simd_vector!(
+  m256, f32, 8, set_splat_m256, load_unaligned_m256, store_unaligned_m256, add_m256, mul_m256,
+  min_m256, max_m256
+);
+#[cfg(all(target_feature = "avx", feature = "avx"))]
+impl_simd_vector!(
+  m256d, f64, 4, set_splat_m256d, load_unaligned_m256d, store_unaligned_m256d, add_m256d,
+  mul_m256d, min_m256d, max_m256d
+);
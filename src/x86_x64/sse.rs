@@ -824,6 +824,48 @@ pub fn max_m128_s(a: m128, b: m128) -> m128 {
   m128(unsafe { _mm_max_ss(a.0, b.0) })
 }
 
+/// Lanewise `max(a, b)`, with a NaN in either input giving a NaN output.
+///
+/// [`max_m128`] uses the `b` lane whenever either lane is NaN, so a NaN in
+/// `a` is silently lost. This keeps it instead. When both lanes are zero (of
+/// either sign) the `b` lane is still the one you get.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128::from_array([1.0, f32::NAN, 3.0, f32::NAN]);
+/// let b = m128::from_array([5.0, 6.0, f32::NAN, f32::NAN]);
+/// let c = max_nan_propagating_m128(a, b).to_array();
+/// assert_eq!(c[0], 5.0);
+/// assert!(c[1].is_nan() && c[2].is_nan() && c[3].is_nan());
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn max_nan_propagating_m128(a: m128, b: m128) -> m128 {
+  let a_nan = cmp_unord_mask_m128(a, a);
+  bitor_m128(bitand_m128(a_nan, a), bitandnot_m128(a_nan, max_m128(a, b)))
+}
+
+/// Lanewise `max(a, b)`, with the IEEE-754 `maxNum` NaN rules.
+///
+/// If only one lane is NaN the other lane is used, and the output is only NaN
+/// when both lanes are NaN. This matches [`f32::max`]. When both lanes are
+/// zero (of either sign) the `b` lane is used.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128::from_array([1.0, f32::NAN, 3.0, f32::NAN]);
+/// let b = m128::from_array([5.0, 6.0, f32::NAN, f32::NAN]);
+/// let c = max_num_m128(a, b).to_array();
+/// assert_eq!(&c[..3], &[5.0, 6.0, 3.0]);
+/// assert!(c[3].is_nan());
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn max_num_m128(a: m128, b: m128) -> m128 {
+  let b_nan = cmp_unord_mask_m128(b, b);
+  bitor_m128(bitand_m128(b_nan, a), bitandnot_m128(b_nan, max_m128(a, b)))
+}
+
 /// Lanewise `min(a, b)`.
 /// ```
 /// # use safe_arch::*;
@@ -854,6 +896,70 @@ pub fn min_m128_s(a: m128, b: m128) -> m128 {
   m128(unsafe { _mm_min_ss(a.0, b.0) })
 }
 
+/// Lanewise `min(a, b)`, with a NaN in either input giving a NaN output.
+///
+/// [`min_m128`] uses the `b` lane whenever either lane is NaN, so a NaN in
+/// `a` is silently lost. This keeps it instead. When both lanes are zero (of
+/// either sign) the `b` lane is still the one you get.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128::from_array([1.0, f32::NAN, 3.0, f32::NAN]);
+/// let b = m128::from_array([5.0, 6.0, f32::NAN, f32::NAN]);
+/// let c = min_nan_propagating_m128(a, b).to_array();
+/// assert_eq!(c[0], 1.0);
+/// assert!(c[1].is_nan() && c[2].is_nan() && c[3].is_nan());
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn min_nan_propagating_m128(a: m128, b: m128) -> m128 {
+  let a_nan = cmp_unord_mask_m128(a, a);
+  bitor_m128(bitand_m128(a_nan, a), bitandnot_m128(a_nan, min_m128(a, b)))
+}
+
+/// Lanewise `min(a, b)`, with the IEEE-754 `minNum` NaN rules.
+///
+/// If only one lane is NaN the other lane is used, and the output is only NaN
+/// when both lanes are NaN. This matches [`f32::min`]. When both lanes are
+/// zero (of either sign) the `b` lane is used.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128::from_array([1.0, f32::NAN, 3.0, f32::NAN]);
+/// let b = m128::from_array([5.0, 6.0, f32::NAN, f32::NAN]);
+/// let c = min_num_m128(a, b).to_array();
+/// assert_eq!(&c[..3], &[1.0, 6.0, 3.0]);
+/// assert!(c[3].is_nan());
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn min_num_m128(a: m128, b: m128) -> m128 {
+  let b_nan = cmp_unord_mask_m128(b, b);
+  bitor_m128(bitand_m128(b_nan, a), bitandnot_m128(b_nan, min_m128(a, b)))
+}
+
+/// Lanewise `x` clamped to the range `lo..=hi`.
+///
+/// A NaN lane of `x` stays NaN, as with [`f32::clamp`]. Unlike that function
+/// this doesn't panic: a NaN `lo` or `hi` lane means that bound is skipped,
+/// and when `lo > hi` the output is `hi`.
+/// ```
+/// # use safe_arch::*;
+/// let x = m128::from_array([-5.0, 0.5, 9.0, f32::NAN]);
+/// let lo = set_splat_m128(0.0);
+/// let hi = set_splat_m128(1.0);
+/// let c = clamp_m128(x, lo, hi).to_array();
+/// assert_eq!(&c[..3], &[0.0, 0.5, 1.0]);
+/// assert!(c[3].is_nan());
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn clamp_m128(x: m128, lo: m128, hi: m128) -> m128 {
+  // With these argument orders a NaN `x` is what gets passed along.
+  min_m128(hi, max_m128(lo, x))
+}
+
 /// Move the low lane of `b` to `a`, other lanes unchanged.
 /// ```
 /// # use safe_arch::*;
@@ -915,6 +1021,35 @@ pub fn move_mask_m128(a: m128) -> i32 {
   unsafe { _mm_movemask_ps(a.0) }
 }
 
+/// If any lane of a comparison mask is true.
+///
+/// A lane counts as true if its sign bit is set, which is how the
+/// comparison functions make their masks.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128::from_array([1.0, 2.0, 3.0, 4.0]);
+/// let m = cmp_lt_mask_m128(a, set_splat_m128(3.5));
+/// assert!(any_lane_true_m128(m));
+/// assert!(!all_lanes_true_m128(m));
+/// assert!(!any_lane_true_m128(zeroed_m128()));
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn any_lane_true_m128(mask: m128) -> bool {
+  move_mask_m128(mask) != 0
+}
+
+/// If every lane of a comparison mask is true.
+///
+/// See [`any_lane_true_m128`] for what counts as true.
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn all_lanes_true_m128(mask: m128) -> bool {
+  move_mask_m128(mask) == 0b1111
+}
+
 /// Lanewise `a * b`.
 /// ```
 /// # use safe_arch::*;
@@ -930,6 +1065,35 @@ pub fn mul_m128(a: m128, b: m128) -> m128 {
   m128(unsafe { _mm_mul_ps(a.0, b.0) })
 }
 
+/// Lanewise `a * b + c`, fused when the `fma` module is in the build.
+///
+/// With `fma` this is `fused_mul_add_m128`, which rounds once. Otherwise
+/// it's a multiply and then an add, which rounds twice. So the result can
+/// differ in the last bit between builds, but this is the same call either
+/// way.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128::from_array([1.0, 2.0, 3.0, 4.0]);
+/// let c = mul_add_m128(a, set_splat_m128(2.0), set_splat_m128(1.0)).to_array();
+/// assert_eq!(c, [3.0, 5.0, 7.0, 9.0]);
+/// ```
+/// * **Assembly:** one of
+///   * `vfmadd213ps xmm, xmm, xmm`
+///   * `mulps xmm, xmm` then `addps xmm, xmm`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn mul_add_m128(a: m128, b: m128, c: m128) -> m128 {
+  #[cfg(all(target_feature = "fma", feature = "fma"))]
+  {
+    fused_mul_add_m128(a, b, c)
+  }
+  #[cfg(not(all(target_feature = "fma", feature = "fma")))]
+  {
+    add_m128(mul_m128(a, b), c)
+  }
+}
+
 /// Low lane `a * b`, other lanes unchanged.
 /// ```
 /// # use safe_arch::*;
@@ -960,7 +1124,29 @@ pub fn bitor_m128(a: m128, b: m128) -> m128 {
   m128(unsafe { _mm_or_ps(a.0, b.0) })
 }
 
+/// Lanewise select: lanes of `if_true` where `mask` is set, otherwise the lanes
+/// of `if_false`.
+///
+/// Each lane of `mask` should be all 1s (true) or all 0s (false), as the
+/// `cmp_*_mask` functions give.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128::from_array([1.0, 5.0, 3.0, 8.0]);
+/// let b = m128::from_array([4.0, 2.0, 6.0, 7.0]);
+/// let c = select_m128(cmp_gt_mask_m128(a, b), a, b).to_array();
+/// assert_eq!(c, [4.0, 5.0, 6.0, 8.0]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn select_m128(mask: m128, if_true: m128, if_false: m128) -> m128 {
+  bitor_m128(bitand_m128(mask, if_true), bitandnot_m128(mask, if_false))
+}
+
 /// Lanewise `1.0 / a` approximation.
+///
+/// The relative error is at most `1.5 * 2^-12`, so only about 12 bits are
+/// correct. See [`reciprocal_refined_m128`] for a more accurate version.
 /// ```
 /// # use safe_arch::*;
 /// let a = m128::from_array([1.0, 2.0, 4.0, 8.0]);
@@ -994,7 +1180,36 @@ pub fn reciprocal_m128_s(a: m128) -> m128 {
   m128(unsafe { _mm_rcp_ss(a.0) })
 }
 
+/// Lanewise `1.0 / a` approximation, refined with one Newton-Raphson step.
+///
+/// This starts from [`reciprocal_m128`] and then computes
+/// `x * (2.0 - a * x)`, giving a relative error of about `2^-22` (so about 22
+/// of the 24 bits are correct). That's still not as exact as
+/// [`div_m128`], but it's usually faster.
+///
+/// Lanes that are `0.0` or infinite come out as NaN, unlike with the estimate
+/// alone.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128::from_array([1.0, 3.0, -7.0, 1000.0]);
+/// let b = reciprocal_refined_m128(a).to_array();
+/// let expected = [1.0, 1.0 / 3.0, -1.0 / 7.0, 0.001];
+/// for i in 0..4 {
+///   assert!(((b[i] - expected[i]) / expected[i]).abs() < 1.0e-6);
+/// }
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn reciprocal_refined_m128(a: m128) -> m128 {
+  let x = reciprocal_m128(a);
+  mul_m128(x, sub_m128(set_splat_m128(2.0), mul_m128(a, x)))
+}
+
 /// Lanewise `1.0 / sqrt(a)` approximation.
+///
+/// The relative error is at most `1.5 * 2^-12`, so only about 12 bits are
+/// correct. See [`reciprocal_sqrt_refined_m128`] for a more accurate version.
 /// ```
 /// # use safe_arch::*;
 /// let a = m128::from_array([16.0, 9.0, 4.0, 25.0]);
@@ -1028,6 +1243,52 @@ pub fn reciprocal_sqrt_m128_s(a: m128) -> m128 {
   m128(unsafe { _mm_rsqrt_ss(a.0) })
 }
 
+/// Lanewise `1.0 / sqrt(a)` approximation, refined with one Newton-Raphson
+/// step.
+///
+/// This starts from [`reciprocal_sqrt_m128`] and then computes
+/// `y * (1.5 - 0.5 * a * y * y)`, giving a relative error of about `2^-22`
+/// (so about 22 of the 24 bits are correct). That's good enough for things
+/// like normalizing vectors, without a full [`sqrt_m128`] and [`div_m128`].
+///
+/// Lanes that are `0.0` or infinite come out as NaN, unlike with the estimate
+/// alone.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128::from_array([16.0, 2.0, 0.01, 12345.0]);
+/// let b = reciprocal_sqrt_refined_m128(a).to_array();
+/// for (x, r) in a.to_array().iter().zip(b.iter()) {
+///   let expected = 1.0 / x.sqrt();
+///   assert!(((r - expected) / expected).abs() < 1.0e-6);
+/// }
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn reciprocal_sqrt_refined_m128(a: m128) -> m128 {
+  let y = reciprocal_sqrt_m128(a);
+  let half_a_y_y = mul_m128(mul_m128(set_splat_m128(0.5), a), mul_m128(y, y));
+  mul_m128(y, sub_m128(set_splat_m128(1.5), half_a_y_y))
+}
+
+/// Adds all four lanes of `a` together.
+///
+/// This is a shuffle and add tree, so the order of the additions isn't
+/// left-to-right (which can matter for float rounding).
+/// ```
+/// # use safe_arch::*;
+/// let a = m128::from_array([1.0, 2.0, 3.0, 4.5]);
+/// assert_eq!(reduce_add_m128(a), 10.5);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn reduce_add_m128(a: m128) -> f32 {
+  let pairs = add_m128(a, move_high_low_m128(a, a));
+  let one = shuffle_abi_f32_all_m128::<0b01>(pairs, pairs);
+  get_f32_from_m128_s(add_m128_s(pairs, one))
+}
+
 /// Sets the args into an `m128`, first arg is the high lane.
 /// ```
 /// # use safe_arch::*;
@@ -1066,8 +1327,8 @@ pub fn set_m128_s(low: f32) -> m128 {
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
-pub fn set_splat_m128(all: f32) -> m128 {
-  m128(unsafe { _mm_set1_ps(all) })
+pub const fn set_splat_m128(all: f32) -> m128 {
+  m128::from_array([all; 4])
 }
 
 /// Sets the args into an `m128`, first arg is the low lane.
@@ -1093,8 +1354,8 @@ pub fn set_reversed_m128(zero: f32, one: f32, two: f32, three: f32) -> m128 {
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
-pub fn zeroed_m128() -> m128 {
-  m128(unsafe { _mm_setzero_ps() })
+pub const fn zeroed_m128() -> m128 {
+  m128::from_array([0.0; 4])
 }
 
 /// Shuffle the `f32` lanes from `$a` and `$b` together using an immediate
@@ -1173,6 +1434,82 @@ pub fn store_m128(r: &mut m128, a: m128) {
   unsafe { _mm_store_ps(r as *mut m128 as *mut f32, a.0) }
 }
 
+/// Lets you do non-temporal ("streaming") stores, then fences them on drop.
+///
+/// Non-temporal stores skip the cache, which speeds up writing large buffers
+/// that you won't read again soon. However, they are weakly ordered, so until
+/// an `sfence` happens other threads might see them out of order (or not at
+/// all), even if there's other synchronization. That's why the stores are
+/// methods on this type, and when it drops it runs [`store_fence`].
+///
+/// You get one of these by calling [`streaming_stores`]. The destinations stay
+/// borrowed until the closure returns (and so the fence has happened), so no
+/// other code can observe a store that hasn't been fenced yet.
+///
+/// The fence only covers stores made by the current thread, so this is neither
+/// `Send` nor `Sync`.
+/// ```compile_fail
+/// # use safe_arch::*;
+/// fn assert_send<T: Send>() {}
+/// assert_send::<StreamingStores<'static>>();
+/// ```
+#[derive(Debug)]
+pub struct StreamingStores<'a> {
+  _borrows: core::marker::PhantomData<&'a mut ()>,
+  _not_send: core::marker::PhantomData<*mut ()>,
+}
+impl Drop for StreamingStores<'_> {
+  #[inline(always)]
+  fn drop(&mut self) {
+    store_fence();
+  }
+}
+impl<'a> StreamingStores<'a> {
+  /// Stores the value to the reference given, bypassing the cache.
+  ///
+  /// * **Intrinsic:** [`_mm_stream_ps`]
+  /// * **Assembly:** `movntps m128, xmm`
+  #[inline(always)]
+  #[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+  pub fn store_nontemporal_m128(&mut self, r: &'a mut m128, a: m128) {
+    unsafe { _mm_stream_ps(r as *mut m128 as *mut f32, a.0) }
+  }
+}
+
+/// Runs the closure with a [`StreamingStores`], then fences the stores.
+///
+/// The fence also happens if the closure panics.
+/// ```
+/// # use safe_arch::*;
+/// let mut buf = [zeroed_m128(); 4];
+/// streaming_stores(|s| {
+///   for r in buf.iter_mut() {
+///     s.store_nontemporal_m128(r, set_splat_m128(1.0));
+///   }
+/// });
+/// assert!(buf.iter().all(|r| r.to_array() == [1.0; 4]));
+/// ```
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn streaming_stores<'a, R>(f: impl FnOnce(&mut StreamingStores<'a>) -> R) -> R {
+  let mut s =
+    StreamingStores { _borrows: core::marker::PhantomData, _not_send: core::marker::PhantomData };
+  f(&mut s)
+}
+
+/// Makes all previous stores globally visible before any later stores.
+///
+/// This is mostly needed after non-temporal stores, which [`StreamingStores`]
+/// already handles for you.
+///
+/// * **Intrinsic:** [`_mm_sfence`]
+/// * **Assembly:** `sfence`
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn store_fence() {
+  unsafe { _mm_sfence() }
+}
+
 /// Stores the low lane value to the reference given.
 /// ```
 /// # use safe_arch::*;
@@ -1284,6 +1621,69 @@ pub fn transpose_four_m128(a: &mut m128, b: &mut m128, c: &mut m128, d: &mut m12
   unsafe { _MM_TRANSPOSE4_PS(&mut a.0, &mut b.0, &mut c.0, &mut d.0) }
 }
 
+/// Splits 4 values of 3 channels each (such as `xyz` points) into one register
+/// per channel.
+///
+/// The inputs are the 12 values as laid out in memory, and the outputs are
+/// all the `x` values, then all the `y` values, then all the `z` values.
+/// [`interleave_3_m128`] goes the other way. For 4 channels, use
+/// [`transpose_four_m128`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m128::from_array([0.0, 10.0, 20.0, 1.0]);
+/// let b = m128::from_array([11.0, 21.0, 2.0, 12.0]);
+/// let c = m128::from_array([22.0, 3.0, 13.0, 23.0]);
+/// let [x, y, z] = deinterleave_3_m128([a, b, c]);
+/// assert_eq!(x.to_array(), [0.0, 1.0, 2.0, 3.0]);
+/// assert_eq!(y.to_array(), [10.0, 11.0, 12.0, 13.0]);
+/// assert_eq!(z.to_array(), [20.0, 21.0, 22.0, 23.0]);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn deinterleave_3_m128(aos: [m128; 3]) -> [m128; 3] {
+  let [a, b, c] = aos;
+  // a: x0 y0 z0 x1, b: y1 z1 x2 y2, c: z2 x3 y3 z3
+  let t = shuffle_abi_f32_all_m128::<0b01_01_10_10>(b, c);
+  let x = shuffle_abi_f32_all_m128::<0b10_00_11_00>(a, t);
+  let t = shuffle_abi_f32_all_m128::<0b00_00_01_01>(a, b);
+  let u = shuffle_abi_f32_all_m128::<0b10_10_11_11>(b, c);
+  let y = shuffle_abi_f32_all_m128::<0b10_00_10_00>(t, u);
+  let t = shuffle_abi_f32_all_m128::<0b01_01_10_10>(a, b);
+  let z = shuffle_abi_f32_all_m128::<0b11_00_10_00>(t, c);
+  [x, y, z]
+}
+
+/// Merges one register per channel into 4 values of 3 channels each.
+///
+/// This undoes [`deinterleave_3_m128`].
+/// ```
+/// # use safe_arch::*;
+/// let x = m128::from_array([0.0, 1.0, 2.0, 3.0]);
+/// let y = m128::from_array([10.0, 11.0, 12.0, 13.0]);
+/// let z = m128::from_array([20.0, 21.0, 22.0, 23.0]);
+/// let [a, b, c] = interleave_3_m128([x, y, z]);
+/// assert_eq!(a.to_array(), [0.0, 10.0, 20.0, 1.0]);
+/// assert_eq!(b.to_array(), [11.0, 21.0, 2.0, 12.0]);
+/// assert_eq!(c.to_array(), [22.0, 3.0, 13.0, 23.0]);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn interleave_3_m128(soa: [m128; 3]) -> [m128; 3] {
+  let [x, y, z] = soa;
+  let t = shuffle_abi_f32_all_m128::<0b00_00_00_00>(x, y);
+  let u = shuffle_abi_f32_all_m128::<0b01_01_00_00>(z, x);
+  let a = shuffle_abi_f32_all_m128::<0b10_00_10_00>(t, u);
+  let t = shuffle_abi_f32_all_m128::<0b01_01_01_01>(y, z);
+  let u = shuffle_abi_f32_all_m128::<0b10_10_10_10>(x, y);
+  let b = shuffle_abi_f32_all_m128::<0b10_00_10_00>(t, u);
+  let t = shuffle_abi_f32_all_m128::<0b11_11_10_10>(z, x);
+  let u = shuffle_abi_f32_all_m128::<0b11_11_11_11>(y, z);
+  let c = shuffle_abi_f32_all_m128::<0b10_00_10_00>(t, u);
+  [a, b, c]
+}
+
 /// Unpack and interleave high lanes of `a` and `b`.
 /// ```
 /// # use safe_arch::*;
@@ -1329,6 +1729,141 @@ pub fn bitxor_m128(a: m128, b: m128) -> m128 {
   m128(unsafe { _mm_xor_ps(a.0, b.0) })
 }
 
+/// Lanewise `-a`, by flipping the sign bit.
+///
+/// Unlike `0.0 - a`, this turns `0.0` into `-0.0` (and back), and it flips the
+/// sign of NaN lanes too, as [`f32`]'s `Neg` does.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128::from_array([1.0, -2.0, 0.0, -0.0]);
+/// let c = negate_m128(a).to_array();
+/// let expected: [f32; 4] = [-1.0, 2.0, -0.0, 0.0];
+/// for (c, e) in c.iter().zip(expected.iter()) {
+///   assert_eq!(c.to_bits(), e.to_bits());
+/// }
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn negate_m128(a: m128) -> m128 {
+  bitxor_m128(a, set_splat_m128(-0.0))
+}
+
+/// Lanewise `|a|`, by clearing the sign bit.
+///
+/// This matches [`f32::abs`], including for `-0.0` and NaN lanes.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128::from_array([1.0, -2.0, 0.0, -0.0]);
+/// let c = abs_m128(a).to_array();
+/// let expected: [f32; 4] = [1.0, 2.0, 0.0, 0.0];
+/// for (c, e) in c.iter().zip(expected.iter()) {
+///   assert_eq!(c.to_bits(), e.to_bits());
+/// }
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn abs_m128(a: m128) -> m128 {
+  bitandnot_m128(set_splat_m128(-0.0), a)
+}
+
+/// Lanewise the magnitude of `magnitude` with the sign bit of `sign`.
+///
+/// This matches [`f32::copysign`], so the sign of zero and NaN lanes counts.
+/// ```
+/// # use safe_arch::*;
+/// let magnitude = m128::from_array([1.0, -2.0, 0.0, -0.0]);
+/// let sign = m128::from_array([-1.0, 1.0, -0.0, 0.0]);
+/// let c = copysign_m128(magnitude, sign).to_array();
+/// for ((c, m), s) in c.iter().zip(magnitude.to_array().iter()).zip(sign.to_array().iter()) {
+///   assert_eq!(c.to_bits(), m.copysign(*s).to_bits());
+/// }
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse")))]
+pub fn copysign_m128(magnitude: m128, sign: m128) -> m128 {
+  let sign_bit = set_splat_m128(-0.0);
+  bitor_m128(bitandnot_m128(sign_bit, magnitude), bitand_m128(sign_bit, sign))
+}
+
+/// Turns on flush-to-zero and denormals-are-zero until it's dropped.
+///
+/// While this is alive, any denormal result of a float operation on this thread
+/// is flushed to 0 (FTZ), and any denormal input is read as 0 (DAZ). This can
+/// save a lot of time in audio and DSP code, where values decay towards 0 and
+/// denormal math is very slow. When the guard drops, the previous `MXCSR`
+/// value is restored.
+///
+/// The guard only affects the current thread, so it's neither `Send` nor
+/// `Sync`.
+///
+/// This requires the `flush_denormals` feature.
+#[cfg(feature = "flush_denormals")]
+#[cfg_attr(docsrs, doc(cfg(all(target_feature = "sse", feature = "flush_denormals"))))]
+#[derive(Debug)]
+pub struct FlushDenormalsGuard {
+  previous: u32,
+  _not_send: core::marker::PhantomData<*const ()>,
+}
+#[cfg(feature = "flush_denormals")]
+impl FlushDenormalsGuard {
+  /// `MXCSR` bit for flush-to-zero.
+  const FTZ: u32 = 1 << 15;
+  /// `MXCSR` bit for denormals-are-zero.
+  const DAZ: u32 = 1 << 6;
+
+  /// Sets the FTZ and DAZ bits, returning a guard that restores the old flags.
+  ///
+  /// ## Safety
+  /// LLVM assumes that the float environment is always the default one, so
+  /// code that it optimizes (such as by constant folding) might not match code
+  /// that it actually runs with the flags set. These can give different results
+  /// for denormal values, and that's formally Undefined Behavior. You must only
+  /// use this where getting either result for denormals is acceptable. If the
+  /// guard is leaked, the flags simply stay set on this thread.
+  /// ```
+  /// # use safe_arch::*;
+  /// let tiny = core::hint::black_box(f32::MIN_POSITIVE / 2.0);
+  /// let guard = unsafe { FlushDenormalsGuard::new() };
+  /// let x = add_m128(set_splat_m128(tiny), zeroed_m128());
+  /// drop(guard);
+  /// assert_eq!(x.to_array(), [0.0; 4]);
+  /// ```
+  #[must_use]
+  #[inline]
+  pub unsafe fn new() -> Self {
+    let previous = read_mxcsr();
+    write_mxcsr(previous | Self::FTZ | Self::DAZ);
+    Self { previous, _not_send: core::marker::PhantomData }
+  }
+}
+#[cfg(feature = "flush_denormals")]
+impl Drop for FlushDenormalsGuard {
+  #[inline]
+  fn drop(&mut self) {
+    unsafe { write_mxcsr(self.previous) }
+  }
+}
+
+// Note(Lokathor): `_mm_getcsr` and `_mm_setcsr` are deprecated, and the
+// standard library suggests using inline assembly instead.
+#[cfg(feature = "flush_denormals")]
+#[inline(always)]
+fn read_mxcsr() -> u32 {
+  let mut out = 0_u32;
+  unsafe {
+    core::arch::asm!("stmxcsr [{}]", in(reg) &mut out, options(nostack, preserves_flags))
+  };
+  out
+}
+#[cfg(feature = "flush_denormals")]
+#[inline(always)]
+unsafe fn write_mxcsr(x: u32) {
+  core::arch::asm!("ldmxcsr [{}]", in(reg) &x, options(nostack, readonly, preserves_flags))
+}
+
 //
 // Here we define the Operator Overloads for `m128`. Each one just calls the
 // correct function from above. By putting the impls here and not with the
@@ -1175,6 +1175,8 @@ pub fn get_f64_from_m128d_s(a: m128d) -> f64 {
 /// let b = get_i32_from_m128d_s(a);
 /// assert_eq!(b, 1_i32);
 /// ```
+/// * **Intrinsic:** [`_mm_cvtsd_si32`]
+/// * **Assembly:** `cvtsd2si r32, xmm`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
@@ -1183,12 +1185,17 @@ pub fn get_i32_from_m128d_s(a: m128d) -> i32 {
 }
 
 /// Converts the lower lane to an `i64` value.
+///
+/// This rounds with the current rounding mode (round to nearest, by default).
+/// See [`truncate_to_i64_m128d_s`] to round toward zero instead.
 /// ```
 /// # use safe_arch::*;
 /// let a = m128d::from_array([1.0, 2.5]);
 /// let b = get_i64_from_m128d_s(a);
 /// assert_eq!(b, 1_i64);
 /// ```
+/// * **Intrinsic:** [`_mm_cvtsd_si64`]
+/// * **Assembly:** `cvtsd2si r64, xmm`
 #[must_use]
 #[inline(always)]
 #[cfg(target_arch = "x86_64")]
@@ -1209,7 +1216,6 @@ pub fn get_i64_from_m128d_s(a: m128d) -> i64 {
 /// * **Assembly:** `cvtsd2ss xmm, xmm`
 #[must_use]
 #[inline(always)]
-#[cfg(target_arch = "x86_64")]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
 pub fn convert_m128d_s_replace_m128_s(a: m128, b: m128d) -> m128 {
   m128(unsafe { _mm_cvtsd_ss(a.0, b.0) })
@@ -1222,6 +1228,8 @@ pub fn convert_m128d_s_replace_m128_s(a: m128, b: m128d) -> m128 {
 /// let b = get_i32_from_m128i_s(a);
 /// assert_eq!(b, 1_i32);
 /// ```
+/// * **Intrinsic:** [`_mm_cvtsi128_si32`]
+/// * **Assembly:** `movd r32, xmm`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
@@ -1236,6 +1244,8 @@ pub fn get_i32_from_m128i_s(a: m128i) -> i32 {
 /// let b = get_i64_from_m128i_s(a);
 /// assert_eq!(b, 1_i64);
 /// ```
+/// * **Intrinsic:** [`_mm_cvtsi128_si64`]
+/// * **Assembly:** `movq r64, xmm`
 #[must_use]
 #[inline(always)]
 #[cfg(target_arch = "x86_64")]
@@ -1267,6 +1277,8 @@ pub fn convert_i32_replace_m128d_s(a: m128d, i: i32) -> m128d {
 /// let b: [i32; 4] = m128i::from([1, 0, 0, 0]).into();
 /// assert_eq!(a, b);
 /// ```
+/// * **Intrinsic:** [`_mm_cvtsi32_si128`]
+/// * **Assembly:** `movd xmm, r32`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
@@ -1298,6 +1310,8 @@ pub fn convert_i64_replace_m128d_s(a: m128d, i: i64) -> m128d {
 /// let b: [i64; 2] = m128i::from([1_i64, 0]).into();
 /// assert_eq!(a, b);
 /// ```
+/// * **Intrinsic:** [`_mm_cvtsi64_si128`]
+/// * **Assembly:** `movq xmm, r64`
 #[must_use]
 #[inline(always)]
 #[cfg(target_arch = "x86_64")]
@@ -1351,12 +1365,175 @@ pub fn truncate_m128_to_m128i(a: m128) -> m128i {
   m128i(unsafe { _mm_cvttps_epi32(a.0) })
 }
 
+/// Truncate the `f32` lanes to `i32` lanes, saturating like an `as` cast.
+///
+/// The plain conversion gives `i32::MIN` (the "integer indefinite" value) for
+/// any lane that's out of range or NaN. This instead clamps out of range lanes
+/// to `i32::MIN` or `i32::MAX`, and turns NaN lanes into 0.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128::from_array([1.5, f32::NAN, 3.0e9, -3.0e9]);
+/// let b = convert_saturating_to_i32_m128i_from_m128(a);
+/// assert_eq!(<[i32; 4]>::from(b), [1, 0, i32::MAX, i32::MIN]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn convert_saturating_to_i32_m128i_from_m128(a: m128) -> m128i {
+  let t = truncate_m128_to_m128i(a);
+  // Positive overflow gave `i32::MIN`, flipping all the bits makes `i32::MAX`.
+  let too_big = cmp_ge_mask_m128(a, set_splat_m128(2147483648.0));
+  let not_nan = cmp_eq_mask_m128(a, a);
+  bitand_m128i(
+    bitxor_m128i(t, cast_to_m128i_from_m128(too_big)),
+    cast_to_m128i_from_m128(not_nan),
+  )
+}
+
+/// Truncate the `f64` lanes to the lower `i32` lanes, saturating like an `as`
+/// cast.
+///
+/// Out of range lanes are clamped to `i32::MIN` or `i32::MAX`, NaN lanes become
+/// 0, and the upper two `i32` lanes of the output are 0.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128d::from_array([f64::NAN, 1.0e10]);
+/// let b = convert_saturating_to_i32_m128i_from_m128d(a);
+/// assert_eq!(<[i32; 4]>::from(b), [0, i32::MAX, 0, 0]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn convert_saturating_to_i32_m128i_from_m128d(a: m128d) -> m128i {
+  // Every `i32` fits in an `f64`, so zero the NaN lanes and then clamp.
+  let not_nan = bitand_m128d(a, cmp_eq_mask_m128d(a, a));
+  let clamped = min_m128d(
+    max_m128d(not_nan, set_splat_m128d(i32::MIN as f64)),
+    set_splat_m128d(i32::MAX as f64),
+  );
+  truncate_m128d_to_m128i(clamped)
+}
+
+/// Convert `f32` lanes to `bf16` values (as `u16` bits) in the low four `u16`
+/// lanes.
+///
+/// Rounds to nearest (ties to even). NaN lanes stay NaN (quieted), and the
+/// upper four lanes of the output are zero. Without `avx512bf16` there's no
+/// instruction for this, so it's done with integer math on the bits.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128::from_array([1.0, -2.5, 1.0 + 1.0 / 256.0, f32::MAX]);
+/// let b: [u16; 8] = convert_to_bf16_m128i_from_m128(a).into();
+/// assert_eq!(b, [0x3F80, 0xC020, 0x3F80, 0x7F80, 0, 0, 0, 0]);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn convert_to_bf16_m128i_from_m128(a: m128) -> m128i {
+  let bits = cast_to_m128i_from_m128(a);
+  let lsb = bitand_m128i(shr_imm_u32_m128i::<16>(bits), set_splat_i32_m128i(1));
+  let rounded = add_i32_m128i(bits, add_i32_m128i(lsb, set_splat_i32_m128i(0x7FFF)));
+  let nan = cast_to_m128i_from_m128(cmp_unord_mask_m128(a, a));
+  let quiet = bitor_m128i(bits, set_splat_i32_m128i(0x0040_0000));
+  let out = bitor_m128i(bitand_m128i(nan, quiet), bitandnot_m128i(nan, rounded));
+  // The arithmetic shift keeps every value in `i16` range, so the saturating
+  // pack doesn't change any of them.
+  pack_i32_to_i16_m128i(shr_imm_i32_m128i::<16>(out), zeroed_m128i())
+}
+
+/// Convert the low four `bf16` values (as `u16` bits) to `f32` lanes.
+///
+/// This is exact, since `bf16` is just the top half of an `f32`.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([0x3F80_u16, 0xC020, 0x7F80, 0xFFC0, 9, 9, 9, 9]);
+/// let b = convert_to_m128_from_lower4_bf16_m128i(a).to_array();
+/// assert_eq!(&b[..3], &[1.0, -2.5, f32::INFINITY]);
+/// assert!(b[3].is_nan());
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn convert_to_m128_from_lower4_bf16_m128i(a: m128i) -> m128 {
+  cast_to_m128_from_m128i(unpack_low_i16_m128i(zeroed_m128i(), a))
+}
+
+/// Converts a slice of `f32` values into `bf16` values (as `u16` bits).
+///
+/// This works 4 lanes at a time with [`convert_to_bf16_m128i_from_m128`], so
+/// it rounds to nearest (ties to even).
+///
+/// Panics if the slices aren't the same length.
+/// ```
+/// # use safe_arch::*;
+/// let src: Vec<f32> = (0..11).map(|i| i as f32 * 0.5).collect();
+/// let mut dst = vec![0_u16; 11];
+/// convert_f32_slice_to_bf16(&src, &mut dst);
+/// assert_eq!(&dst[..4], &[0x0000, 0x3F00, 0x3F80, 0x3FC0]);
+/// assert_eq!(dst[10], 0x40A0);
+/// ```
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn convert_f32_slice_to_bf16(src: &[f32], dst: &mut [u16]) {
+  assert_eq!(src.len(), dst.len(), "convert_f32_slice_to_bf16: slices must be the same length");
+  let mut src_chunks = src.chunks_exact(4);
+  let mut dst_chunks = dst.chunks_exact_mut(4);
+  for (s, d) in (&mut src_chunks).zip(&mut dst_chunks) {
+    let a = m128::from_array(core::convert::TryInto::try_into(s).unwrap());
+    let bits: [u16; 8] = convert_to_bf16_m128i_from_m128(a).into();
+    d.copy_from_slice(&bits[..4]);
+  }
+  let (s, d) = (src_chunks.remainder(), dst_chunks.into_remainder());
+  if !s.is_empty() {
+    let mut padded = [0.0; 4];
+    padded[..s.len()].copy_from_slice(s);
+    let bits: [u16; 8] = convert_to_bf16_m128i_from_m128(m128::from_array(padded)).into();
+    d.copy_from_slice(&bits[..d.len()]);
+  }
+}
+
+/// Converts a slice of `bf16` values (as `u16` bits) into `f32` values.
+///
+/// This works 4 lanes at a time with
+/// [`convert_to_m128_from_lower4_bf16_m128i`], and is exact.
+///
+/// Panics if the slices aren't the same length.
+/// ```
+/// # use safe_arch::*;
+/// let src: Vec<u16> = (0..11).map(|i| 0x3F80 + i).collect();
+/// let mut dst = vec![0.0; 11];
+/// convert_bf16_slice_to_f32(&src, &mut dst);
+/// for (i, d) in dst.iter().enumerate() {
+///   assert_eq!(*d, 1.0 + i as f32 / 128.0);
+/// }
+/// ```
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn convert_bf16_slice_to_f32(src: &[u16], dst: &mut [f32]) {
+  assert_eq!(src.len(), dst.len(), "convert_bf16_slice_to_f32: slices must be the same length");
+  let mut src_chunks = src.chunks_exact(4);
+  let mut dst_chunks = dst.chunks_exact_mut(4);
+  for (s, d) in (&mut src_chunks).zip(&mut dst_chunks) {
+    let bits = m128i::from([s[0], s[1], s[2], s[3], 0, 0, 0, 0]);
+    d.copy_from_slice(&convert_to_m128_from_lower4_bf16_m128i(bits).to_array());
+  }
+  let (s, d) = (src_chunks.remainder(), dst_chunks.into_remainder());
+  if !s.is_empty() {
+    let mut padded = [0_u16; 8];
+    padded[..s.len()].copy_from_slice(s);
+    let floats = convert_to_m128_from_lower4_bf16_m128i(m128i::from(padded)).to_array();
+    d.copy_from_slice(&floats[..d.len()]);
+  }
+}
+
 /// Truncate the lower lane into an `i32`.
 /// ```
 /// # use safe_arch::*;
 /// let a = m128d::from_array([1.7, 2.6]);
 /// assert_eq!(truncate_to_i32_m128d_s(a), 1_i32);
 /// ```
+/// * **Intrinsic:** [`_mm_cvttsd_si32`]
+/// * **Assembly:** `cvttsd2si r32, xmm`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
@@ -1370,6 +1547,8 @@ pub fn truncate_to_i32_m128d_s(a: m128d) -> i32 {
 /// let a = m128d::from_array([1.7, 2.6]);
 /// assert_eq!(truncate_to_i64_m128d_s(a), 1_i64);
 /// ```
+/// * **Intrinsic:** [`_mm_cvttsd_si64`]
+/// * **Assembly:** `cvttsd2si r64, xmm`
 #[must_use]
 #[inline(always)]
 #[cfg(target_arch = "x86_64")]
@@ -1517,18 +1696,93 @@ pub fn load_replace_high_m128d(a: m128d, b: &f64) -> m128d {
   m128d(unsafe { _mm_loadh_pd(a.0, b) })
 }
 
-/// Loads the low `i64` into a register.
+/// Loads the `i32` reference into the low lane of the register, other lanes
+/// zeroed.
 /// ```
 /// # use safe_arch::*;
-/// let a = m128i::from([1_i64, 2]);
+/// let a = 7_i32;
+/// let b = load_i32_m128i_s(&a);
+/// assert_eq!([7, 0, 0, 0], <[i32; 4]>::from(b));
+/// ```
+/// * **Intrinsic:** [`_mm_cvtsi32_si128`]
+/// * **Assembly:** `movd xmm, m32`
+#[must_use]
+#[inline(always)]
+#[allow(clippy::trivially_copy_pass_by_ref)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn load_i32_m128i_s(a: &i32) -> m128i {
+  m128i(unsafe { _mm_cvtsi32_si128(*a) })
+}
+
+/// Loads the `i64` reference into the low lane of the register, other lane
+/// zeroed.
+///
+/// Only 8 bytes are read, and only with the alignment of `i64`.
+/// ```
+/// # use safe_arch::*;
+/// let a = 1_i64;
 /// let b = load_i64_m128i_s(&a);
 /// assert_eq!([1_i64, 0], <[i64; 2]>::from(b));
 /// ```
+/// * **Intrinsic:** [`_mm_loadu_si64`]
+/// * **Assembly:** `movq xmm, m64`
 #[must_use]
 #[inline(always)]
+#[allow(clippy::trivially_copy_pass_by_ref)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
-pub fn load_i64_m128i_s(a: &m128i) -> m128i {
-  m128i(unsafe { _mm_loadl_epi64(a as *const m128i as *const __m128i) })
+pub fn load_i64_m128i_s(a: &i64) -> m128i {
+  m128i(unsafe { _mm_loadu_si64(a as *const i64 as *const u8) })
+}
+
+/// Loads 2 bytes into the low `i16` lane of the register, other lanes zeroed.
+///
+/// The bytes can have any alignment, and only those 2 bytes are read.
+/// ```
+/// # use safe_arch::*;
+/// let a = load_unaligned_i16_m128i_s(&[1, 2]);
+/// assert_eq!(<[i16; 8]>::from(a), [0x0201, 0, 0, 0, 0, 0, 0, 0]);
+/// ```
+/// * **Intrinsic:** [`_mm_cvtsi32_si128`]
+/// * **Assembly:** `movzx r32, m16` then `movd xmm, r32`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn load_unaligned_i16_m128i_s(a: &[u8; 2]) -> m128i {
+  set_i32_m128i_s(i32::from(u16::from_ne_bytes(*a)))
+}
+
+/// Loads 4 bytes into the low `i32` lane of the register, other lanes zeroed.
+///
+/// The bytes can have any alignment, and only those 4 bytes are read.
+/// ```
+/// # use safe_arch::*;
+/// let a = load_unaligned_i32_m128i_s(&[1, 2, 3, 4]);
+/// assert_eq!(<[i32; 4]>::from(a), [0x04030201, 0, 0, 0]);
+/// ```
+/// * **Intrinsic:** [`_mm_cvtsi32_si128`]
+/// * **Assembly:** `movd xmm, m32`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn load_unaligned_i32_m128i_s(a: &[u8; 4]) -> m128i {
+  set_i32_m128i_s(i32::from_ne_bytes(*a))
+}
+
+/// Loads 8 bytes into the low `i64` lane of the register, other lanes zeroed.
+///
+/// The bytes can have any alignment, and only those 8 bytes are read.
+/// ```
+/// # use safe_arch::*;
+/// let a = load_unaligned_i64_m128i_s(&[1, 2, 3, 4, 5, 6, 7, 8]);
+/// assert_eq!(<[i64; 2]>::from(a), [0x0807060504030201, 0]);
+/// ```
+/// * **Intrinsic:** [`_mm_loadu_si64`]
+/// * **Assembly:** `movq xmm, m64`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn load_unaligned_i64_m128i_s(a: &[u8; 8]) -> m128i {
+  m128i(unsafe { _mm_loadu_si64(a.as_ptr()) })
 }
 
 /// Loads the reference into a register, replacing the low lane.
@@ -1673,6 +1927,46 @@ pub fn max_m128d_s(a: m128d, b: m128d) -> m128d {
   m128d(unsafe { _mm_max_sd(a.0, b.0) })
 }
 
+/// Lanewise `max(a, b)`, with a NaN in either input giving a NaN output.
+///
+/// [`max_m128d`] uses the `b` lane whenever either lane is NaN, so a NaN in
+/// `a` is silently lost. This keeps it instead. When both lanes are zero (of
+/// either sign) the `b` lane is still the one you get.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128d::from_array([f64::NAN, 3.0]);
+/// let b = m128d::from_array([6.0, f64::NAN]);
+/// let c = max_nan_propagating_m128d(a, b).to_array();
+/// assert!(c[0].is_nan() && c[1].is_nan());
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn max_nan_propagating_m128d(a: m128d, b: m128d) -> m128d {
+  let a_nan = cmp_unord_mask_m128d(a, a);
+  bitor_m128d(bitand_m128d(a_nan, a), bitandnot_m128d(a_nan, max_m128d(a, b)))
+}
+
+/// Lanewise `max(a, b)`, with the IEEE-754 `maxNum` NaN rules.
+///
+/// If only one lane is NaN the other lane is used, and the output is only NaN
+/// when both lanes are NaN. This matches [`f64::max`]. When both lanes are
+/// zero (of either sign) the `b` lane is used.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128d::from_array([f64::NAN, 3.0]);
+/// let b = m128d::from_array([6.0, f64::NAN]);
+/// let c = max_num_m128d(a, b).to_array();
+/// assert_eq!(c, [6.0, 3.0]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn max_num_m128d(a: m128d, b: m128d) -> m128d {
+  let b_nan = cmp_unord_mask_m128d(b, b);
+  bitor_m128d(bitand_m128d(b_nan, a), bitandnot_m128d(b_nan, max_m128d(a, b)))
+}
+
 /// Lanewise `min(a, b)` with lanes as `u8`.
 /// ```
 /// # use safe_arch::*;
@@ -1688,6 +1982,56 @@ pub fn min_u8_m128i(a: m128i, b: m128i) -> m128i {
   m128i(unsafe { _mm_min_epu8(a.0, b.0) })
 }
 
+/// Lanewise `x` clamped to the range `lo..=hi`, as `u8` values.
+///
+/// When `lo > hi` the output is `hi`.
+/// ```
+/// # use safe_arch::*;
+/// let x = m128i::from([0_u8, 2, 3, 7, 8, 200, 0, 2, 3, 7, 8, 200, 0, 2, 3, 7]);
+/// let c: [u8; 16] = clamp_u8_m128i(x, set_splat_i8_m128i(3), set_splat_i8_m128i(7)).into();
+/// assert_eq!(c, [3, 3, 3, 7, 7, 7, 3, 3, 3, 7, 7, 7, 3, 3, 3, 7]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn clamp_u8_m128i(x: m128i, lo: m128i, hi: m128i) -> m128i {
+  min_u8_m128i(max_u8_m128i(x, lo), hi)
+}
+
+/// Lanewise `|a - b|` of `u8` values.
+///
+/// To add up the differences, [`sum_of_u8_abs_diff_m128i`] does that directly.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([0_u8, 250, 5, 9, 0, 250, 5, 9, 0, 250, 5, 9, 0, 250, 5, 9]);
+/// let b = m128i::from([250_u8, 0, 9, 5, 250, 0, 9, 5, 250, 0, 9, 5, 250, 0, 9, 5]);
+/// let c: [u8; 16] = abs_diff_u8_m128i(a, b).into();
+/// assert_eq!(c, [250, 250, 4, 4, 250, 250, 4, 4, 250, 250, 4, 4, 250, 250, 4, 4]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn abs_diff_u8_m128i(a: m128i, b: m128i) -> m128i {
+  // One of the two saturating subtractions is always 0.
+  bitor_m128i(sub_saturating_u8_m128i(a, b), sub_saturating_u8_m128i(b, a))
+}
+
+/// Lanewise `|a - b|` of `u16` values.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([0_u16, 60000, 5, 9, 0, 60000, 5, 9]);
+/// let b = m128i::from([60000_u16, 0, 9, 5, 60000, 0, 9, 5]);
+/// let c: [u16; 8] = abs_diff_u16_m128i(a, b).into();
+/// assert_eq!(c, [60000, 60000, 4, 4, 60000, 60000, 4, 4]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn abs_diff_u16_m128i(a: m128i, b: m128i) -> m128i {
+  // One of the two saturating subtractions is always 0.
+  bitor_m128i(sub_saturating_u16_m128i(a, b), sub_saturating_u16_m128i(b, a))
+}
+
 /// Lanewise `min(a, b)` with lanes as `i16`.
 /// ```
 /// # use safe_arch::*;
@@ -1703,6 +2047,41 @@ pub fn min_i16_m128i(a: m128i, b: m128i) -> m128i {
   m128i(unsafe { _mm_min_epi16(a.0, b.0) })
 }
 
+/// Lanewise `x` clamped to the range `lo..=hi`, as `i16` values.
+///
+/// When `lo > hi` the output is `hi`.
+/// ```
+/// # use safe_arch::*;
+/// let x = m128i::from([-100_i16, -3, 0, 3, 7, 100, -100, -3]);
+/// let c: [i16; 8] = clamp_i16_m128i(x, set_splat_i16_m128i(-3), set_splat_i16_m128i(7)).into();
+/// assert_eq!(c, [-3, -3, 0, 3, 7, 7, -3, -3]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn clamp_i16_m128i(x: m128i, lo: m128i, hi: m128i) -> m128i {
+  min_i16_m128i(max_i16_m128i(x, lo), hi)
+}
+
+/// Lanewise `|a - b|` of `i16` values.
+///
+/// The difference can be bigger than `i16::MAX`, so the output lanes are
+/// `u16` values, as with [`i16::abs_diff`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([-32768_i16, 32767, -5, 9, -32768, 32767, -5, 9]);
+/// let b = m128i::from([32767_i16, -32768, 9, -5, 32767, -32768, 9, -5]);
+/// let c: [u16; 8] = abs_diff_i16_m128i(a, b).into();
+/// assert_eq!(c, [65535, 65535, 14, 14, 65535, 65535, 14, 14]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn abs_diff_i16_m128i(a: m128i, b: m128i) -> m128i {
+  // The wrapping subtraction gives the right bits once read as unsigned.
+  sub_i16_m128i(max_i16_m128i(a, b), min_i16_m128i(a, b))
+}
+
 /// Lanewise `min(a, b)`.
 /// ```
 /// # use safe_arch::*;
@@ -1733,6 +2112,68 @@ pub fn min_m128d_s(a: m128d, b: m128d) -> m128d {
   m128d(unsafe { _mm_min_sd(a.0, b.0) })
 }
 
+/// Lanewise `min(a, b)`, with a NaN in either input giving a NaN output.
+///
+/// [`min_m128d`] uses the `b` lane whenever either lane is NaN, so a NaN in
+/// `a` is silently lost. This keeps it instead. When both lanes are zero (of
+/// either sign) the `b` lane is still the one you get.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128d::from_array([f64::NAN, 3.0]);
+/// let b = m128d::from_array([6.0, f64::NAN]);
+/// let c = min_nan_propagating_m128d(a, b).to_array();
+/// assert!(c[0].is_nan() && c[1].is_nan());
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn min_nan_propagating_m128d(a: m128d, b: m128d) -> m128d {
+  let a_nan = cmp_unord_mask_m128d(a, a);
+  bitor_m128d(bitand_m128d(a_nan, a), bitandnot_m128d(a_nan, min_m128d(a, b)))
+}
+
+/// Lanewise `min(a, b)`, with the IEEE-754 `minNum` NaN rules.
+///
+/// If only one lane is NaN the other lane is used, and the output is only NaN
+/// when both lanes are NaN. This matches [`f64::min`]. When both lanes are
+/// zero (of either sign) the `b` lane is used.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128d::from_array([f64::NAN, 3.0]);
+/// let b = m128d::from_array([6.0, f64::NAN]);
+/// let c = min_num_m128d(a, b).to_array();
+/// assert_eq!(c, [6.0, 3.0]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn min_num_m128d(a: m128d, b: m128d) -> m128d {
+  let b_nan = cmp_unord_mask_m128d(b, b);
+  bitor_m128d(bitand_m128d(b_nan, a), bitandnot_m128d(b_nan, min_m128d(a, b)))
+}
+
+/// Lanewise `x` clamped to the range `lo..=hi`.
+///
+/// A NaN lane of `x` stays NaN, as with [`f64::clamp`]. Unlike that function
+/// this doesn't panic: a NaN `lo` or `hi` lane means that bound is skipped,
+/// and when `lo > hi` the output is `hi`.
+/// ```
+/// # use safe_arch::*;
+/// let x = m128d::from_array([-5.0, f64::NAN]);
+/// let lo = set_splat_m128d(0.0);
+/// let hi = set_splat_m128d(1.0);
+/// let c = clamp_m128d(x, lo, hi).to_array();
+/// assert_eq!(&c[..1], &[0.0]);
+/// assert!(c[1].is_nan());
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn clamp_m128d(x: m128d, lo: m128d, hi: m128d) -> m128d {
+  // With these argument orders a NaN `x` is what gets passed along.
+  min_m128d(hi, max_m128d(lo, x))
+}
+
 /// Copy the low `i64` lane to a new register, upper bits 0.
 /// ```
 /// # use safe_arch::*;
@@ -1778,6 +2219,393 @@ pub fn move_mask_i8_m128i(a: m128i) -> i32 {
   unsafe { _mm_movemask_epi8(a.0) }
 }
 
+/// If any lane of a comparison mask is true.
+///
+/// A byte counts as true if its sign bit is set. With a mask from any of
+/// the integer comparison functions, every byte of a lane is the same, so this
+/// works for lanes of any size.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1, 2, 3, 4]);
+/// let m = cmp_eq_mask_i32_m128i(a, set_splat_i32_m128i(3));
+/// assert!(any_lane_true_m128i(m));
+/// assert!(!all_lanes_true_m128i(m));
+/// assert!(!any_lane_true_m128i(zeroed_m128i()));
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn any_lane_true_m128i(mask: m128i) -> bool {
+  move_mask_i8_m128i(mask) != 0
+}
+
+/// If every lane of a comparison mask is true.
+///
+/// See [`any_lane_true_m128i`] for what counts as true.
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn all_lanes_true_m128i(mask: m128i) -> bool {
+  move_mask_i8_m128i(mask) == 0xFFFF
+}
+
+/// Checks if all bits of `a` and `b` are equal, in constant time.
+///
+/// The work done doesn't depend on where (or if) the values differ, so this
+/// is suitable for checking secrets such as authentication tags. Everything is
+/// folded together with `xor` and `or`, and then there's one final compare.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1_u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+/// let b = m128i::from([1_u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 0]);
+/// assert!(eq_constant_time_m128i(a, a));
+/// assert!(!eq_constant_time_m128i(a, b));
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn eq_constant_time_m128i(a: m128i, b: m128i) -> bool {
+  let diff = core::hint::black_box(bitxor_m128i(a, b));
+  move_mask_i8_m128i(cmp_eq_mask_i8_m128i(diff, zeroed_m128i())) == 0xFFFF
+}
+
+/// Checks if two byte slices are equal, in constant time for a given length.
+///
+/// If the lengths differ this returns `false` right away, since the length
+/// isn't usually secret. Otherwise the work done doesn't depend on where (or
+/// if) the bytes differ, so this is suitable for checking secrets such as
+/// authentication tags.
+/// ```
+/// # use safe_arch::*;
+/// let tag = [7_u8; 20];
+/// let mut other = tag;
+/// assert!(eq_constant_time_bytes(&tag, &other));
+/// other[19] = 0;
+/// assert!(!eq_constant_time_bytes(&tag, &other));
+/// assert!(!eq_constant_time_bytes(&tag, &other[..19]));
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn eq_constant_time_bytes(a: &[u8], b: &[u8]) -> bool {
+  if a.len() != b.len() {
+    return false;
+  }
+  let mut acc = zeroed_m128i();
+  let mut a_chunks = a.chunks_exact(16);
+  let mut b_chunks = b.chunks_exact(16);
+  for (x, y) in (&mut a_chunks).zip(&mut b_chunks) {
+    let x = load_unaligned_m128i(core::convert::TryInto::try_into(x).unwrap());
+    let y = load_unaligned_m128i(core::convert::TryInto::try_into(y).unwrap());
+    acc = core::hint::black_box(bitor_m128i(acc, bitxor_m128i(x, y)));
+  }
+  let mut tail = 0_u8;
+  for (x, y) in a_chunks.remainder().iter().zip(b_chunks.remainder()) {
+    tail = core::hint::black_box(tail | (x ^ y));
+  }
+  let tail = set_i32_m128i_s(i32::from(tail));
+  eq_constant_time_m128i(bitor_m128i(acc, tail), zeroed_m128i())
+}
+
+/// Finds the index of the first `needle` byte within `haystack`.
+///
+/// When `avx2` is enabled this searches 32 bytes at a time, and otherwise 16.
+/// ```
+/// # use safe_arch::*;
+/// let mut haystack = [0_u8; 100];
+/// assert_eq!(find_byte(&haystack, 1), None);
+/// for i in (0..100).rev() {
+///   haystack[i] = 1;
+///   assert_eq!(find_byte(&haystack, 1), Some(i));
+///   assert_eq!(find_byte(&haystack[i..], 1), Some(0));
+///   assert_eq!(find_byte(&haystack[..i], 1), None);
+/// }
+/// assert_eq!(find_byte(&[], 0), None);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn find_byte(haystack: &[u8], needle: u8) -> Option<usize> {
+  #[cfg(all(target_feature = "avx2", feature = "avx2"))]
+  let start = {
+    let n = set_splat_i8_m256i(needle as i8);
+    match find_first_m256i(haystack, |x| cmp_eq_mask_i8_m256i(x, n)) {
+      Ok(i) => return Some(i),
+      Err(start) => start,
+    }
+  };
+  #[cfg(not(all(target_feature = "avx2", feature = "avx2")))]
+  let start = 0;
+  let n = set_splat_i8_m128i(needle as i8);
+  find_first_m128i(&haystack[start..], |x| cmp_eq_mask_i8_m128i(x, n))
+    .map(|i| start + i)
+}
+
+/// Finds the index of the first byte within `haystack` that's any of the bytes
+/// in `set`.
+///
+/// To search for fewer than 16 different bytes, repeat one of them to fill
+/// out the rest of `set`.
+/// ```
+/// # use safe_arch::*;
+/// let mut set = [b' '; 16];
+/// set[..3].copy_from_slice(b"\t\r\n");
+/// let text = b"the quick brown fox\njumps over the lazy dog";
+/// assert_eq!(find_any_of_16(text, set), Some(3));
+/// assert_eq!(find_any_of_16(&text[16..], set), Some(3));
+/// assert_eq!(find_any_of_16(b"nothing_to_find_here_at_all_but_more_than_32", set), None);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn find_any_of_16(haystack: &[u8], set: [u8; 16]) -> Option<usize> {
+  #[cfg(all(target_feature = "avx2", feature = "avx2"))]
+  let start = {
+    let set = set.map(|b| set_splat_i8_m256i(b as i8));
+    let any_of = |x| {
+      set.iter().fold(zeroed_m256i(), |acc, &s| bitor_m256i(acc, cmp_eq_mask_i8_m256i(x, s)))
+    };
+    match find_first_m256i(haystack, any_of) {
+      Ok(i) => return Some(i),
+      Err(start) => start,
+    }
+  };
+  #[cfg(not(all(target_feature = "avx2", feature = "avx2")))]
+  let start = 0;
+  let set = set.map(|b| set_splat_i8_m128i(b as i8));
+  let any_of = |x| {
+    set.iter().fold(zeroed_m128i(), |acc, &s| bitor_m128i(acc, cmp_eq_mask_i8_m128i(x, s)))
+  };
+  find_first_m128i(&haystack[start..], any_of).map(|i| start + i)
+}
+
+/// Counts how many times `byte` appears within `haystack`.
+///
+/// When `avx2` is enabled this checks 32 bytes at a time, and otherwise 16.
+/// ```
+/// # use safe_arch::*;
+/// let text = b"she sells sea shells by the sea shore, and the shells she sells";
+/// assert_eq!(count_byte_occurrences(text, b's'), 13);
+/// assert_eq!(count_byte_occurrences(text, b'z'), 0);
+/// assert_eq!(count_byte_occurrences(&[7; 10_000], 7), 10_000);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn count_byte_occurrences(haystack: &[u8], byte: u8) -> usize {
+  #[cfg(all(target_feature = "avx2", feature = "avx2"))]
+  let (start, total) = count_byte_m256i(haystack, byte);
+  #[cfg(not(all(target_feature = "avx2", feature = "avx2")))]
+  let (start, total) = (0, 0);
+  let needle = set_splat_i8_m128i(byte as i8);
+  let flush = |counts: m128i| -> usize {
+    let [s0, s1]: [u64; 2] = sum_of_u8_abs_diff_m128i(counts, zeroed_m128i()).into();
+    (s0 + s1) as usize
+  };
+  let mut total = total;
+  let mut counts = zeroed_m128i();
+  let mut chunks = haystack[start..].chunks_exact(16);
+  for (i, chunk) in (&mut chunks).enumerate() {
+    let x = load_unaligned_m128i(core::convert::TryInto::try_into(chunk).unwrap());
+    // A match is -1, so subtracting it adds 1 to that lane's count. That can
+    // only happen 255 times before the lane wraps, so flush the counts then.
+    counts = sub_i8_m128i(counts, cmp_eq_mask_i8_m128i(x, needle));
+    if i % 255 == 254 {
+      total += flush(counts);
+      counts = zeroed_m128i();
+    }
+  }
+  let tail = chunks.remainder().iter().filter(|&&b| b == byte).count();
+  total + flush(counts) + tail
+}
+
+/// Checks if two byte slices are equal.
+///
+/// Unlike [`eq_constant_time_bytes`] this stops at the first difference, so
+/// don't use it on secrets.
+/// ```
+/// # use safe_arch::*;
+/// let a = [3_u8; 50];
+/// let mut b = a;
+/// assert!(slices_equal(&a, &b));
+/// b[49] = 0;
+/// assert!(!slices_equal(&a, &b));
+/// assert!(!slices_equal(&a, &a[..49]));
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn slices_equal(a: &[u8], b: &[u8]) -> bool {
+  a.len() == b.len() && first_mismatch(a, b).is_none()
+}
+
+/// Finds the first index where two byte slices differ.
+///
+/// If one slice is a prefix of the other, the index is the length of the
+/// shorter slice. If the slices are equal this is `None`.
+///
+/// When `avx2` is enabled this compares 32 bytes at a time, and otherwise 16.
+/// ```
+/// # use safe_arch::*;
+/// let a = [3_u8; 70];
+/// let mut b = a;
+/// assert_eq!(first_mismatch(&a, &b), None);
+/// for i in (0..70).rev() {
+///   b[i] = 0;
+///   assert_eq!(first_mismatch(&a, &b), Some(i));
+/// }
+/// assert_eq!(first_mismatch(&a, &a[..40]), Some(40));
+/// assert_eq!(first_mismatch(&[], &a), Some(0));
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn first_mismatch(a: &[u8], b: &[u8]) -> Option<usize> {
+  let len = a.len().min(b.len());
+  let (a_short, b_short) = (&a[..len], &b[..len]);
+  #[cfg(all(target_feature = "avx2", feature = "avx2"))]
+  let start = match first_mismatch_m256i(a_short, b_short) {
+    Ok(i) => return Some(i),
+    Err(start) => start,
+  };
+  #[cfg(not(all(target_feature = "avx2", feature = "avx2")))]
+  let start = 0;
+  let mut a_chunks = a_short[start..].chunks_exact(16);
+  let mut b_chunks = b_short[start..].chunks_exact(16);
+  for (i, (x, y)) in (&mut a_chunks).zip(&mut b_chunks).enumerate() {
+    let x = load_unaligned_m128i(core::convert::TryInto::try_into(x).unwrap());
+    let y = load_unaligned_m128i(core::convert::TryInto::try_into(y).unwrap());
+    let mask = move_mask_i8_m128i(cmp_eq_mask_i8_m128i(x, y)) ^ 0xFFFF;
+    if mask != 0 {
+      return Some(start + i * 16 + mask.trailing_zeros() as usize);
+    }
+  }
+  let tail_start = len - a_chunks.remainder().len();
+  let mut tail = a_chunks.remainder().iter().zip(b_chunks.remainder());
+  match tail.position(|(x, y)| x != y) {
+    Some(i) => Some(tail_start + i),
+    None if a.len() != b.len() => Some(len),
+    None => None,
+  }
+}
+
+/// Checks if every byte of `bytes` is ASCII (below `0x80`).
+///
+/// When `avx2` is enabled this checks 32 bytes at a time, and otherwise 16.
+/// ```
+/// # use safe_arch::*;
+/// let mut text = *b"plain ascii text that is longer than thirty two bytes";
+/// assert!(is_ascii(&text));
+/// for i in 0..text.len() {
+///   let old = text[i];
+///   text[i] = 0xC3;
+///   assert!(!is_ascii(&text));
+///   text[i] = old;
+/// }
+/// assert!(is_ascii(&[]));
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn is_ascii(bytes: &[u8]) -> bool {
+  // A non-ASCII byte has its sign bit set, which is the bit that the mask
+  // gathers, so each chunk is its own mask.
+  #[cfg(all(target_feature = "avx2", feature = "avx2"))]
+  let start = match find_first_m256i(bytes, |x| x) {
+    Ok(_) => return false,
+    Err(start) => start,
+  };
+  #[cfg(not(all(target_feature = "avx2", feature = "avx2")))]
+  let start = 0;
+  find_first_m128i(&bytes[start..], |x| x).is_none()
+}
+
+/// Converts the ASCII lowercase letters of `bytes` to uppercase, in place.
+///
+/// All other bytes are left alone, as with [`<[u8]>::make_ascii_uppercase`].
+/// ```
+/// # use safe_arch::*;
+/// let mut text = *b"Hello, World! [`{@az]} and some more bytes: \xFF";
+/// let mut expected = text;
+/// expected.make_ascii_uppercase();
+/// to_ascii_uppercase_in_place(&mut text);
+/// assert_eq!(text, expected);
+/// ```
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn to_ascii_uppercase_in_place(bytes: &mut [u8]) {
+  flip_ascii_case(bytes, b'a', b'z')
+}
+
+/// Converts the ASCII uppercase letters of `bytes` to lowercase, in place.
+///
+/// All other bytes are left alone, as with [`<[u8]>::make_ascii_lowercase`].
+/// ```
+/// # use safe_arch::*;
+/// let mut text = *b"Hello, World! [`{@AZ]} AND SOME MORE BYTES: \xFF";
+/// let mut expected = text;
+/// expected.make_ascii_lowercase();
+/// to_ascii_lowercase_in_place(&mut text);
+/// assert_eq!(text, expected);
+/// ```
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn to_ascii_lowercase_in_place(bytes: &mut [u8]) {
+  flip_ascii_case(bytes, b'A', b'Z')
+}
+
+/// Flips the case bit (`0x20`) of every byte in `first..=last`, which must be
+/// an ASCII range.
+#[inline(always)]
+fn flip_ascii_case(bytes: &mut [u8], first: u8, last: u8) {
+  #[cfg(all(target_feature = "avx2", feature = "avx2"))]
+  let start = flip_ascii_case_m256i(bytes, first, last);
+  #[cfg(not(all(target_feature = "avx2", feature = "avx2")))]
+  let start = 0;
+  // Bytes `0x80` and up are negative as `i8`, so they're never in range.
+  let below = set_splat_i8_m128i(first as i8 - 1);
+  let above = set_splat_i8_m128i(last as i8 + 1);
+  let case_bit = set_splat_i8_m128i(0x20);
+  let mut chunks = bytes[start..].chunks_exact_mut(16);
+  for chunk in &mut chunks {
+    let chunk: &mut [u8; 16] = core::convert::TryInto::try_into(chunk).unwrap();
+    let x = load_unaligned_m128i(chunk);
+    let in_range = bitand_m128i(cmp_gt_mask_i8_m128i(x, below), cmp_lt_mask_i8_m128i(x, above));
+    store_unaligned_m128i(chunk, bitxor_m128i(x, bitand_m128i(in_range, case_bit)));
+  }
+  for b in chunks.into_remainder() {
+    if (first..=last).contains(b) {
+      *b ^= 0x20;
+    }
+  }
+}
+
+/// The index of the first byte where `f` gives a set mask lane, checking 16
+/// bytes at a time. The tail is padded out, and the padding lanes ignored.
+#[inline(always)]
+fn find_first_m128i(haystack: &[u8], f: impl Fn(m128i) -> m128i) -> Option<usize> {
+  let mut chunks = haystack.chunks_exact(16);
+  for (i, chunk) in (&mut chunks).enumerate() {
+    let x = load_unaligned_m128i(core::convert::TryInto::try_into(chunk).unwrap());
+    let mask = move_mask_i8_m128i(f(x));
+    if mask != 0 {
+      return Some(i * 16 + mask.trailing_zeros() as usize);
+    }
+  }
+  let tail = chunks.remainder();
+  if tail.is_empty() {
+    return None;
+  }
+  let mut padded = [0_u8; 16];
+  padded[..tail.len()].copy_from_slice(tail);
+  let mask = move_mask_i8_m128i(f(m128i::from(padded))) & ((1 << tail.len()) - 1);
+  if mask != 0 {
+    Some(haystack.len() - tail.len() + mask.trailing_zeros() as usize)
+  } else {
+    None
+  }
+}
+
 /// Gathers the sign bit of each lane.
 ///
 /// The output has lane 0 as bit 0, lane 1 as bit 1.
@@ -1794,6 +2622,35 @@ pub fn move_mask_m128d(a: m128d) -> i32 {
   unsafe { _mm_movemask_pd(a.0) }
 }
 
+/// If any lane of a comparison mask is true.
+///
+/// A lane counts as true if its sign bit is set, which is how the
+/// comparison functions make their masks.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128d::from_array([1.0, 4.0]);
+/// let m = cmp_lt_mask_m128d(a, set_splat_m128d(3.5));
+/// assert!(any_lane_true_m128d(m));
+/// assert!(!all_lanes_true_m128d(m));
+/// assert!(!any_lane_true_m128d(zeroed_m128d()));
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn any_lane_true_m128d(mask: m128d) -> bool {
+  move_mask_m128d(mask) != 0
+}
+
+/// If every lane of a comparison mask is true.
+///
+/// See [`any_lane_true_m128d`] for what counts as true.
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn all_lanes_true_m128d(mask: m128d) -> bool {
+  move_mask_m128d(mask) == 0b11
+}
+
 /// Multiplies the odd `u32` lanes and gives the widened (`u64`) results.
 ///
 /// ```
@@ -1825,6 +2682,35 @@ pub fn mul_m128d(a: m128d, b: m128d) -> m128d {
   m128d(unsafe { _mm_mul_pd(a.0, b.0) })
 }
 
+/// Lanewise `a * b + c`, fused when the `fma` module is in the build.
+///
+/// With `fma` this is `fused_mul_add_m128d`, which rounds once. Otherwise
+/// it's a multiply and then an add, which rounds twice. So the result can
+/// differ in the last bit between builds, but this is the same call either
+/// way.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128d::from_array([1.0, 2.0]);
+/// let c = mul_add_m128d(a, set_splat_m128d(2.0), set_splat_m128d(1.0)).to_array();
+/// assert_eq!(c, [3.0, 5.0]);
+/// ```
+/// * **Assembly:** one of
+///   * `vfmadd213pd xmm, xmm, xmm`
+///   * `mulpd xmm, xmm` then `addpd xmm, xmm`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn mul_add_m128d(a: m128d, b: m128d, c: m128d) -> m128d {
+  #[cfg(all(target_feature = "fma", feature = "fma"))]
+  {
+    fused_mul_add_m128d(a, b, c)
+  }
+  #[cfg(not(all(target_feature = "fma", feature = "fma")))]
+  {
+    add_m128d(mul_m128d(a, b), c)
+  }
+}
+
 /// Lowest lane `a * b`, high lane unchanged.
 /// ```
 /// # use safe_arch::*;
@@ -1918,6 +2804,44 @@ pub fn bitor_m128i(a: m128i, b: m128i) -> m128i {
   m128i(unsafe { _mm_or_si128(a.0, b.0) })
 }
 
+/// Lanewise select: lanes of `if_true` where `mask` is set, otherwise the lanes
+/// of `if_false`.
+///
+/// Each lane of `mask` should be all 1s (true) or all 0s (false), as the
+/// `cmp_*_mask` functions give.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128d::from_array([1.0, 5.0]);
+/// let b = m128d::from_array([4.0, 2.0]);
+/// let c = select_m128d(cmp_gt_mask_m128d(a, b), a, b).to_array();
+/// assert_eq!(c, [4.0, 5.0]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn select_m128d(mask: m128d, if_true: m128d, if_false: m128d) -> m128d {
+  bitor_m128d(bitand_m128d(mask, if_true), bitandnot_m128d(mask, if_false))
+}
+
+/// Lanewise select: lanes of `if_true` where `mask` is set, otherwise the lanes
+/// of `if_false`.
+///
+/// Each lane of `mask` should be all 1s (true) or all 0s (false), as the
+/// `cmp_*_mask` functions give.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1, 5, 3, 8]);
+/// let b = m128i::from([4, 2, 6, 7]);
+/// let c: [i32; 4] = select_m128i(cmp_gt_mask_i32_m128i(a, b), a, b).into();
+/// assert_eq!(c, [4, 5, 6, 8]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn select_m128i(mask: m128i, if_true: m128i, if_false: m128i) -> m128i {
+  bitor_m128i(bitand_m128i(mask, if_true), bitandnot_m128i(mask, if_false))
+}
+
 /// Saturating convert `i16` to `i8`, and pack the values.
 /// ```
 /// # use safe_arch::*;
@@ -1933,34 +2857,127 @@ pub fn pack_i16_to_i8_m128i(a: m128i, b: m128i) -> m128i {
   m128i(unsafe { _mm_packs_epi16(a.0, b.0) })
 }
 
-/// Saturating convert `i32` to `i16`, and pack the values.
+/// Saturating convert `i32` to `i16`, and pack the values.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1_i32, 2, 3, 4]);
+/// let b = m128i::from([5_i32, 6, 7, 8]);
+/// let c: [i16; 8] = pack_i32_to_i16_m128i(a, b).into();
+/// assert_eq!(c, [1_i16, 2, 3, 4, 5, 6, 7, 8]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn pack_i32_to_i16_m128i(a: m128i, b: m128i) -> m128i {
+  m128i(unsafe { _mm_packs_epi32(a.0, b.0) })
+}
+
+/// Saturating convert `i16` to `u8`, and pack the values.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([-1_i16, 2, -3, 4, -5, 6, -7, 8]);
+/// let b = m128i::from([9_i16, 10, 11, 12, 13, -14, 15, -16]);
+/// let c: [u8; 16] = pack_i16_to_u8_m128i(a, b).into();
+/// assert_eq!(c, [0, 2, 0, 4, 0, 6, 0, 8, 9, 10, 11, 12, 13, 0, 15, 0]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn pack_i16_to_u8_m128i(a: m128i, b: m128i) -> m128i {
+  m128i(unsafe { _mm_packus_epi16(a.0, b.0) })
+}
+
+/// Narrows 16 `f32` values to 16 `u8` values, multiplying each by `scale`
+/// first.
+///
+/// Each value is rounded (using the current rounding mode, which is to nearest
+/// by default) and then saturated to `0..=255`. NaN lanes become 0.
+///
+/// Use a `scale` of `255.0` to turn normalized `0.0..=1.0` data back into
+/// pixel data, or `1.0` to just convert.
+/// ```
+/// # use safe_arch::*;
+/// let a = [
+///   m128::from_array([0.0, 0.25, 0.5, 1.0]),
+///   m128::from_array([-1.0, 2.0, f32::NAN, f32::INFINITY]),
+///   m128::from_array([0.1; 4]),
+///   m128::from_array([1.0 / 255.0; 4]),
+/// ];
+/// let c = narrow_m128_to_u8_saturating(a, 255.0);
+/// assert_eq!(c, [0, 64, 128, 255, 0, 255, 0, 255, 26, 26, 26, 26, 1, 1, 1, 1]);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn narrow_m128_to_u8_saturating(a: [m128; 4], scale: f32) -> [u8; 16] {
+  let scale = set_splat_m128(scale);
+  let max = set_splat_m128(255.0);
+  // `max_m128` gives the second argument for NaN lanes.
+  let narrow = |x: m128| {
+    let clamped = min_m128(max_m128(mul_m128(x, scale), zeroed_m128()), max);
+    convert_to_i32_m128i_from_m128(clamped)
+  };
+  narrow_i32_to_u8_saturating_m128i(narrow(a[0]), narrow(a[1]), narrow(a[2]), narrow(a[3]))
+    .into()
+}
+
+/// Narrows the `i32` lanes of `a` then `b` to `i16` lanes, saturating.
+///
+/// This is just [`pack_i32_to_i16_m128i`], which already keeps the lanes in
+/// order at this width.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1, -70_000, 70_000, 4]);
+/// let b = m128i::from([5, 6, 7, 8]);
+/// let c: [i16; 8] = narrow_i32_to_i16_saturating_m128i(a, b).into();
+/// assert_eq!(c, [1, i16::MIN, i16::MAX, 4, 5, 6, 7, 8]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn narrow_i32_to_i16_saturating_m128i(a: m128i, b: m128i) -> m128i {
+  pack_i32_to_i16_m128i(a, b)
+}
+
+/// Narrows the `i16` lanes of `a` then `b` to `u8` lanes, saturating.
+///
+/// This is just [`pack_i16_to_u8_m128i`], which already keeps the lanes in
+/// order at this width.
 /// ```
 /// # use safe_arch::*;
-/// let a = m128i::from([1_i32, 2, 3, 4]);
-/// let b = m128i::from([5_i32, 6, 7, 8]);
-/// let c: [i16; 8] = pack_i32_to_i16_m128i(a, b).into();
-/// assert_eq!(c, [1_i16, 2, 3, 4, 5, 6, 7, 8]);
+/// let a = m128i::from([1_i16, -2, 300, 4, 5, 6, 7, 8]);
+/// let b = m128i::from([9_i16; 8]);
+/// let c: [u8; 16] = narrow_i16_to_u8_saturating_m128i(a, b).into();
+/// assert_eq!(c, [1, 0, 255, 4, 5, 6, 7, 8, 9, 9, 9, 9, 9, 9, 9, 9]);
 /// ```
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
-pub fn pack_i32_to_i16_m128i(a: m128i, b: m128i) -> m128i {
-  m128i(unsafe { _mm_packs_epi32(a.0, b.0) })
+pub fn narrow_i16_to_u8_saturating_m128i(a: m128i, b: m128i) -> m128i {
+  pack_i16_to_u8_m128i(a, b)
 }
 
-/// Saturating convert `i16` to `u8`, and pack the values.
+/// Narrows the `i32` lanes of `a`, `b`, `c`, then `d` to `u8` lanes,
+/// saturating.
+///
+/// This packs to `i16` first, which saturates the same way as going straight
+/// to `u8` would.
 /// ```
 /// # use safe_arch::*;
-/// let a = m128i::from([-1_i16, 2, -3, 4, -5, 6, -7, 8]);
-/// let b = m128i::from([9_i16, 10, 11, 12, 13, -14, 15, -16]);
-/// let c: [u8; 16] = pack_i16_to_u8_m128i(a, b).into();
-/// assert_eq!(c, [0, 2, 0, 4, 0, 6, 0, 8, 9, 10, 11, 12, 13, 0, 15, 0]);
+/// let a = m128i::from([1, -2, 300, 100_000]);
+/// let b = m128i::from([5, 6, 7, 8]);
+/// let c = m128i::from([9, 10, 11, 12]);
+/// let d = m128i::from([i32::MIN, 14, 15, i32::MAX]);
+/// let e: [u8; 16] = narrow_i32_to_u8_saturating_m128i(a, b, c, d).into();
+/// assert_eq!(e, [1, 0, 255, 255, 5, 6, 7, 8, 9, 10, 11, 12, 0, 14, 15, 255]);
 /// ```
 #[must_use]
-#[inline(always)]
+#[inline]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
-pub fn pack_i16_to_u8_m128i(a: m128i, b: m128i) -> m128i {
-  m128i(unsafe { _mm_packus_epi16(a.0, b.0) })
+pub fn narrow_i32_to_u8_saturating_m128i(
+  a: m128i, b: m128i, c: m128i, d: m128i,
+) -> m128i {
+  pack_i16_to_u8_m128i(pack_i32_to_i16_m128i(a, b), pack_i32_to_i16_m128i(c, d))
 }
 
 /// Compute "sum of `u8` absolute differences".
@@ -1982,6 +2999,67 @@ pub fn sum_of_u8_abs_diff_m128i(a: m128i, b: m128i) -> m128i {
   m128i(unsafe { _mm_sad_epu8(a.0, b.0) })
 }
 
+/// Inclusive prefix sum of the `i32` lanes, wrapping on overflow.
+///
+/// Output lane `n` is the sum of input lanes `0..=n`.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1, 2, 3, 4]);
+/// let c: [i32; 4] = prefix_sum_i32_m128i(a).into();
+/// assert_eq!(c, [1, 3, 6, 10]);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn prefix_sum_i32_m128i(a: m128i) -> m128i {
+  let a = add_i32_m128i(a, byte_shl_imm_u128_m128i::<4>(a));
+  add_i32_m128i(a, byte_shl_imm_u128_m128i::<8>(a))
+}
+
+/// Inclusive prefix sum of the `f32` lanes.
+///
+/// Output lane `n` is the sum of input lanes `0..=n`.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128::from_array([1.0, 2.0, 3.0, 4.5]);
+/// assert_eq!(prefix_sum_m128(a).to_array(), [1.0, 3.0, 6.0, 10.5]);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn prefix_sum_m128(a: m128) -> m128 {
+  let bits = cast_to_m128i_from_m128(a);
+  let a = add_m128(a, cast_to_m128_from_m128i(byte_shl_imm_u128_m128i::<4>(bits)));
+  let bits = cast_to_m128i_from_m128(a);
+  add_m128(a, cast_to_m128_from_m128i(byte_shl_imm_u128_m128i::<8>(bits)))
+}
+
+/// Replaces each element of `data` with the sum of itself and all elements
+/// before it, wrapping on overflow.
+/// ```
+/// # use safe_arch::*;
+/// let mut data = [1_i32; 11];
+/// running_sum_i32(&mut data);
+/// assert_eq!(data, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+/// ```
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn running_sum_i32(data: &mut [i32]) {
+  let mut carry = zeroed_m128i();
+  let mut chunks = data.chunks_exact_mut(4);
+  for chunk in &mut chunks {
+    let x: [i32; 4] = core::convert::TryInto::try_into(&*chunk).unwrap();
+    let sums = add_i32_m128i(prefix_sum_i32_m128i(m128i::from(x)), carry);
+    chunk.copy_from_slice(&<[i32; 4]>::from(sums));
+    carry = shuffle_ai_f32_all_m128i::<0b11_11_11_11>(sums);
+  }
+  let mut total = get_i32_from_m128i_s(carry);
+  for x in chunks.into_remainder() {
+    total = total.wrapping_add(*x);
+    *x = total;
+  }
+}
+
 /// Sets the args into an `m128i`, first arg is the high lane.
 /// ```
 /// # use safe_arch::*;
@@ -2080,8 +3158,8 @@ pub fn set_m128d_s(a: f64) -> m128d {
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
-pub fn set_splat_m128d(a: f64) -> m128d {
-  m128d(unsafe { _mm_set1_pd(a) })
+pub const fn set_splat_m128d(a: f64) -> m128d {
+  m128d::from_array([a; 2])
 }
 
 /// Splats the `i8` to all lanes of the `m128i`.
@@ -2094,8 +3172,8 @@ pub fn set_splat_m128d(a: f64) -> m128d {
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
-pub fn set_splat_i8_m128i(i: i8) -> m128i {
-  m128i(unsafe { _mm_set1_epi8(i) })
+pub const fn set_splat_i8_m128i(i: i8) -> m128i {
+  unsafe { core::mem::transmute([i; 16]) }
 }
 
 /// Splats the `i16` to all lanes of the `m128i`.
@@ -2108,8 +3186,8 @@ pub fn set_splat_i8_m128i(i: i8) -> m128i {
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
-pub fn set_splat_i16_m128i(i: i16) -> m128i {
-  m128i(unsafe { _mm_set1_epi16(i) })
+pub const fn set_splat_i16_m128i(i: i16) -> m128i {
+  unsafe { core::mem::transmute([i; 8]) }
 }
 
 /// Splats the `i32` to all lanes of the `m128i`.
@@ -2122,8 +3200,8 @@ pub fn set_splat_i16_m128i(i: i16) -> m128i {
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
-pub fn set_splat_i32_m128i(i: i32) -> m128i {
-  m128i(unsafe { _mm_set1_epi32(i) })
+pub const fn set_splat_i32_m128i(i: i32) -> m128i {
+  unsafe { core::mem::transmute([i; 4]) }
 }
 
 /// Splats the `i64` to both lanes of the `m128i`.
@@ -2136,8 +3214,8 @@ pub fn set_splat_i32_m128i(i: i32) -> m128i {
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
-pub fn set_splat_i64_m128i(i: i64) -> m128i {
-  m128i(unsafe { _mm_set1_epi64x(i) })
+pub const fn set_splat_i64_m128i(i: i64) -> m128i {
+  unsafe { core::mem::transmute([i; 2]) }
 }
 
 /// Sets the args into an `m128i`, first arg is the low lane.
@@ -2209,8 +3287,8 @@ pub fn set_reversed_m128d(a: f64, b: f64) -> m128d {
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
-pub fn zeroed_m128i() -> m128i {
-  m128i(unsafe { _mm_setzero_si128() })
+pub const fn zeroed_m128i() -> m128i {
+  unsafe { core::mem::transmute([0_u64; 2]) }
 }
 
 /// Both lanes zero.
@@ -2222,8 +3300,8 @@ pub fn zeroed_m128i() -> m128i {
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
-pub fn zeroed_m128d() -> m128d {
-  m128d(unsafe { _mm_setzero_pd() })
+pub const fn zeroed_m128d() -> m128d {
+  m128d::from_array([0.0; 2])
 }
 
 /// Shuffle the `i32` lanes in `$a` using an immediate
@@ -2323,6 +3401,42 @@ pub fn shl_all_u16_m128i(a: m128i, count: m128i) -> m128i {
   m128i(unsafe { _mm_sll_epi16(a.0, count.0) })
 }
 
+/// Shift all `u16` lanes to the left by `count`, giving 0 if `count` is
+/// 16 or more.
+///
+/// The plain shifts (such as [`shl_all_u16_m128i`]) are the bare `psll`,
+/// `psrl`, and `psra` instructions, which take their count from the whole
+/// lower `u64` lane of a register. For a count of the lane width or more the
+/// hardware gives 0 for `psll` and `psrl` and fills each lane with its sign bit
+/// for `psra`, but any junk in the upper bits of that lane (such as from
+/// splatting the count to every lane) also counts, and Rust's own `<<` and `>>`
+/// on a lane would panic (debug) or wrap the count (release) instead.
+///
+/// The `checked` shifts take a plain `u32`, and check it themselves rather than
+/// leaving it to the hardware:
+/// * A count less than the lane width shifts normally.
+/// * Otherwise, left shifts and unsigned right shifts give 0 in every lane, and
+///   signed right shifts clamp the count to one less than the lane width, so
+///   every bit is a copy of the sign bit.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1_u16, 100, 1, 100, 1, 100, 1, 100]);
+/// let b: [u16; 8] = shl_all_checked_u16_m128i(a, 2).into();
+/// assert_eq!(b, [1 << 2, 100 << 2, 1 << 2, 100 << 2, 1 << 2, 100 << 2, 1 << 2, 100 << 2]);
+/// let c: [u16; 8] = shl_all_checked_u16_m128i(a, 16).into();
+/// assert_eq!(c, [0, 0, 0, 0, 0, 0, 0, 0]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn shl_all_checked_u16_m128i(a: m128i, count: u32) -> m128i {
+  if count < 16 {
+    shl_all_u16_m128i(a, set_i32_m128i_s(count as i32))
+  } else {
+    zeroed_m128i()
+  }
+}
+
 /// Shift all `u32` lanes to the left by the `count` in the lower `u64` lane.
 ///
 /// New bits are 0s.
@@ -2340,6 +3454,30 @@ pub fn shl_all_u32_m128i(a: m128i, count: m128i) -> m128i {
   m128i(unsafe { _mm_sll_epi32(a.0, count.0) })
 }
 
+/// Shift all `u32` lanes to the left by `count`, giving 0 if `count` is
+/// 32 or more.
+///
+/// See [`shl_all_checked_u16_m128i`] for how this differs from
+/// [`shl_all_u32_m128i`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1_u32, 100, 1, 100]);
+/// let b: [u32; 4] = shl_all_checked_u32_m128i(a, 2).into();
+/// assert_eq!(b, [1 << 2, 100 << 2, 1 << 2, 100 << 2]);
+/// let c: [u32; 4] = shl_all_checked_u32_m128i(a, 32).into();
+/// assert_eq!(c, [0, 0, 0, 0]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn shl_all_checked_u32_m128i(a: m128i, count: u32) -> m128i {
+  if count < 32 {
+    shl_all_u32_m128i(a, set_i32_m128i_s(count as i32))
+  } else {
+    zeroed_m128i()
+  }
+}
+
 /// Shift all `u64` lanes to the left by the `count` in the lower `u64` lane.
 ///
 /// New bits are 0s.
@@ -2357,6 +3495,30 @@ pub fn shl_all_u64_m128i(a: m128i, count: m128i) -> m128i {
   m128i(unsafe { _mm_sll_epi64(a.0, count.0) })
 }
 
+/// Shift all `u64` lanes to the left by `count`, giving 0 if `count` is
+/// 64 or more.
+///
+/// See [`shl_all_checked_u16_m128i`] for how this differs from
+/// [`shl_all_u64_m128i`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1_u64, 100]);
+/// let b: [u64; 2] = shl_all_checked_u64_m128i(a, 2).into();
+/// assert_eq!(b, [1 << 2, 100 << 2]);
+/// let c: [u64; 2] = shl_all_checked_u64_m128i(a, 64).into();
+/// assert_eq!(c, [0, 0]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn shl_all_checked_u64_m128i(a: m128i, count: u32) -> m128i {
+  if count < 64 {
+    shl_all_u64_m128i(a, set_i32_m128i_s(count as i32))
+  } else {
+    zeroed_m128i()
+  }
+}
+
 /// Shifts all `u16` lanes left by an immediate.
 ///
 /// ```
@@ -2402,6 +3564,183 @@ pub fn shl_imm_u64_m128i<const IMM: i32>(a: m128i) -> m128i {
   m128i(unsafe { _mm_slli_epi64(a.0, IMM) })
 }
 
+/// Hints to the CPU that this is the body of a spin-wait loop.
+///
+/// This lets the CPU save power and avoid a memory order mix-up (and the
+/// pipeline flush it causes) when the loop finally exits. It's the same thing
+/// as [`core::hint::spin_loop`], but with the instruction spelled out.
+/// ```
+/// # use safe_arch::*;
+/// use core::sync::atomic::{AtomicBool, Ordering};
+/// let ready = AtomicBool::new(true);
+/// while !ready.load(Ordering::Acquire) {
+///   spin_loop_pause();
+/// }
+/// ```
+/// * **Intrinsic:** [`_mm_pause`]
+/// * **Assembly:** `pause`
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn spin_loop_pause() {
+  // Note: newer compilers consider this intrinsic safe to call.
+  #[allow(unused_unsafe)]
+  unsafe {
+    _mm_pause()
+  }
+}
+
+/// Exponential backoff for spin loops, built on [`spin_loop_pause`].
+///
+/// Each [`spin`](Self::spin) pauses twice as many times as the one before, up
+/// to `2^limit` pauses. Once the limit is reached spinning longer mostly wastes
+/// power, so [`snooze`](Self::snooze) calls a yield hook that you give it
+/// instead (such as `std::thread::yield_now`, since this crate is `no_std`).
+/// ```
+/// # use safe_arch::*;
+/// use core::sync::atomic::{AtomicBool, Ordering};
+/// let ready = AtomicBool::new(false);
+/// let mut backoff = Backoff::new();
+/// let mut yields = 0;
+/// while !ready.load(Ordering::Acquire) {
+///   backoff.snooze(|| {
+///     yields += 1;
+///     // pretend another thread finished the work
+///     ready.store(true, Ordering::Release);
+///   });
+/// }
+/// assert!(backoff.is_completed());
+/// assert_eq!(yields, 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub struct Backoff {
+  step: u32,
+  limit: u32,
+}
+impl Default for Backoff {
+  #[inline(always)]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+impl Backoff {
+  /// A backoff that spins up to `2^6` pauses at a time.
+  #[must_use]
+  #[inline(always)]
+  pub const fn new() -> Self {
+    Self::with_limit(6)
+  }
+
+  /// A backoff that spins up to `2^limit` pauses at a time.
+  ///
+  /// The limit is capped at 31 so the pause count fits a `u32`.
+  #[must_use]
+  #[inline(always)]
+  pub const fn with_limit(limit: u32) -> Self {
+    Self { step: 0, limit: if limit < 31 { limit } else { 31 } }
+  }
+
+  /// Pauses `2^step` times, then moves to the next step (if below the limit).
+  #[inline]
+  pub fn spin(&mut self) {
+    for _ in 0..(1_u32 << self.step) {
+      spin_loop_pause();
+    }
+    if self.step < self.limit {
+      self.step += 1;
+    }
+  }
+
+  /// Like [`spin`](Self::spin), but once the limit is reached it calls
+  /// `yield_now` instead of pausing.
+  #[inline]
+  pub fn snooze(&mut self, yield_now: impl FnOnce()) {
+    if self.is_completed() {
+      yield_now()
+    } else {
+      self.spin()
+    }
+  }
+
+  /// If the limit has been reached, so that [`snooze`](Self::snooze) yields.
+  #[must_use]
+  #[inline(always)]
+  pub const fn is_completed(&self) -> bool {
+    self.step >= self.limit
+  }
+
+  /// Goes back to the first step.
+  #[inline(always)]
+  pub fn reset(&mut self) {
+    self.step = 0;
+  }
+}
+
+/// Counts timestamp counter ticks across a span of code, for benchmarking.
+///
+/// Both ends read the counter with `rdtscp` followed by an `lfence`. The
+/// `rdtscp` waits for all earlier instructions to finish, and the `lfence`
+/// keeps later instructions from starting before the read, so the span
+/// contains exactly the code between [`start`](Self::start) and
+/// [`elapsed`](Self::elapsed).
+///
+/// The counter ticks at a fixed reference rate on modern CPUs, which isn't
+/// the same as core clock cycles when the clock speed changes. Each read also
+/// gives the `IA32_TSC_AUX` value, which the OS sets to identify the core (on
+/// Linux it's `node << 12 | cpu`). If the thread moves to another core during
+/// the span the two values differ, and the count probably isn't meaningful.
+/// ```
+/// # use safe_arch::*;
+/// let timer = CycleTimer::start();
+/// let x = (0..100_u64).sum::<u64>();
+/// let (ticks, aux) = timer.elapsed();
+/// assert_eq!(x, 4950);
+/// if aux == timer.start_aux() {
+///   assert!(ticks < 1_000_000_000);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub struct CycleTimer {
+  start: u64,
+  start_aux: u32,
+}
+impl CycleTimer {
+  /// Reads the counter to begin a span.
+  #[must_use]
+  #[inline(always)]
+  pub fn start() -> Self {
+    let (start, start_aux) = Self::read();
+    Self { start, start_aux }
+  }
+
+  /// The `IA32_TSC_AUX` value read by [`start`](Self::start).
+  #[must_use]
+  #[inline(always)]
+  pub fn start_aux(&self) -> u32 {
+    self.start_aux
+  }
+
+  /// Reads the counter again, giving the ticks since the start and the
+  /// `IA32_TSC_AUX` value of this read.
+  ///
+  /// This doesn't change the timer, so you can call it more than once.
+  #[must_use]
+  #[inline(always)]
+  pub fn elapsed(&self) -> (u64, u32) {
+    let (now, aux) = Self::read();
+    (now.wrapping_sub(self.start), aux)
+  }
+
+  #[inline(always)]
+  fn read() -> (u64, u32) {
+    let mut aux = 0;
+    let t = read_timestamp_counter_p(&mut aux);
+    unsafe { _mm_lfence() };
+    (t, aux)
+  }
+}
+
 /// Lanewise `sqrt(a)`.
 /// ```
 /// # use safe_arch::*;
@@ -2448,6 +3787,26 @@ pub fn shr_all_i16_m128i(a: m128i, count: m128i) -> m128i {
   m128i(unsafe { _mm_sra_epi16(a.0, count.0) })
 }
 
+/// Shift all `i16` lanes to the right by `count`, filling each lane with
+/// its sign bit if `count` is 16 or more.
+///
+/// See [`shl_all_checked_u16_m128i`] for how this differs from
+/// [`shr_all_i16_m128i`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1_i16, -100, 1, -100, 1, -100, 1, -100]);
+/// let b: [i16; 8] = shr_all_checked_i16_m128i(a, 2).into();
+/// assert_eq!(b, [1 >> 2, -100 >> 2, 1 >> 2, -100 >> 2, 1 >> 2, -100 >> 2, 1 >> 2, -100 >> 2]);
+/// let c: [i16; 8] = shr_all_checked_i16_m128i(a, 16).into();
+/// assert_eq!(c, [0, -1, 0, -1, 0, -1, 0, -1]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn shr_all_checked_i16_m128i(a: m128i, count: u32) -> m128i {
+  shr_all_i16_m128i(a, set_i32_m128i_s(count.min(15) as i32))
+}
+
 /// Shift each `i32` lane to the right by the `count` in the lower `i64` lane.
 ///
 /// New bits are the sign bit.
@@ -2465,6 +3824,26 @@ pub fn shr_all_i32_m128i(a: m128i, count: m128i) -> m128i {
   m128i(unsafe { _mm_sra_epi32(a.0, count.0) })
 }
 
+/// Shift all `i32` lanes to the right by `count`, filling each lane with
+/// its sign bit if `count` is 32 or more.
+///
+/// See [`shl_all_checked_u16_m128i`] for how this differs from
+/// [`shr_all_i32_m128i`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1_i32, -100, 1, -100]);
+/// let b: [i32; 4] = shr_all_checked_i32_m128i(a, 2).into();
+/// assert_eq!(b, [1 >> 2, -100 >> 2, 1 >> 2, -100 >> 2]);
+/// let c: [i32; 4] = shr_all_checked_i32_m128i(a, 32).into();
+/// assert_eq!(c, [0, -1, 0, -1]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn shr_all_checked_i32_m128i(a: m128i, count: u32) -> m128i {
+  shr_all_i32_m128i(a, set_i32_m128i_s(count.min(31) as i32))
+}
+
 /// Shifts all `i16` lanes right by an immediate.
 ///
 /// New bits are the sign bit.
@@ -2515,6 +3894,30 @@ pub fn shr_all_u16_m128i(a: m128i, count: m128i) -> m128i {
   m128i(unsafe { _mm_srl_epi16(a.0, count.0) })
 }
 
+/// Shift all `u16` lanes to the right by `count`, giving 0 if `count` is
+/// 16 or more.
+///
+/// See [`shl_all_checked_u16_m128i`] for how this differs from
+/// [`shr_all_u16_m128i`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1_u16, 100, 1, 100, 1, 100, 1, 100]);
+/// let b: [u16; 8] = shr_all_checked_u16_m128i(a, 2).into();
+/// assert_eq!(b, [1 >> 2, 100 >> 2, 1 >> 2, 100 >> 2, 1 >> 2, 100 >> 2, 1 >> 2, 100 >> 2]);
+/// let c: [u16; 8] = shr_all_checked_u16_m128i(a, 16).into();
+/// assert_eq!(c, [0, 0, 0, 0, 0, 0, 0, 0]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn shr_all_checked_u16_m128i(a: m128i, count: u32) -> m128i {
+  if count < 16 {
+    shr_all_u16_m128i(a, set_i32_m128i_s(count as i32))
+  } else {
+    zeroed_m128i()
+  }
+}
+
 /// Shift each `u32` lane to the right by the `count` in the lower `u64` lane.
 ///
 /// ```
@@ -2531,6 +3934,30 @@ pub fn shr_all_u32_m128i(a: m128i, count: m128i) -> m128i {
   m128i(unsafe { _mm_srl_epi32(a.0, count.0) })
 }
 
+/// Shift all `u32` lanes to the right by `count`, giving 0 if `count` is
+/// 32 or more.
+///
+/// See [`shl_all_checked_u16_m128i`] for how this differs from
+/// [`shr_all_u32_m128i`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1_u32, 100, 1, 100]);
+/// let b: [u32; 4] = shr_all_checked_u32_m128i(a, 2).into();
+/// assert_eq!(b, [1 >> 2, 100 >> 2, 1 >> 2, 100 >> 2]);
+/// let c: [u32; 4] = shr_all_checked_u32_m128i(a, 32).into();
+/// assert_eq!(c, [0, 0, 0, 0]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn shr_all_checked_u32_m128i(a: m128i, count: u32) -> m128i {
+  if count < 32 {
+    shr_all_u32_m128i(a, set_i32_m128i_s(count as i32))
+  } else {
+    zeroed_m128i()
+  }
+}
+
 /// Shift each `u64` lane to the right by the `count` in the lower `u64` lane.
 ///
 /// New bits are 0s.
@@ -2548,6 +3975,30 @@ pub fn shr_all_u64_m128i(a: m128i, count: m128i) -> m128i {
   m128i(unsafe { _mm_srl_epi64(a.0, count.0) })
 }
 
+/// Shift all `u64` lanes to the right by `count`, giving 0 if `count` is
+/// 64 or more.
+///
+/// See [`shl_all_checked_u16_m128i`] for how this differs from
+/// [`shr_all_u64_m128i`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1_u64, 100]);
+/// let b: [u64; 2] = shr_all_checked_u64_m128i(a, 2).into();
+/// assert_eq!(b, [1 >> 2, 100 >> 2]);
+/// let c: [u64; 2] = shr_all_checked_u64_m128i(a, 64).into();
+/// assert_eq!(c, [0, 0]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn shr_all_checked_u64_m128i(a: m128i, count: u32) -> m128i {
+  if count < 64 {
+    shr_all_u64_m128i(a, set_i32_m128i_s(count as i32))
+  } else {
+    zeroed_m128i()
+  }
+}
+
 /// Shifts all `u16` lanes right by an immediate.
 ///
 /// New bits are 0s.
@@ -2660,6 +4111,55 @@ pub fn store_m128i(r: &mut m128i, a: m128i) {
   unsafe { _mm_store_si128(&mut r.0, a.0) }
 }
 
+impl<'a> StreamingStores<'a> {
+  /// Stores the value to the reference given, bypassing the cache.
+  ///
+  /// * **Intrinsic:** [`_mm_stream_pd`]
+  /// * **Assembly:** `movntpd m128, xmm`
+  #[inline(always)]
+  #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+  pub fn store_nontemporal_m128d(&mut self, r: &'a mut m128d, a: m128d) {
+    unsafe { _mm_stream_pd(r as *mut m128d as *mut f64, a.0) }
+  }
+
+  /// Stores the value to the reference given, bypassing the cache.
+  /// ```
+  /// # use safe_arch::*;
+  /// let mut r = zeroed_m128i();
+  /// streaming_stores(|s| s.store_nontemporal_m128i(&mut r, set_splat_i32_m128i(7)));
+  /// assert_eq!(<[i32; 4]>::from(r), [7; 4]);
+  /// ```
+  /// * **Intrinsic:** [`_mm_stream_si128`]
+  /// * **Assembly:** `movntdq m128, xmm`
+  #[inline(always)]
+  #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+  pub fn store_nontemporal_m128i(&mut self, r: &'a mut m128i, a: m128i) {
+    unsafe { _mm_stream_si128(r as *mut m128i as *mut __m128i, a.0) }
+  }
+
+  /// Stores the bytes of `a` where the `mask` byte is negative, bypassing the
+  /// cache.
+  ///
+  /// The other bytes of `r` are left alone. This is a non-temporal store just
+  /// like the others here, so it's only on this type, and it's usually slow on
+  /// newer CPUs (a blend and a normal store is often faster).
+  /// ```
+  /// # use safe_arch::*;
+  /// let mut r = [0_u8; 16];
+  /// let mask = m128i::from([-1_i8, 0, -1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, -1]);
+  /// let a = set_splat_i8_m128i(9);
+  /// streaming_stores(|s| s.store_bytes_masked_m128i(&mut r, mask, a));
+  /// assert_eq!(r, [9, 0, 9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 9]);
+  /// ```
+  /// * **Intrinsic:** [`_mm_maskmoveu_si128`]
+  /// * **Assembly:** `maskmovdqu xmm, xmm`
+  #[inline(always)]
+  #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+  pub fn store_bytes_masked_m128i(&mut self, r: &'a mut [u8; 16], mask: m128i, a: m128i) {
+    unsafe { _mm_maskmoveu_si128(a.0, mask.0, r.as_mut_ptr() as *mut i8) }
+  }
+}
+
 /// Stores the high lane value to the reference given.
 /// ```
 /// # use safe_arch::*;
@@ -2674,7 +4174,25 @@ pub fn store_high_m128d_s(r: &mut f64, a: m128d) {
   unsafe { _mm_storeh_pd(r as *mut f64, a.0) }
 }
 
-/// Stores the value to the reference given.
+/// Stores the low `i32` lane to the reference given.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1, 2, 3, 4]);
+/// let mut b = 0_i32;
+/// store_i32_m128i_s(&mut b, a);
+/// assert_eq!(b, 1_i32);
+/// ```
+/// * **Intrinsic:** [`_mm_cvtsi128_si32`]
+/// * **Assembly:** `movd m32, xmm`
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn store_i32_m128i_s(r: &mut i32, a: m128i) {
+  *r = unsafe { _mm_cvtsi128_si32(a.0) };
+}
+
+/// Stores the low `i64` lane to the reference given.
+///
+/// Only 8 bytes are written, and only with the alignment of `i64`.
 /// ```
 /// # use safe_arch::*;
 /// let a = m128i::from([1_i64, 2]);
@@ -2682,11 +4200,66 @@ pub fn store_high_m128d_s(r: &mut f64, a: m128d) {
 /// store_i64_m128i_s(&mut b, a);
 /// assert_eq!(b, 1_i64);
 /// ```
+/// * **Intrinsic:** [`_mm_storeu_si64`]
+/// * **Assembly:** `movq m64, xmm`
 #[inline(always)]
-#[allow(clippy::cast_ptr_alignment)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
 pub fn store_i64_m128i_s(r: &mut i64, a: m128i) {
-  unsafe { _mm_storel_epi64(r as *mut i64 as *mut __m128i, a.0) }
+  unsafe { _mm_storeu_si64(r as *mut i64 as *mut u8, a.0) }
+}
+
+/// Stores the low `i16` lane of the register as 2 bytes.
+///
+/// The bytes can have any alignment, and only those 2 bytes are written.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1_u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+/// let mut b = [0_u8; 2];
+/// store_unaligned_i16_m128i_s(&mut b, a);
+/// assert_eq!(b, [1, 2]);
+/// ```
+/// * **Intrinsic:** [`_mm_cvtsi128_si32`]
+/// * **Assembly:** `movd r32, xmm` then `mov m16, r16`
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn store_unaligned_i16_m128i_s(r: &mut [u8; 2], a: m128i) {
+  *r = (get_i32_from_m128i_s(a) as u16).to_ne_bytes();
+}
+
+/// Stores the low `i32` lane of the register as 4 bytes.
+///
+/// The bytes can have any alignment, and only those 4 bytes are written.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1_u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+/// let mut b = [0_u8; 4];
+/// store_unaligned_i32_m128i_s(&mut b, a);
+/// assert_eq!(b, [1, 2, 3, 4]);
+/// ```
+/// * **Intrinsic:** [`_mm_cvtsi128_si32`]
+/// * **Assembly:** `movd m32, xmm`
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn store_unaligned_i32_m128i_s(r: &mut [u8; 4], a: m128i) {
+  *r = get_i32_from_m128i_s(a).to_ne_bytes();
+}
+
+/// Stores the low `i64` lane of the register as 8 bytes.
+///
+/// The bytes can have any alignment, and only those 8 bytes are written.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1_u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
+/// let mut b = [0_u8; 8];
+/// store_unaligned_i64_m128i_s(&mut b, a);
+/// assert_eq!(b, [1, 2, 3, 4, 5, 6, 7, 8]);
+/// ```
+/// * **Intrinsic:** [`_mm_storeu_si64`]
+/// * **Assembly:** `movq m64, xmm`
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn store_unaligned_i64_m128i_s(r: &mut [u8; 8], a: m128i) {
+  unsafe { _mm_storeu_si64(r.as_mut_ptr(), a.0) }
 }
 
 /// Stores the value to the reference given.
@@ -3047,6 +4620,65 @@ pub fn bitxor_m128d(a: m128d, b: m128d) -> m128d {
   m128d(unsafe { _mm_xor_pd(a.0, b.0) })
 }
 
+/// Lanewise `-a`, by flipping the sign bit.
+///
+/// Unlike `0.0 - a`, this turns `0.0` into `-0.0` (and back), and it flips the
+/// sign of NaN lanes too, as [`f64`]'s `Neg` does.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128d::from_array([1.0, -2.0]);
+/// let c = negate_m128d(a).to_array();
+/// let expected: [f64; 2] = [-1.0, 2.0];
+/// for (c, e) in c.iter().zip(expected.iter()) {
+///   assert_eq!(c.to_bits(), e.to_bits());
+/// }
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn negate_m128d(a: m128d) -> m128d {
+  bitxor_m128d(a, set_splat_m128d(-0.0))
+}
+
+/// Lanewise `|a|`, by clearing the sign bit.
+///
+/// This matches [`f64::abs`], including for `-0.0` and NaN lanes.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128d::from_array([1.0, -2.0]);
+/// let c = abs_m128d(a).to_array();
+/// let expected: [f64; 2] = [1.0, 2.0];
+/// for (c, e) in c.iter().zip(expected.iter()) {
+///   assert_eq!(c.to_bits(), e.to_bits());
+/// }
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn abs_m128d(a: m128d) -> m128d {
+  bitandnot_m128d(set_splat_m128d(-0.0), a)
+}
+
+/// Lanewise the magnitude of `magnitude` with the sign bit of `sign`.
+///
+/// This matches [`f64::copysign`], so the sign of zero and NaN lanes counts.
+/// ```
+/// # use safe_arch::*;
+/// let magnitude = m128d::from_array([1.0, -2.0]);
+/// let sign = m128d::from_array([-1.0, 1.0]);
+/// let c = copysign_m128d(magnitude, sign).to_array();
+/// for ((c, m), s) in c.iter().zip(magnitude.to_array().iter()).zip(sign.to_array().iter()) {
+///   assert_eq!(c.to_bits(), m.copysign(*s).to_bits());
+/// }
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse2")))]
+pub fn copysign_m128d(magnitude: m128d, sign: m128d) -> m128d {
+  let sign_bit = set_splat_m128d(-0.0);
+  bitor_m128d(bitandnot_m128d(sign_bit, magnitude), bitand_m128d(sign_bit, sign))
+}
+
 /// Bitwise `a ^ b`.
 /// ```
 /// # use safe_arch::*;
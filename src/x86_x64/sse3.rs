@@ -101,3 +101,22 @@ pub fn duplicate_even_lanes_m128(a: m128) -> m128 {
   m128(unsafe { _mm_moveldup_ps(a.0) })
 }
 
+/// Loads the reference given, like [`load_unaligned_m128i`].
+///
+/// On some older CPUs this is faster than `movdqu` when the data crosses a
+/// cache line boundary. On newer CPUs the two are the same.
+/// ```
+/// # use safe_arch::*;
+/// let a = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+/// let b = load_unaligned_lddqu_m128i(&a);
+/// assert_eq!(a, <[u8; 16]>::from(b));
+/// ```
+/// * **Intrinsic:** [`_mm_lddqu_si128`]
+/// * **Assembly:** `lddqu xmm, m128`
+#[must_use]
+#[inline(always)]
+#[allow(clippy::cast_ptr_alignment)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse3")))]
+pub fn load_unaligned_lddqu_m128i(a: &[u8; 16]) -> m128i {
+  m128i(unsafe { _mm_lddqu_si128(a as *const [u8; 16] as *const __m128i) })
+}
@@ -7,16 +7,31 @@ use super::*;
 /// Each bit 0 though 7 controls lane 0 through 7. Use 0 for the `a` value and
 /// 1 for the `b` value.
 ///
+/// To blend `i32` lanes without AVX2, give each lane two bits (`0b11` for
+/// `b`, `0b00` for `a`).
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([0_i16, 1, 2, 3, 4, 5, 6, 7]);
+/// let b = m128i::from([-1_i16; 8]);
+/// let c: [i16; 8] = blend_imm_i16_m128i::<0b1000_0001>(a, b).into();
+/// assert_eq!(c, [-1, 1, 2, 3, 4, 5, 6, -1]);
+///
+/// let a = m128i::from([10_i32, 20, 30, 40]);
+/// let b = m128i::from([100_i32, 200, 300, 400]);
+/// let c: [i32; 4] = blend_imm_i16_m128i::<0b0011_1100>(a, b).into();
+/// assert_eq!(c, [10, 200, 300, 40]);
+/// ```
 /// * **Intrinsic:** [`_mm_blend_epi16`]
 /// * **Assembly:** `pblendw xmm, xmm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 pub fn blend_imm_i16_m128i<const IMM: i32>(a: m128i, b: m128i) -> m128i {
+  let () = ImmBits::<IMM, 8>::OK;
   m128i(unsafe { _mm_blend_epi16(a.0, b.0, IMM) })
 }
 
-/// Blends the `i16` lanes according to the immediate mask.
+/// Blends the `f64` lanes according to the immediate mask.
 ///
 /// Bits 0 and 1 control where output lane 0 and 1 come from. Use 0 for the `a`
 /// value and 1 for the `b` value.
@@ -27,6 +42,7 @@ pub fn blend_imm_i16_m128i<const IMM: i32>(a: m128i, b: m128i) -> m128i {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 pub fn blend_imm_m128d<const IMM: i32>(a: m128d, b: m128d) -> m128d {
+  let () = ImmBits::<IMM, 2>::OK;
   m128d(unsafe { _mm_blend_pd(a.0, b.0, IMM) })
 }
 
@@ -41,14 +57,23 @@ pub fn blend_imm_m128d<const IMM: i32>(a: m128d, b: m128d) -> m128d {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 pub fn blend_imm_m128<const IMM: i32>(a: m128, b: m128) -> m128 {
+  let () = ImmBits::<IMM, 4>::OK;
   m128(unsafe { _mm_blend_ps(a.0, b.0, IMM) })
 }
 
 /// Blend the `i8` lanes according to a runtime varying mask.
 ///
 /// The sign bit of each `i8` lane in the `mask` value determines if the output
-/// lane uses `a` (mask non-negative) or `b` (mask negative).
-///
+/// lane uses `a` (mask non-negative) or `b` (mask negative). Comparison masks
+/// are all ones or all zeroes per lane, so they work with any lane width.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([1_i32, 2, 3, 4]);
+/// let b = m128i::from([5_i32, 6, 7, 8]);
+/// let mask = cmp_gt_mask_i32_m128i(m128i::from([0_i32, 9, 0, 9]), m128i::from([5_i32; 4]));
+/// let c: [i32; 4] = blend_varying_i8_m128i(a, b, mask).into();
+/// assert_eq!(c, [1, 6, 3, 8]);
+/// ```
 /// * **Intrinsic:** [`_mm_blendv_epi8`]
 /// * **Assembly:** `pblendvb xmm, xmm`
 #[must_use]
@@ -275,6 +300,29 @@ pub fn convert_to_u64_m128i_from_lower2_u8_m128i(a: m128i) -> m128i {
   m128i(unsafe { _mm_cvtepu8_epi64(a.0) })
 }
 
+/// Widens 16 `u8` values to 16 `f32` values, multiplying each by `scale`.
+///
+/// Use a `scale` of `1.0 / 255.0` to normalize pixel data into `0.0..=1.0`,
+/// or `1.0` to just convert. See [`narrow_m128_to_u8_saturating`] for the
+/// reverse.
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
+pub fn widen_u8_to_m128(bytes: [u8; 16], scale: f32) -> [m128; 4] {
+  let a = m128i::from(bytes);
+  let scale = set_splat_m128(scale);
+  let widen = |x: m128i| {
+    let i = convert_to_u32_m128i_from_lower4_u8_m128i(x);
+    mul_m128(convert_to_m128_from_i32_m128i(i), scale)
+  };
+  [
+    widen(a),
+    widen(byte_shr_imm_u128_m128i::<4>(a)),
+    widen(byte_shr_imm_u128_m128i::<8>(a)),
+    widen(byte_shr_imm_u128_m128i::<12>(a)),
+  ]
+}
+
 /// Performs a dot product of two `m128d` registers.
 ///
 /// The output details are determined by the constant:
@@ -292,6 +340,7 @@ pub fn convert_to_u64_m128i_from_lower2_u8_m128i(a: m128i) -> m128i {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 pub fn dot_product_m128d<const IMM: i32>(a: m128d, b: m128d) -> m128d {
+  let () = ImmBits::<IMM, 8>::OK;
   m128d(unsafe { _mm_dp_pd(a.0, b.0, IMM) })
 }
 
@@ -311,10 +360,16 @@ pub fn dot_product_m128d<const IMM: i32>(a: m128d, b: m128d) -> m128d {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 pub fn dot_product_m128<const IMM: i32>(a: m128, b: m128) -> m128 {
+  let () = ImmBits::<IMM, 8>::OK;
   m128(unsafe { _mm_dp_ps(a.0, b.0, IMM) })
 }
 
-/// Gets the `i32` lane requested. Only the lowest 2 bits are considered.
+/// Gets the `i32` lane requested. The lane index must be 0 through 3.
+///
+/// ```compile_fail
+/// # use safe_arch::*;
+/// let _ = extract_i32_imm_m128i::<4>(m128i::default());
+/// ```
 ///
 /// * **Intrinsic:** [`_mm_extract_epi32`]
 /// * **Assembly:** `pextrd r32, xmm, imm8`
@@ -322,11 +377,13 @@ pub fn dot_product_m128<const IMM: i32>(a: m128, b: m128) -> m128 {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 pub fn extract_i32_imm_m128i<const IMM: i32>(a: m128i) -> i32 {
+  let () = ImmBits::<IMM, 2>::OK;
   unsafe { _mm_extract_epi32(a.0, IMM) }
 }
 
-/// Gets the `i64` lane requested. Only the lowest bit is considered.
+/// Gets the `i64` lane requested. The lane index must be 0 or 1.
 ///
+/// On 32-bit x86 this goes through memory instead.
 /// ```
 /// # use safe_arch::*;
 /// let a = m128i::from([5_i64, 6]);
@@ -334,13 +391,20 @@ pub fn extract_i32_imm_m128i<const IMM: i32>(a: m128i) -> i32 {
 /// ```
 #[must_use]
 #[inline(always)]
-#[cfg(target_arch = "x86_64")]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 pub fn extract_i64_imm_m128i<const IMM: i32>(a: m128i) -> i64 {
-  unsafe { _mm_extract_epi64(a.0, IMM) }
+  let () = ImmBits::<IMM, 1>::OK;
+  #[cfg(target_arch = "x86_64")]
+  {
+    unsafe { _mm_extract_epi64(a.0, IMM) }
+  }
+  #[cfg(not(target_arch = "x86_64"))]
+  {
+    <[i64; 2]>::from(a)[IMM as usize]
+  }
 }
 
-/// Gets the `i8` lane requested. Only the lowest 4 bits are considered.
+/// Gets the `i8` lane requested. The lane index must be 0 through 15.
 ///
 /// ```
 /// # use safe_arch::*;
@@ -351,6 +415,7 @@ pub fn extract_i64_imm_m128i<const IMM: i32>(a: m128i) -> i64 {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 pub fn extract_i8_as_i32_imm_m128i<const IMM: i32>(a: m128i) -> i32 {
+  let () = ImmBits::<IMM, 4>::OK;
   unsafe { _mm_extract_epi8(a.0, IMM) }
 }
 
@@ -365,6 +430,7 @@ pub fn extract_i8_as_i32_imm_m128i<const IMM: i32>(a: m128i) -> i32 {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 pub fn extract_f32_as_i32_bits_imm_m128<const IMM: i32>(a: m128) -> i32 {
+  let () = ImmBits::<IMM, 2>::OK;
   unsafe { _mm_extract_ps(a.0, IMM) }
 }
 
@@ -438,11 +504,13 @@ pub fn floor_m128_s(a: m128, b: m128) -> m128 {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 pub fn insert_i32_imm_m128i<const IMM: i32>(a: m128i, new: i32) -> m128i {
+  let () = ImmBits::<IMM, 2>::OK;
   m128i(unsafe { _mm_insert_epi32(a.0, new, IMM) })
 }
 
 /// Inserts a new value for the `i64` lane specified.
 ///
+/// On 32-bit x86 this goes through memory instead.
 /// ```
 /// # use safe_arch::*;
 /// let a = m128i::from([5_i64, 6]);
@@ -451,13 +519,22 @@ pub fn insert_i32_imm_m128i<const IMM: i32>(a: m128i, new: i32) -> m128i {
 /// ```
 #[must_use]
 #[inline(always)]
-#[cfg(target_arch = "x86_64")]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 pub fn insert_i64_imm_m128i<const IMM: i32>(a: m128i, new: i64) -> m128i {
-  m128i(unsafe { _mm_insert_epi64(a.0, new, IMM) })
+  let () = ImmBits::<IMM, 1>::OK;
+  #[cfg(target_arch = "x86_64")]
+  {
+    m128i(unsafe { _mm_insert_epi64(a.0, new, IMM) })
+  }
+  #[cfg(not(target_arch = "x86_64"))]
+  {
+    let mut lanes = <[i64; 2]>::from(a);
+    lanes[IMM as usize] = new;
+    m128i::from(lanes)
+  }
 }
 
-/// Inserts a new value for the `i64` lane specified.
+/// Inserts a new value for the `i8` lane specified.
 ///
 /// ```
 /// # use safe_arch::*;
@@ -469,6 +546,7 @@ pub fn insert_i64_imm_m128i<const IMM: i32>(a: m128i, new: i64) -> m128i {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 pub fn insert_i8_imm_m128i<const IMM: i32>(a: m128i, new: i32) -> m128i {
+  let () = ImmBits::<IMM, 4>::OK;
   m128i(unsafe { _mm_insert_epi8(a.0, new, IMM) })
 }
 
@@ -492,6 +570,7 @@ pub fn insert_i8_imm_m128i<const IMM: i32>(a: m128i, new: i32) -> m128i {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 pub fn insert_f32_imm_m128<const IMM: i32>(a: m128, b: m128) -> m128 {
+  let () = ImmBits::<IMM, 8>::OK;
   m128(unsafe { _mm_insert_ps(a.0, b.0, IMM) })
 }
 
@@ -570,6 +649,41 @@ pub fn min_i32_m128i(a: m128i, b: m128i) -> m128i {
   m128i(unsafe { _mm_min_epi32(a.0, b.0) })
 }
 
+/// Lanewise `x` clamped to the range `lo..=hi`, as `i32` values.
+///
+/// When `lo > hi` the output is `hi`.
+/// ```
+/// # use safe_arch::*;
+/// let x = m128i::from([-100_i32, -3, 0, 3]);
+/// let c: [i32; 4] = clamp_i32_m128i(x, set_splat_i32_m128i(-3), set_splat_i32_m128i(7)).into();
+/// assert_eq!(c, [-3, -3, 0, 3]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
+pub fn clamp_i32_m128i(x: m128i, lo: m128i, hi: m128i) -> m128i {
+  min_i32_m128i(max_i32_m128i(x, lo), hi)
+}
+
+/// Lanewise `|a - b|` of `i32` values.
+///
+/// The difference can be bigger than `i32::MAX`, so the output lanes are
+/// `u32` values, as with [`i32::abs_diff`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([-2_000_000_000_i32, 2_000_000_000, -5, 9]);
+/// let b = m128i::from([2_000_000_000_i32, -2_000_000_000, 9, -5]);
+/// let c: [u32; 4] = abs_diff_i32_m128i(a, b).into();
+/// assert_eq!(c, [4_000_000_000, 4_000_000_000, 14, 14]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
+pub fn abs_diff_i32_m128i(a: m128i, b: m128i) -> m128i {
+  // The wrapping subtraction gives the right bits once read as unsigned.
+  sub_i32_m128i(max_i32_m128i(a, b), min_i32_m128i(a, b))
+}
+
 /// Lanewise `min(a, b)` with lanes as `i8`.
 /// ```
 /// # use safe_arch::*;
@@ -585,6 +699,41 @@ pub fn min_i8_m128i(a: m128i, b: m128i) -> m128i {
   m128i(unsafe { _mm_min_epi8(a.0, b.0) })
 }
 
+/// Lanewise `x` clamped to the range `lo..=hi`, as `i8` values.
+///
+/// When `lo > hi` the output is `hi`.
+/// ```
+/// # use safe_arch::*;
+/// let x = m128i::from([-100_i8, -3, 0, 3, 7, 100, -100, -3, 0, 3, 7, 100, -100, -3, 0, 3]);
+/// let c: [i8; 16] = clamp_i8_m128i(x, set_splat_i8_m128i(-3), set_splat_i8_m128i(7)).into();
+/// assert_eq!(c, [-3, -3, 0, 3, 7, 7, -3, -3, 0, 3, 7, 7, -3, -3, 0, 3]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
+pub fn clamp_i8_m128i(x: m128i, lo: m128i, hi: m128i) -> m128i {
+  min_i8_m128i(max_i8_m128i(x, lo), hi)
+}
+
+/// Lanewise `|a - b|` of `i8` values.
+///
+/// The difference can be bigger than `i8::MAX`, so the output lanes are
+/// `u8` values, as with [`i8::abs_diff`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([-128_i8, 127, -5, 9, -128, 127, -5, 9, -128, 127, -5, 9, -128, 127, -5, 9]);
+/// let b = m128i::from([127_i8, -128, 9, -5, 127, -128, 9, -5, 127, -128, 9, -5, 127, -128, 9, -5]);
+/// let c: [u8; 16] = abs_diff_i8_m128i(a, b).into();
+/// assert_eq!(c, [255, 255, 14, 14, 255, 255, 14, 14, 255, 255, 14, 14, 255, 255, 14, 14]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
+pub fn abs_diff_i8_m128i(a: m128i, b: m128i) -> m128i {
+  // The wrapping subtraction gives the right bits once read as unsigned.
+  sub_i8_m128i(max_i8_m128i(a, b), min_i8_m128i(a, b))
+}
+
 /// Lanewise `min(a, b)` with lanes as `u16`.
 /// ```
 /// # use safe_arch::*;
@@ -600,6 +749,22 @@ pub fn min_u16_m128i(a: m128i, b: m128i) -> m128i {
   m128i(unsafe { _mm_min_epu16(a.0, b.0) })
 }
 
+/// Lanewise `x` clamped to the range `lo..=hi`, as `u16` values.
+///
+/// When `lo > hi` the output is `hi`.
+/// ```
+/// # use safe_arch::*;
+/// let x = m128i::from([0_u16, 2, 3, 7, 8, 60000, 0, 2]);
+/// let c: [u16; 8] = clamp_u16_m128i(x, set_splat_i16_m128i(3), set_splat_i16_m128i(7)).into();
+/// assert_eq!(c, [3, 3, 3, 7, 7, 7, 3, 3]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
+pub fn clamp_u16_m128i(x: m128i, lo: m128i, hi: m128i) -> m128i {
+  min_u16_m128i(max_u16_m128i(x, lo), hi)
+}
+
 /// Lanewise `min(a, b)` with lanes as `u32`.
 /// ```
 /// # use safe_arch::*;
@@ -615,6 +780,37 @@ pub fn min_u32_m128i(a: m128i, b: m128i) -> m128i {
   m128i(unsafe { _mm_min_epu32(a.0, b.0) })
 }
 
+/// Lanewise `x` clamped to the range `lo..=hi`, as `u32` values.
+///
+/// When `lo > hi` the output is `hi`.
+/// ```
+/// # use safe_arch::*;
+/// let x = m128i::from([0_u32, 2, 3, 7]);
+/// let c: [u32; 4] = clamp_u32_m128i(x, set_splat_i32_m128i(3), set_splat_i32_m128i(7)).into();
+/// assert_eq!(c, [3, 3, 3, 7]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
+pub fn clamp_u32_m128i(x: m128i, lo: m128i, hi: m128i) -> m128i {
+  min_u32_m128i(max_u32_m128i(x, lo), hi)
+}
+
+/// Lanewise `|a - b|` of `u32` values.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([0_u32, 4_000_000_000, 5, 9]);
+/// let b = m128i::from([4_000_000_000_u32, 0, 9, 5]);
+/// let c: [u32; 4] = abs_diff_u32_m128i(a, b).into();
+/// assert_eq!(c, [4_000_000_000, 4_000_000_000, 4, 4]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
+pub fn abs_diff_u32_m128i(a: m128i, b: m128i) -> m128i {
+  sub_i32_m128i(max_u32_m128i(a, b), min_u32_m128i(a, b))
+}
+
 /// Min `u16` value, position, and other lanes zeroed.
 ///
 /// ```
@@ -628,6 +824,9 @@ pub fn min_u32_m128i(a: m128i, b: m128i) -> m128i {
 /// let c: [u16; 8] = min_position_u16_m128i(a).into();
 /// assert_eq!(c, [24_u16, 1, 0, 0, 0, 0, 0, 0]);
 /// ```
+/// * See [`min_position_pair_u16_m128i`] to get the two values directly.
+/// * **Intrinsic:** [`_mm_minpos_epu16`]
+/// * **Assembly:** `phminposuw xmm, xmm`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
@@ -635,6 +834,27 @@ pub fn min_position_u16_m128i(a: m128i) -> m128i {
   m128i(unsafe { _mm_minpos_epu16(a.0) })
 }
 
+/// Min `u16` value and its position, as `(value, index)`.
+///
+/// If the minimum appears more than once, the lowest index is given.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([120_u16, 24, 300, 400, 90, 129, 31, 114]);
+/// assert_eq!(min_position_pair_u16_m128i(a), (24, 1));
+///
+/// let a = m128i::from([120_u16, 24, 300, 400, 90, 129, 31, 0]);
+/// assert_eq!(min_position_pair_u16_m128i(a), (0, 7));
+/// ```
+/// * **Intrinsic:** [`_mm_minpos_epu16`]
+/// * **Assembly:** `phminposuw xmm, xmm`
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
+pub fn min_position_pair_u16_m128i(a: m128i) -> (u16, usize) {
+  let bits = get_i32_from_m128i_s(min_position_u16_m128i(a)) as u32;
+  ((bits & 0xFFFF) as u16, ((bits >> 16) & 0b111) as usize)
+}
+
 /// Computes eight `u16` "sum of absolute difference" values according to the
 /// bytes selected.
 ///
@@ -679,6 +899,7 @@ pub fn min_position_u16_m128i(a: m128i) -> m128i {
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 pub fn multi_packed_sum_abs_diff_u8_m128i<const IMM: i32>(a: m128i, b: m128i) -> m128i {
+  let () = ImmBits::<IMM, 3>::OK;
   m128i(unsafe { _mm_mpsadbw_epu8(a.0, b.0, IMM) })
 }
 
@@ -736,94 +957,206 @@ pub fn pack_i32_to_u16_m128i(a: m128i, b: m128i) -> m128i {
   m128i(unsafe { _mm_packus_epi32(a.0, b.0) })
 }
 
+/// A rounding mode for the `round` functions.
+///
+/// Use [`RoundMode::imm`] to get the immediate value, such as
+/// `round_m256::<{ RoundMode::Floor.imm() }>(a)`. Every mode also suppresses
+/// the "inexact" floating point exception.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128::from_array([-1.5, -0.5, 0.5, 2.5]);
+/// assert_eq!(round_m128::<{ RoundMode::Nearest.imm() }>(a).to_array(), [-2.0, 0.0, 0.0, 2.0]);
+/// assert_eq!(round_m128::<{ RoundMode::Floor.imm() }>(a).to_array(), [-2.0, -1.0, 0.0, 2.0]);
+/// assert_eq!(round_m128::<{ RoundMode::Ceil.imm() }>(a).to_array(), [-1.0, 0.0, 1.0, 3.0]);
+/// assert_eq!(round_m128::<{ RoundMode::TowardZero.imm() }>(a).to_array(), [-1.0, 0.0, 0.0, 2.0]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i32)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
+pub enum RoundMode {
+  /// Round to the nearest integer, with ties going to even.
+  Nearest = _MM_FROUND_NO_EXC | _MM_FROUND_TO_NEAREST_INT,
+  /// Round towards negative infinity.
+  Floor = _MM_FROUND_NO_EXC | _MM_FROUND_TO_NEG_INF,
+  /// Round towards positive infinity.
+  Ceil = _MM_FROUND_NO_EXC | _MM_FROUND_TO_POS_INF,
+  /// Round towards zero, which drops the fractional part.
+  TowardZero = _MM_FROUND_NO_EXC | _MM_FROUND_TO_ZERO,
+}
+impl RoundMode {
+  /// The immediate value for this rounding mode.
+  #[must_use]
+  #[inline(always)]
+  pub const fn imm(self) -> i32 {
+    self as i32
+  }
+}
+
 /// Rounds each lane in the style specified.
 ///
+/// `MODE` is usually a [`RoundMode`], turned into the immediate with
+/// [`RoundMode::imm`]. Other bits of the immediate can also pick the current
+/// `MXCSR` rounding mode, or allow the precision exception.
 /// ```
 /// # use safe_arch::*;
 /// let a = m128d::from_array([-0.1, 1.6]);
 /// //
-/// assert_eq!(round_m128d::<{ round_op!(Nearest) }>(a).to_array(), [0.0, 2.0]);
+/// assert_eq!(round_m128d::<{ RoundMode::Nearest.imm() }>(a).to_array(), [0.0, 2.0]);
 /// //
-/// assert_eq!(round_m128d::<{ round_op!(NegInf) }>(a).to_array(), [-1.0, 1.0]);
+/// assert_eq!(round_m128d::<{ RoundMode::Floor.imm() }>(a).to_array(), [-1.0, 1.0]);
 /// //
-/// assert_eq!(round_m128d::<{ round_op!(PosInf) }>(a).to_array(), [0.0, 2.0]);
+/// assert_eq!(round_m128d::<{ RoundMode::Ceil.imm() }>(a).to_array(), [0.0, 2.0]);
 /// //
-/// assert_eq!(round_m128d::<{ round_op!(Zero) }>(a).to_array(), [0.0, 1.0]);
+/// assert_eq!(round_m128d::<{ RoundMode::TowardZero.imm() }>(a).to_array(), [0.0, 1.0]);
 /// ```
+/// * **Intrinsic:** [`_mm_round_pd`]
+/// * **Assembly:** `roundpd xmm, xmm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 pub fn round_m128d<const MODE: i32>(a: m128d) -> m128d {
+  let () = ImmBits::<MODE, 4>::OK;
   m128d(unsafe { _mm_round_pd(a.0, MODE) })
 }
 
-/// Rounds `$b` low as specified, keeps `$a` high.
+/// Rounds the low lane of `b` in the style specified, and keeps the high lane
+/// of `a`.
 ///
+/// `MODE` is usually a [`RoundMode`], as with [`round_m128d`].
 /// ```
 /// # use safe_arch::*;
 /// let a = m128d::from_array([f64::NAN, 900.0]);
 /// //
 /// let b = m128d::from_array([-0.1, f64::NAN]);
 /// //
-/// assert_eq!(round_m128d_s::<{ round_op!(Nearest) }>(a, b).to_array(), [0.0, 900.0]);
-/// assert_eq!(round_m128d_s::<{ round_op!(NegInf) }>(a, b).to_array(), [-1.0, 900.0]);
+/// assert_eq!(round_m128d_s::<{ RoundMode::Nearest.imm() }>(a, b).to_array(), [0.0, 900.0]);
+/// assert_eq!(round_m128d_s::<{ RoundMode::Floor.imm() }>(a, b).to_array(), [-1.0, 900.0]);
 /// //
 /// let b = m128d::from_array([2.4, f64::NAN]);
 /// //
-/// assert_eq!(round_m128d_s::<{ round_op!(PosInf) }>(a, b).to_array(), [3.0, 900.0]);
-/// assert_eq!(round_m128d_s::<{ round_op!(Zero) }>(a, b).to_array(), [2.0, 900.0]);
+/// assert_eq!(round_m128d_s::<{ RoundMode::Ceil.imm() }>(a, b).to_array(), [3.0, 900.0]);
+/// assert_eq!(round_m128d_s::<{ RoundMode::TowardZero.imm() }>(a, b).to_array(), [2.0, 900.0]);
 /// ```
+/// * **Intrinsic:** [`_mm_round_sd`]
+/// * **Assembly:** `roundsd xmm, xmm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 pub fn round_m128d_s<const MODE: i32>(a: m128d, b: m128d) -> m128d {
+  let () = ImmBits::<MODE, 4>::OK;
   m128d(unsafe { _mm_round_sd(a.0, b.0, MODE) })
 }
 
 /// Rounds each lane in the style specified.
 ///
+/// `MODE` is usually a [`RoundMode`], turned into the immediate with
+/// [`RoundMode::imm`]. Other bits of the immediate can also pick the current
+/// `MXCSR` rounding mode, or allow the precision exception.
 /// ```
 /// # use safe_arch::*;
 /// let a = m128::from_array([-0.1, 1.6, 3.3, 4.5]);
 /// //
-/// assert_eq!(round_m128::<{ round_op!(Nearest) }>(a).to_array(), [0.0, 2.0, 3.0, 4.0]);
+/// assert_eq!(round_m128::<{ RoundMode::Nearest.imm() }>(a).to_array(), [0.0, 2.0, 3.0, 4.0]);
 /// //
-/// assert_eq!(round_m128::<{ round_op!(NegInf) }>(a).to_array(), [-1.0, 1.0, 3.0, 4.0]);
+/// assert_eq!(round_m128::<{ RoundMode::Floor.imm() }>(a).to_array(), [-1.0, 1.0, 3.0, 4.0]);
 /// //
-/// assert_eq!(round_m128::<{ round_op!(PosInf) }>(a).to_array(), [0.0, 2.0, 4.0, 5.0]);
+/// assert_eq!(round_m128::<{ RoundMode::Ceil.imm() }>(a).to_array(), [0.0, 2.0, 4.0, 5.0]);
 /// //
-/// assert_eq!(round_m128::<{ round_op!(Zero) }>(a).to_array(), [0.0, 1.0, 3.0, 4.0]);
+/// assert_eq!(round_m128::<{ RoundMode::TowardZero.imm() }>(a).to_array(), [0.0, 1.0, 3.0, 4.0]);
 /// ```
+/// * **Intrinsic:** [`_mm_round_ps`]
+/// * **Assembly:** `roundps xmm, xmm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 pub fn round_m128<const MODE: i32>(a: m128) -> m128 {
+  let () = ImmBits::<MODE, 4>::OK;
   m128(unsafe { _mm_round_ps(a.0, MODE) })
 }
 
-/// Rounds `$b` low as specified, other lanes use `$a`.
+/// Rounds the low lane of `b` in the style specified, and keeps the other
+/// lanes of `a`.
 ///
+/// `MODE` is usually a [`RoundMode`], as with [`round_m128`].
 /// ```
 /// # use safe_arch::*;
 /// let a = m128::from_array([f32::NAN, 6.0, 7.0, 8.0]);
 /// //
 /// let b = m128::from_array([-0.1, f32::NAN, f32::NAN, f32::NAN]);
 /// //
-/// assert_eq!(round_m128_s::<{ round_op!(Nearest) }>(a, b).to_array(), [0.0, 6.0, 7.0, 8.0]);
-/// assert_eq!(round_m128_s::<{ round_op!(NegInf) }>(a, b).to_array(), [-1.0, 6.0, 7.0, 8.0]);
+/// assert_eq!(round_m128_s::<{ RoundMode::Nearest.imm() }>(a, b).to_array(), [0.0, 6.0, 7.0, 8.0]);
+/// assert_eq!(round_m128_s::<{ RoundMode::Floor.imm() }>(a, b).to_array(), [-1.0, 6.0, 7.0, 8.0]);
 /// //
 /// let b = m128::from_array([2.4, f32::NAN, f32::NAN, f32::NAN]);
 /// //
-/// assert_eq!(round_m128_s::<{ round_op!(PosInf) }>(a, b).to_array(), [3.0, 6.0, 7.0, 8.0]);
-/// assert_eq!(round_m128_s::<{ round_op!(Zero) }>(a, b).to_array(), [2.0, 6.0, 7.0, 8.0]);
+/// assert_eq!(round_m128_s::<{ RoundMode::Ceil.imm() }>(a, b).to_array(), [3.0, 6.0, 7.0, 8.0]);
+/// assert_eq!(round_m128_s::<{ RoundMode::TowardZero.imm() }>(a, b).to_array(), [2.0, 6.0, 7.0, 8.0]);
 /// ```
+/// * **Intrinsic:** [`_mm_round_ss`]
+/// * **Assembly:** `roundss xmm, xmm, imm8`
 #[must_use]
 #[inline(always)]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
 pub fn round_m128_s<const MODE: i32>(a: m128, b: m128) -> m128 {
+  let () = ImmBits::<MODE, 4>::OK;
   m128(unsafe { _mm_round_ss(a.0, b.0, MODE) })
 }
 
+/// Lets you do non-temporal ("streaming") loads, with fences on both sides.
+///
+/// On normal memory a streaming load is just a load. On write-combining
+/// memory (such as a mapped GPU buffer) it can read a whole line at once, which
+/// is much faster, but those reads are weakly ordered against other memory
+/// operations. So [`streaming_loads`] does an `mfence` before the closure runs
+/// (so earlier stores are seen) and this runs an `lfence` when it drops (so
+/// later loads can't pass the streaming ones).
+#[derive(Debug)]
+pub struct StreamingLoads<'a> {
+  _borrows: core::marker::PhantomData<&'a ()>,
+}
+impl Drop for StreamingLoads<'_> {
+  #[inline(always)]
+  fn drop(&mut self) {
+    unsafe { _mm_lfence() }
+  }
+}
+impl<'a> StreamingLoads<'a> {
+  /// Loads the value from the reference given, as a streaming load.
+  /// ```
+  /// # use safe_arch::*;
+  /// let r = set_splat_i32_m128i(7);
+  /// let a = streaming_loads(|l| l.load_nontemporal_m128i(&r));
+  /// assert_eq!(<[i32; 4]>::from(a), [7; 4]);
+  /// ```
+  /// * **Intrinsic:** [`_mm_stream_load_si128`]
+  /// * **Assembly:** `movntdqa xmm, m128`
+  #[must_use]
+  #[inline(always)]
+  #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
+  pub fn load_nontemporal_m128i(&self, r: &'a m128i) -> m128i {
+    m128i(unsafe { _mm_stream_load_si128(r as *const m128i as *const __m128i) })
+  }
+}
+
+/// Runs the closure with a [`StreamingLoads`], fencing before and after.
+///
+/// The closing fence also happens if the closure panics.
+/// ```
+/// # use safe_arch::*;
+/// let buf = [set_splat_i32_m128i(1), set_splat_i32_m128i(2)];
+/// let sum = streaming_loads(|l| {
+///   buf.iter().fold(zeroed_m128i(), |acc, r| add_i32_m128i(acc, l.load_nontemporal_m128i(r)))
+/// });
+/// assert_eq!(<[i32; 4]>::from(sum), [3; 4]);
+/// ```
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.1")))]
+pub fn streaming_loads<'a, R>(f: impl FnOnce(&StreamingLoads<'a>) -> R) -> R {
+  unsafe { _mm_mfence() };
+  let l = StreamingLoads { _borrows: core::marker::PhantomData };
+  f(&l)
+}
+
 /// Computes the bitwise AND of 256 bits in `a` and
 /// `b`, returns 1 if the result is zero, otherwise 0.
 /// * **Intrinsic:** [`_mm_testz_si128`]
@@ -52,14 +52,28 @@ pub fn crc32_u32(crc: u32, v: u32) -> u32 {
 ///
 /// **Note:** Has a different return type from the other crc32 functions.
 ///
+/// On 32-bit x86 this is done as two [`crc32_u32`] steps, low half first.
+/// ```
+/// # use safe_arch::*;
+/// let v = 0x0123_4567_89AB_CDEF_u64;
+/// let expected = crc32_u32(crc32_u32(!0, v as u32), (v >> 32) as u32);
+/// assert_eq!(crc32_u64(u64::from(!0_u32), v), u64::from(expected));
+/// ```
 /// * **Intrinsic:** [`_mm_crc32_u64`]
 /// * **Assembly:** `crc32 r64, r64`
 #[must_use]
 #[inline(always)]
-#[cfg(target_arch = "x86_64")]
 #[cfg_attr(docsrs, doc(cfg(target_feature = "sse4.2")))]
 pub fn crc32_u64(crc: u64, v: u64) -> u64 {
-  unsafe { _mm_crc32_u64(crc, v) }
+  #[cfg(target_arch = "x86_64")]
+  {
+    unsafe { _mm_crc32_u64(crc, v) }
+  }
+  #[cfg(not(target_arch = "x86_64"))]
+  {
+    let crc = crc32_u32(crc as u32, v as u32);
+    u64::from(crc32_u32(crc, (v >> 32) as u32))
+  }
 }
 
 /// string segment elements are u8 values
@@ -168,6 +168,442 @@ pub fn shuffle_av_i8z_all_m128i(a: m128i, v: m128i) -> m128i {
   m128i(unsafe { _mm_shuffle_epi8(a.0, v.0) })
 }
 
+/// Builds the table register for [`lookup_nibbles_m128i`].
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "ssse3")))]
+pub fn nibble_table_m128i(table: [u8; 16]) -> m128i {
+  m128i::from(table)
+}
+
+/// Looks up each `u8` lane of `indices` in a 16 entry table.
+///
+/// This is [`shuffle_av_i8z_all_m128i`] under a name that says what it's for.
+/// For each lane:
+/// * If the high bit of the index is set, the output is 0.
+/// * Otherwise the low 4 bits pick the table entry, and bits 4 to 6 are
+///   ignored.
+///
+/// So to look up the high nibble of each byte, shift it down and mask it with
+/// `0x0F` first.
+/// ```
+/// # use safe_arch::*;
+/// let hex = nibble_table_m128i(*b"0123456789abcdef");
+/// let bytes = m128i::from([0x1F_u8, 0xA2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 0]);
+/// let low = bitand_m128i(bytes, set_splat_i8_m128i(0x0F));
+/// let c: [u8; 16] = lookup_nibbles_m128i(hex, low).into();
+/// assert_eq!(&c, b"f23456789abcdef0");
+/// // A set high bit zeroes the lane instead.
+/// let c: [u8; 16] = lookup_nibbles_m128i(hex, set_splat_i8_m128i(-1)).into();
+/// assert_eq!(c, [0; 16]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "ssse3")))]
+pub fn lookup_nibbles_m128i(table: m128i, indices: m128i) -> m128i {
+  shuffle_av_i8z_all_m128i(table, indices)
+}
+
+/// Counts the set bits in each `u8` lane.
+///
+/// This looks up the count for each nibble with [`lookup_nibbles_m128i`] and
+/// adds the two halves.
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([0_u8, 1, 2, 3, 4, 7, 8, 15, 16, 31, 127, 128, 170, 254, 255, 0x0F]);
+/// let c: [u8; 16] = population_count_u8_lanes_m128i(a).into();
+/// assert_eq!(c, [0, 1, 1, 2, 1, 3, 1, 4, 1, 5, 7, 1, 4, 7, 8, 4]);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "ssse3")))]
+pub fn population_count_u8_lanes_m128i(a: m128i) -> m128i {
+  let table = nibble_table_m128i([0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4]);
+  let low_mask = set_splat_i8_m128i(0x0F);
+  let low = bitand_m128i(a, low_mask);
+  let high = bitand_m128i(shr_imm_u16_m128i::<4>(a), low_mask);
+  add_i8_m128i(lookup_nibbles_m128i(table, low), lookup_nibbles_m128i(table, high))
+}
+
+/// Counts the set bits in each `u64` lane.
+///
+/// This is [`population_count_u8_lanes_m128i`] with each group of eight byte
+/// counts summed by [`sum_of_u8_abs_diff_m128i`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([u64::MAX, 0x0F0F_0000_0000_0001]);
+/// let c: [u64; 2] = population_count_u64_lanes_m128i(a).into();
+/// assert_eq!(c, [64, 9]);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "ssse3")))]
+pub fn population_count_u64_lanes_m128i(a: m128i) -> m128i {
+  sum_of_u8_abs_diff_m128i(population_count_u8_lanes_m128i(a), zeroed_m128i())
+}
+
+/// Swaps the bytes within each `i16` lane.
+///
+/// This converts each lane between little-endian and big-endian (network)
+/// order, the same as [`i16::swap_bytes`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([0x0102_i16, 0x0304, 0x0506, 0x0708, 0x090A, 0x0B0C, 0x0D0E, 0x0F10]);
+/// let c: [i16; 8] = byte_swap_i16_m128i(a).into();
+/// assert_eq!(c, [0x0201, 0x0403, 0x0605, 0x0807, 0x0A09, 0x0C0B, 0x0E0D, 0x100F]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "ssse3")))]
+pub fn byte_swap_i16_m128i(a: m128i) -> m128i {
+  let v = m128i::from([1_u8, 0, 3, 2, 5, 4, 7, 6, 9, 8, 11, 10, 13, 12, 15, 14]);
+  shuffle_av_i8z_all_m128i(a, v)
+}
+
+/// Swaps the bytes within each `i32` lane.
+///
+/// This converts each lane between little-endian and big-endian (network)
+/// order, the same as [`byte_swap_i32`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([0x0A123456, 1, -1, 0x01020304]);
+/// let c: [i32; 4] = byte_swap_i32_m128i(a).into();
+/// assert_eq!(c, [0x5634120A, 0x01000000, -1, 0x04030201]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "ssse3")))]
+pub fn byte_swap_i32_m128i(a: m128i) -> m128i {
+  let v = m128i::from([3_u8, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12]);
+  shuffle_av_i8z_all_m128i(a, v)
+}
+
+/// Swaps the bytes within each `i64` lane.
+///
+/// This converts each lane between little-endian and big-endian (network)
+/// order, the same as [`i64::swap_bytes`].
+/// ```
+/// # use safe_arch::*;
+/// let a = m128i::from([0x0A123456_789ABC01_i64, 1]);
+/// let c: [i64; 2] = byte_swap_i64_m128i(a).into();
+/// assert_eq!(c, [0x01BC9A78_5634120A, 0x01000000_00000000]);
+/// ```
+#[must_use]
+#[inline(always)]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "ssse3")))]
+pub fn byte_swap_i64_m128i(a: m128i) -> m128i {
+  let v = m128i::from([7_u8, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8]);
+  shuffle_av_i8z_all_m128i(a, v)
+}
+
+/// The control for [`shuffle_av_i8z_all_m128i`] that gathers one channel of 3
+/// channel bytes out of one of the 3 input registers, zeroing the other lanes.
+pub(crate) const fn deinterleave_3_u8_control(channel: usize, register: usize) -> [u8; 16] {
+  let mut out = [0x80; 16];
+  let mut i = 0;
+  while i < 16 {
+    let src = 3 * i + channel;
+    if src >= 16 * register && src < 16 * (register + 1) {
+      out[i] = (src - 16 * register) as u8;
+    }
+    i += 1;
+  }
+  out
+}
+
+/// The control for [`shuffle_av_i8z_all_m128i`] that puts one channel of 3
+/// channel bytes into its lanes of one of the 3 output registers, zeroing the
+/// other lanes.
+pub(crate) const fn interleave_3_u8_control(channel: usize, register: usize) -> [u8; 16] {
+  let mut out = [0x80; 16];
+  let mut i = 0;
+  while i < 16 {
+    let dst = 16 * register + i;
+    if dst % 3 == channel {
+      out[i] = (dst / 3) as u8;
+    }
+    i += 1;
+  }
+  out
+}
+
+/// The control for [`shuffle_av_i8z_all_m128i`] that turns 4 pixels of 4 bytes
+/// into 4 bytes of each channel, and back again.
+pub(crate) const TRANSPOSE_4X4_U8: [u8; 16] = [0, 4, 8, 12, 1, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15];
+
+/// Splits 16 pixels of 3 `u8` channels each (such as RGB) into one register
+/// per channel.
+///
+/// The inputs are the 48 bytes as laid out in memory, and the outputs are the
+/// 16 bytes of each channel in order. [`interleave_3_u8_m128i`] goes the other
+/// way.
+/// ```
+/// # use safe_arch::*;
+/// let mut rgb = [[0_u8; 16]; 3];
+/// for i in 0..48 {
+///   rgb[i / 16][i % 16] = ((i % 3) * 100 + i / 3) as u8;
+/// }
+/// let [r, g, b] = deinterleave_3_u8_m128i(rgb.map(m128i::from));
+/// assert_eq!(<[u8; 16]>::from(r), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+/// assert_eq!(<[u8; 16]>::from(g)[..4], [100, 101, 102, 103]);
+/// assert_eq!(<[u8; 16]>::from(b)[12..], [212, 213, 214, 215]);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "ssse3")))]
+pub fn deinterleave_3_u8_m128i(aos: [m128i; 3]) -> [m128i; 3] {
+  let gather = |channel: usize| {
+    let mut out = zeroed_m128i();
+    for (register, a) in aos.iter().enumerate() {
+      let v = m128i::from(deinterleave_3_u8_control(channel, register));
+      out = bitor_m128i(out, shuffle_av_i8z_all_m128i(*a, v));
+    }
+    out
+  };
+  [gather(0), gather(1), gather(2)]
+}
+
+/// Merges one register per channel into 16 pixels of 3 `u8` channels each.
+///
+/// This undoes [`deinterleave_3_u8_m128i`].
+/// ```
+/// # use safe_arch::*;
+/// let r = m128i::from([0_u8, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+/// let g = bitor_m128i(r, set_splat_i8_m128i(0x40));
+/// let b = bitor_m128i(r, set_splat_i8_m128i(0x80_u8 as i8));
+/// let [x, y, z] = interleave_3_u8_m128i([r, g, b]);
+/// assert_eq!(<[u8; 16]>::from(x)[..7], [0, 0x40, 0x80, 1, 0x41, 0x81, 2]);
+/// assert_eq!(<[u8; 16]>::from(z)[13..], [0x0F, 0x4F, 0x8F]);
+/// assert_eq!(deinterleave_3_u8_m128i([x, y, z]).map(<[u8; 16]>::from), [r, g, b].map(<[u8; 16]>::from));
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "ssse3")))]
+pub fn interleave_3_u8_m128i(soa: [m128i; 3]) -> [m128i; 3] {
+  let scatter = |register: usize| {
+    let mut out = zeroed_m128i();
+    for (channel, a) in soa.iter().enumerate() {
+      let v = m128i::from(interleave_3_u8_control(channel, register));
+      out = bitor_m128i(out, shuffle_av_i8z_all_m128i(*a, v));
+    }
+    out
+  };
+  [scatter(0), scatter(1), scatter(2)]
+}
+
+/// Splits 16 pixels of 4 `u8` channels each (such as RGBA) into one register
+/// per channel.
+///
+/// The inputs are the 64 bytes as laid out in memory, and the outputs are the
+/// 16 bytes of each channel in order. [`interleave_4_u8_m128i`] goes the other
+/// way.
+/// ```
+/// # use safe_arch::*;
+/// let mut rgba = [[0_u8; 16]; 4];
+/// for i in 0..64 {
+///   rgba[i / 16][i % 16] = ((i % 4) * 64 + i / 4) as u8;
+/// }
+/// let soa = deinterleave_4_u8_m128i(rgba.map(m128i::from));
+/// for (channel, s) in soa.iter().enumerate() {
+///   let s: [u8; 16] = (*s).into();
+///   assert!(s.iter().enumerate().all(|(i, b)| usize::from(*b) == channel * 64 + i));
+/// }
+/// assert_eq!(interleave_4_u8_m128i(soa).map(<[u8; 16]>::from), rgba);
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "ssse3")))]
+pub fn deinterleave_4_u8_m128i(aos: [m128i; 4]) -> [m128i; 4] {
+  let v = m128i::from(TRANSPOSE_4X4_U8);
+  let [a, b, c, d] = aos.map(|a| shuffle_av_i8z_all_m128i(a, v));
+  transpose_4x4_i32_m128i(a, b, c, d)
+}
+
+/// Merges one register per channel into 16 pixels of 4 `u8` channels each.
+///
+/// This undoes [`deinterleave_4_u8_m128i`].
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "ssse3")))]
+pub fn interleave_4_u8_m128i(soa: [m128i; 4]) -> [m128i; 4] {
+  let v = m128i::from(TRANSPOSE_4X4_U8);
+  let [r, g, b, a] = soa;
+  transpose_4x4_i32_m128i(r, g, b, a).map(|x| shuffle_av_i8z_all_m128i(x, v))
+}
+
+/// Transposes four registers of `i32` lanes as a 4x4 matrix.
+#[inline(always)]
+fn transpose_4x4_i32_m128i(a: m128i, b: m128i, c: m128i, d: m128i) -> [m128i; 4] {
+  let t0 = unpack_low_i32_m128i(a, b);
+  let t1 = unpack_high_i32_m128i(a, b);
+  let t2 = unpack_low_i32_m128i(c, d);
+  let t3 = unpack_high_i32_m128i(c, d);
+  [
+    unpack_low_i64_m128i(t0, t2),
+    unpack_high_i64_m128i(t0, t2),
+    unpack_low_i64_m128i(t1, t3),
+    unpack_high_i64_m128i(t1, t3),
+  ]
+}
+
+/// Counts the set bits in a byte slice.
+///
+/// This sums [`population_count_u64_lanes_m128i`] over the slice. When `avx2`
+/// is enabled it does the same 32 bytes at a time.
+/// ```
+/// # use safe_arch::*;
+/// let mut bytes = [0_u8; 75];
+/// for (i, b) in bytes.iter_mut().enumerate() {
+///   *b = (i * 37) as u8;
+/// }
+/// for n in 0..bytes.len() {
+///   let expected: u32 = bytes[..n].iter().map(|b| b.count_ones()).sum();
+///   assert_eq!(count_ones_in_slice(&bytes[..n]), u64::from(expected));
+/// }
+/// ```
+#[must_use]
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "ssse3")))]
+pub fn count_ones_in_slice(bytes: &[u8]) -> u64 {
+  #[cfg(all(target_feature = "avx2", feature = "avx2"))]
+  let (start, total) = count_ones_m256i(bytes);
+  #[cfg(not(all(target_feature = "avx2", feature = "avx2")))]
+  let (start, total) = (0, 0);
+  let mut sums = zeroed_m128i();
+  let mut chunks = bytes[start..].chunks_exact(16);
+  for chunk in &mut chunks {
+    let x = load_unaligned_m128i(core::convert::TryInto::try_into(chunk).unwrap());
+    sums = add_i64_m128i(sums, population_count_u64_lanes_m128i(x));
+  }
+  let [s0, s1]: [u64; 2] = sums.into();
+  let tail: u32 = chunks.remainder().iter().map(|b| b.count_ones()).sum();
+  total + s0 + s1 + u64::from(tail)
+}
+
+/// Writes the lowercase hex digits of `bytes` into `out`, two per byte.
+///
+/// This looks up each nibble with [`lookup_nibbles_m128i`]. When `avx2` is
+/// enabled it does 32 bytes at a time, and otherwise 16.
+///
+/// Panics if `out` isn't exactly twice as long as `bytes`.
+/// ```
+/// # use safe_arch::*;
+/// let bytes: Vec<u8> = (0..75_u32).map(|i| (i * 7) as u8).collect();
+/// let mut out = vec![0_u8; 150];
+/// hex_encode(&bytes, &mut out);
+/// let expected: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+/// assert_eq!(out, expected.as_bytes());
+/// ```
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "ssse3")))]
+pub fn hex_encode(bytes: &[u8], out: &mut [u8]) {
+  assert_eq!(out.len(), bytes.len() * 2, "hex_encode: `out` must be twice the length of `bytes`");
+  #[cfg(all(target_feature = "avx2", feature = "avx2"))]
+  let start = hex_encode_m256i(bytes, out);
+  #[cfg(not(all(target_feature = "avx2", feature = "avx2")))]
+  let start = 0;
+  let table = nibble_table_m128i(HEX_DIGITS);
+  let low_nibble = set_splat_i8_m128i(0x0F);
+  let mut chunks = bytes[start..].chunks_exact(16);
+  let mut out_chunks = out[start * 2..].chunks_exact_mut(32);
+  for (chunk, out) in (&mut chunks).zip(&mut out_chunks) {
+    let x = load_unaligned_m128i(core::convert::TryInto::try_into(chunk).unwrap());
+    let high = lookup_nibbles_m128i(table, bitand_m128i(shr_imm_u16_m128i::<4>(x), low_nibble));
+    let low = lookup_nibbles_m128i(table, bitand_m128i(x, low_nibble));
+    let (out_a, out_b) = out.split_at_mut(16);
+    store_unaligned_m128i(core::convert::TryInto::try_into(out_a).unwrap(), unpack_low_i8_m128i(high, low));
+    store_unaligned_m128i(core::convert::TryInto::try_into(out_b).unwrap(), unpack_high_i8_m128i(high, low));
+  }
+  for (b, out) in chunks.remainder().iter().zip(out_chunks.into_remainder().chunks_exact_mut(2)) {
+    out[0] = HEX_DIGITS[usize::from(b >> 4)];
+    out[1] = HEX_DIGITS[usize::from(b & 0x0F)];
+  }
+}
+
+/// Reads the hex digits of `hex` into `out`, two digits per byte.
+///
+/// Both uppercase and lowercase digits are accepted. If there's a byte in
+/// `hex` that isn't a hex digit, this gives `Err` with its index, and `out`
+/// might be partly written. When `avx2` is enabled this does 64 digits at a
+/// time, and otherwise 32.
+///
+/// Panics if `hex` isn't exactly twice as long as `out`.
+/// ```
+/// # use safe_arch::*;
+/// let hex = b"00112233445566778899aabbccddeeffFFEEDDCCBBAA99887766554433221100c0ffee";
+/// let mut out = [0_u8; 35];
+/// assert_eq!(hex_decode(hex, &mut out), Ok(()));
+/// assert_eq!(&out[..4], &[0x00, 0x11, 0x22, 0x33]);
+/// assert_eq!(&out[16..20], &[0xFF, 0xEE, 0xDD, 0xCC]);
+/// assert_eq!(&out[32..], &[0xC0, 0xFF, 0xEE]);
+/// //
+/// let mut bad = *hex;
+/// bad[40] = b'g';
+/// assert_eq!(hex_decode(&bad, &mut out), Err(40));
+/// bad[69] = b' ';
+/// assert_eq!(hex_decode(&bad[50..], &mut out[25..]), Err(19));
+/// ```
+#[inline]
+#[cfg_attr(docsrs, doc(cfg(target_feature = "ssse3")))]
+pub fn hex_decode(hex: &[u8], out: &mut [u8]) -> Result<(), usize> {
+  assert_eq!(hex.len(), out.len() * 2, "hex_decode: `hex` must be twice the length of `out`");
+  #[cfg(all(target_feature = "avx2", feature = "avx2"))]
+  let start = hex_decode_m256i(hex, out)?;
+  #[cfg(not(all(target_feature = "avx2", feature = "avx2")))]
+  let start = 0;
+  // Each digit gets its value, and a lane mask of if it really was a digit.
+  let values = |x: m128i| -> (m128i, i32) {
+    let is_digit = bitand_m128i(
+      cmp_gt_mask_i8_m128i(x, set_splat_i8_m128i(b'0' as i8 - 1)),
+      cmp_lt_mask_i8_m128i(x, set_splat_i8_m128i(b'9' as i8 + 1)),
+    );
+    let lower = bitor_m128i(x, set_splat_i8_m128i(0x20));
+    let is_letter = bitand_m128i(
+      cmp_gt_mask_i8_m128i(lower, set_splat_i8_m128i(b'a' as i8 - 1)),
+      cmp_lt_mask_i8_m128i(lower, set_splat_i8_m128i(b'f' as i8 + 1)),
+    );
+    let digit = bitand_m128i(is_digit, sub_i8_m128i(x, set_splat_i8_m128i(b'0' as i8)));
+    let letter = bitand_m128i(is_letter, sub_i8_m128i(lower, set_splat_i8_m128i(b'a' as i8 - 10)));
+    (bitor_m128i(digit, letter), move_mask_i8_m128i(bitor_m128i(is_digit, is_letter)))
+  };
+  // `high * 16 + low` for each pair of digits.
+  let weights = set_splat_i16_m128i(0x01_10);
+  let mut chunks = hex[start * 2..].chunks_exact(32);
+  let mut out_chunks = out[start..].chunks_exact_mut(16);
+  for (i, (chunk, out)) in (&mut chunks).zip(&mut out_chunks).enumerate() {
+    let (a, b) = chunk.split_at(16);
+    let (a, a_ok) = values(load_unaligned_m128i(core::convert::TryInto::try_into(a).unwrap()));
+    let (b, b_ok) = values(load_unaligned_m128i(core::convert::TryInto::try_into(b).unwrap()));
+    let bad = !(a_ok | (b_ok << 16));
+    if bad != 0 {
+      return Err(start * 2 + i * 32 + bad.trailing_zeros() as usize);
+    }
+    let a = mul_u8i8_add_horizontal_saturating_m128i(a, weights);
+    let b = mul_u8i8_add_horizontal_saturating_m128i(b, weights);
+    store_unaligned_m128i(core::convert::TryInto::try_into(out).unwrap(), pack_i16_to_u8_m128i(a, b));
+  }
+  let tail_start = hex.len() - chunks.remainder().len();
+  for (i, (pair, out)) in chunks.remainder().chunks_exact(2).zip(out_chunks.into_remainder()).enumerate() {
+    let at = tail_start + i * 2;
+    let high = hex_digit_value(pair[0]).ok_or(at)?;
+    let low = hex_digit_value(pair[1]).ok_or(at + 1)?;
+    *out = (high << 4) | low;
+  }
+  Ok(())
+}
+
+const HEX_DIGITS: [u8; 16] = *b"0123456789abcdef";
+
+#[inline(always)]
+fn hex_digit_value(c: u8) -> Option<u8> {
+  match c {
+    b'0'..=b'9' => Some(c - b'0'),
+    b'a'..=b'f' => Some(c - b'a' + 10),
+    b'A'..=b'F' => Some(c - b'A' + 10),
+    _ => None,
+  }
+}
+
 /// Applies the sign of `i8` values in `b` to the values in `a`.
 ///
 /// * If `b` is negative: the `a` value is negated.
@@ -14,3 +14,23 @@ fn test_add_carry_u64() {
   assert_eq!(add_carry_u64(1, u64::MAX, 5, &mut out), 1);
   assert_eq!(out, 5);
 }
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+fn test_add_carry_u64_slices() {
+  // Check against u128 math on two words.
+  let cases = [(0_u128, 0_u128), (u128::MAX, 1), (u64::MAX as u128, u64::MAX as u128), (u128::MAX, u128::MAX)];
+  for &(x, y) in cases.iter() {
+    let mut acc = [x as u64, (x >> 64) as u64];
+    let c = add_carry_u64_slices(&mut acc, &[y as u64, (y >> 64) as u64]);
+    let (sum, overflow) = x.overflowing_add(y);
+    assert_eq!(acc, [sum as u64, (sum >> 64) as u64]);
+    assert_eq!(c, overflow as u8);
+  }
+  let mut acc = [u64::MAX; 4];
+  assert_eq!(add_carry_u64_slices(&mut acc, &[1]), 1);
+  assert_eq!(acc, [0; 4]);
+  let mut acc = [5_u64];
+  assert_eq!(add_carry_u64_slices(&mut acc, &[]), 0);
+  assert_eq!(acc, [5]);
+}
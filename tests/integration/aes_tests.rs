@@ -0,0 +1,80 @@
+use super::*;
+
+fn bytes(k: m128i) -> [u8; 16] {
+  k.into()
+}
+
+#[test]
+fn test_aes_key_expand_128() {
+  // FIPS-197, Appendix A.1
+  let k = aes_key_expand_128([0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c]);
+  assert_eq!(bytes(k[1]), [0xa0, 0xfa, 0xfe, 0x17, 0x88, 0x54, 0x2c, 0xb1, 0x23, 0xa3, 0x39, 0x39, 0x2a, 0x6c, 0x76, 0x05]);
+  assert_eq!(bytes(k[10]), [0xd0, 0x14, 0xf9, 0xa8, 0xc9, 0xee, 0x25, 0x89, 0xe1, 0x3f, 0x0c, 0xc8, 0xb6, 0x63, 0x0c, 0xa6]);
+}
+
+#[test]
+fn test_aes_key_expand_192() {
+  // FIPS-197, Appendix A.2
+  let k = aes_key_expand_192([0x8e, 0x73, 0xb0, 0xf7, 0xda, 0x0e, 0x64, 0x52, 0xc8, 0x10, 0xf3, 0x2b, 0x80, 0x90, 0x79, 0xe5, 0x62, 0xf8, 0xea, 0xd2, 0x52, 0x2c, 0x6b, 0x7b]);
+  assert_eq!(bytes(k[1]), [0x62, 0xf8, 0xea, 0xd2, 0x52, 0x2c, 0x6b, 0x7b, 0xfe, 0x0c, 0x91, 0xf7, 0x24, 0x02, 0xf5, 0xa5]);
+  assert_eq!(bytes(k[12]), [0xe9, 0x8b, 0xa0, 0x6f, 0x44, 0x8c, 0x77, 0x3c, 0x8e, 0xcc, 0x72, 0x04, 0x01, 0x00, 0x22, 0x02]);
+}
+
+#[test]
+fn test_aes_key_expand_256() {
+  // FIPS-197, Appendix A.3
+  let k = aes_key_expand_256([0x60, 0x3d, 0xeb, 0x10, 0x15, 0xca, 0x71, 0xbe, 0x2b, 0x73, 0xae, 0xf0, 0x85, 0x7d, 0x77, 0x81, 0x1f, 0x35, 0x2c, 0x07, 0x3b, 0x61, 0x08, 0xd7, 0x2d, 0x98, 0x10, 0xa3, 0x09, 0x14, 0xdf, 0xf4]);
+  assert_eq!(bytes(k[2]), [0x9b, 0xa3, 0x54, 0x11, 0x8e, 0x69, 0x25, 0xaf, 0xa5, 0x1a, 0x8b, 0x5f, 0x20, 0x67, 0xfc, 0xde]);
+  assert_eq!(bytes(k[3]), [0xa8, 0xb0, 0x9c, 0x1a, 0x93, 0xd1, 0x94, 0xcd, 0xbe, 0x49, 0x84, 0x6e, 0xb7, 0x5d, 0x5b, 0x9a]);
+  assert_eq!(bytes(k[14]), [0xfe, 0x48, 0x90, 0xd1, 0xe6, 0x18, 0x8d, 0x0b, 0x04, 0x6d, 0xf3, 0x44, 0x70, 0x6c, 0x63, 0x1e]);
+}
+
+#[test]
+fn test_aes_inverse_round_keys() {
+  fn round_trip(enc: &[m128i], dec: &[m128i]) {
+    let plain = m128i::from(0x0011_2233_4455_6677_8899_AABB_CCDD_EEFF_u128);
+    let mut blocks = [plain];
+    aes_ecb_encrypt_blocks(enc, &mut blocks);
+    let mut p = blocks[0] ^ dec[0];
+    for k in &dec[1..dec.len() - 1] {
+      p = aes_decrypt_m128i(p, *k);
+    }
+    p = aes_decrypt_last_m128i(p, dec[dec.len() - 1]);
+    assert_eq!(bytes(p), bytes(plain));
+  }
+  let k = aes_key_expand_128([7; 16]);
+  round_trip(&k, &aes_inverse_round_keys(k));
+  let k = aes_key_expand_192([7; 24]);
+  round_trip(&k, &aes_inverse_round_keys(k));
+  let k = aes_key_expand_256([7; 32]);
+  round_trip(&k, &aes_inverse_round_keys(k));
+}
+
+#[test]
+fn test_aes_ecb_encrypt_blocks() {
+  // NIST SP 800-38A, F.1.1
+  let k = aes_key_expand_128(0x2b7e151628aed2a6abf7158809cf4f3c_u128.to_be_bytes());
+  let plain = [0x6bc1bee22e409f96e93d7e117393172a_u128, 0xae2d8a571e03ac9c9eb76fac45af8e51, 0x30c81c46a35ce411e5fbc1191a0a52ef, 0xf69f2445df4f9b17ad2b417be66c3710];
+  let cipher = [0x3ad77bb40d7a3660a89ecaf32466ef97_u128, 0xf5d3d58503b9699de785895a96fdbaaf, 0x43b1cd7f598ece23881b00e3ed030688, 0x7b0c785e27e8ad3f8223207104725dd4];
+  // enough blocks to use both the 8 block path and the remainder path
+  let mut blocks: Vec<m128i> = plain.iter().cycle().take(11).map(|p| m128i::from(p.to_be_bytes())).collect();
+  aes_ecb_encrypt_blocks(&k, &mut blocks);
+  for (b, c) in blocks.iter().zip(cipher.iter().cycle()) {
+    assert_eq!(bytes(*b), c.to_be_bytes());
+  }
+}
+
+#[test]
+fn test_aes_ctr_keystream() {
+  // NIST SP 800-38A, F.5.1
+  let k = aes_key_expand_128(0x2b7e151628aed2a6abf7158809cf4f3c_u128.to_be_bytes());
+  let plain = [0x6bc1bee22e409f96e93d7e117393172a_u128, 0xae2d8a571e03ac9c9eb76fac45af8e51];
+  let cipher = [0x874d6191b620e3261bef6864990db6ce_u128, 0x9806f66b7970fdff8617187bb9fffdff];
+  let mut stream = [m128i::default(); 2];
+  let next = aes_ctr_keystream(&k, 0xf0f1f2f3f4f5f6f7f8f9fafbfcfdfeff, &mut stream);
+  assert_eq!(next, 0xf0f1f2f3f4f5f6f7f8f9fafbfcfdff01);
+  for ((s, p), c) in stream.iter().zip(plain.iter()).zip(cipher.iter()) {
+    let s = u128::from_be_bytes(bytes(*s));
+    assert_eq!(s ^ p, *c);
+  }
+}
@@ -100,6 +100,24 @@ fn test_blend_varying_m256() {
   assert_eq!(c, [4.0, 1.0, 6.0, 3.0, -4.0, -5.0, 10.0, 11.0]);
 }
 
+#[test]
+fn test_select_m256() {
+  let a = m256::from_array([1.0, 5.0, 3.0, 8.0, 0.0, 9.0, -1.0, 2.0]);
+  let b = m256::from_array([4.0, 2.0, 6.0, 7.0, 1.0, 1.0, 1.0, 1.0]);
+  let mask = cmp_op_mask_m256::<{ CmpOp::GreaterThanOrdered.imm() }>(a, b);
+  let c = select_m256(mask, a, b).to_array();
+  assert_eq!(c, [4.0, 5.0, 6.0, 8.0, 1.0, 9.0, 1.0, 2.0]);
+}
+
+#[test]
+fn test_select_m256d() {
+  let a = m256d::from_array([1.0, 5.0, 3.0, 8.0]);
+  let b = m256d::from_array([4.0, 2.0, 6.0, 7.0]);
+  let mask = cmp_op_mask_m256d::<{ CmpOp::GreaterThanOrdered.imm() }>(a, b);
+  let c = select_m256d(mask, a, b).to_array();
+  assert_eq!(c, [4.0, 5.0, 6.0, 8.0]);
+}
+
 #[test]
 fn test_load_m128d_splat_m256d() {
   let a = m128d::from_array([0.0, 1.0]);
@@ -128,6 +146,22 @@ fn test_load_f32_splat_m256() {
   assert_eq!(b, [1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0]);
 }
 
+#[test]
+fn test_load_splat_from_slice_m256() {
+  let row = [1.0_f32, 2.0, 3.0];
+  assert_eq!(load_splat_from_slice_m256(&row, 0).to_array(), [1.0; 8]);
+  assert_eq!(load_splat_from_slice_m256d(&[5.0, 6.0], 1).to_array(), [6.0; 4]);
+  let b: [i32; 8] = load_splat_from_slice_m256i(&[i32::MIN, 7], 0).into();
+  assert_eq!(b, [i32::MIN; 8]);
+}
+
+#[test]
+#[should_panic]
+fn test_load_splat_from_slice_m256_out_of_bounds() {
+  let row = [1.0_f32, 2.0, 3.0];
+  let _ = load_splat_from_slice_m256(&row, 3);
+}
+
 #[test]
 fn test_cast_to_m256_from_m256d() {
   let a = load_f64_splat_m256d(&1.0);
@@ -190,11 +224,32 @@ fn test_ceil_m256() {
   assert_eq!(b, [2.0, 3.0, 4.0, 5.0, 0.0, -1.0, -2.0, -3.0]);
 }
 
+#[test]
+#[allow(deprecated)]
+fn test_cmp_op_enum_matches_macro() {
+  assert_eq!(CmpOp::EqualOrdered.imm(), cmp_op!(EqualOrdered));
+  assert_eq!(CmpOp::EqualUnordered.imm(), cmp_op!(EqualUnordered));
+  assert_eq!(CmpOp::False.imm(), cmp_op!(False));
+  assert_eq!(CmpOp::GreaterEqualOrdered.imm(), cmp_op!(GreaterEqualOrdered));
+  assert_eq!(CmpOp::GreaterThanOrdered.imm(), cmp_op!(GreaterThanOrdered));
+  assert_eq!(CmpOp::LessEqualOrdered.imm(), cmp_op!(LessEqualOrdered));
+  assert_eq!(CmpOp::LessThanOrdered.imm(), cmp_op!(LessThanOrdered));
+  assert_eq!(CmpOp::NotEqualOrdered.imm(), cmp_op!(NotEqualOrdered));
+  assert_eq!(CmpOp::NotEqualUnordered.imm(), cmp_op!(NotEqualUnordered));
+  assert_eq!(CmpOp::NotGreaterEqualUnordered.imm(), cmp_op!(NotGreaterEqualUnordered));
+  assert_eq!(CmpOp::NotGreaterThanUnordered.imm(), cmp_op!(NotGreaterThanUnordered));
+  assert_eq!(CmpOp::NotLessEqualUnordered.imm(), cmp_op!(NotLessEqualUnordered));
+  assert_eq!(CmpOp::NotLessThanUnordered.imm(), cmp_op!(NotLessThanUnordered));
+  assert_eq!(CmpOp::Ordered.imm(), cmp_op!(Ordered));
+  assert_eq!(CmpOp::True.imm(), cmp_op!(True));
+  assert_eq!(CmpOp::Unordered.imm(), cmp_op!(Unordered));
+}
+
 #[test]
 fn test_cmp_op_mask_m128() {
   let a = m128::from_array([2.0, 0.0, -2.0, 0.0]);
   let b = m128::from_array([1.0, 1.0, -1.0, -1.0]);
-  let c = cmp_op_mask_m128::<{ cmp_op!(GreaterThanOrdered) }>(a, b).to_bits();
+  let c = cmp_op_mask_m128::<{ CmpOp::GreaterThanOrdered.imm() }>(a, b).to_bits();
   assert_eq!(c, [u32::MAX, 0, 0, u32::MAX]);
 }
 
@@ -202,7 +257,7 @@ fn test_cmp_op_mask_m128() {
 fn test_cmp_op_mask_m128_s() {
   let a = m128::from_array([2.0, 0.0, -2.0, 0.0]);
   let b = m128::from_array([1.0, 1.0, -1.0, -1.0]);
-  let c = cmp_op_mask_m128_s::<{ cmp_op!(GreaterThanOrdered) }>(a, b).to_bits();
+  let c = cmp_op_mask_m128_s::<{ CmpOp::GreaterThanOrdered.imm() }>(a, b).to_bits();
   assert_eq!(c, [u32::MAX, 0, (-2_f32).to_bits(), 0]);
 }
 
@@ -210,7 +265,7 @@ fn test_cmp_op_mask_m128_s() {
 fn test_cmp_op_mask_m256() {
   let a = m256::from_array([1.0, 5.0, 0.0, 7.0, 5.0, 6.0, 7.0, -20.0]);
   let b = m256::from_array([2.0, 1.0, 3.0, 4.0, 1.0, -2.0, -3.0, -4.0]);
-  let c = cmp_op_mask_m256::<{ cmp_op!(LessThanOrdered) }>(a, b).to_bits();
+  let c = cmp_op_mask_m256::<{ CmpOp::LessThanOrdered.imm() }>(a, b).to_bits();
   assert_eq!(c, [u32::MAX, 0, u32::MAX, 0, 0, 0, 0, u32::MAX]);
 }
 
@@ -218,7 +273,7 @@ fn test_cmp_op_mask_m256() {
 fn test_cmp_op_mask_m128d() {
   let a = m128d::from_array([1.0, 0.0]);
   let b = m128d::from_array([1.0, 1.0]);
-  let c = cmp_op_mask_m128d::<{ cmp_op!(EqualOrdered) }>(a, b).to_bits();
+  let c = cmp_op_mask_m128d::<{ CmpOp::EqualOrdered.imm() }>(a, b).to_bits();
   assert_eq!(c, [u64::MAX, 0]);
 }
 
@@ -226,7 +281,7 @@ fn test_cmp_op_mask_m128d() {
 fn test_cmp_op_mask_m128d_s() {
   let a = m128d::from_array([1.0, 7.0]);
   let b = m128d::from_array([1.0, 1.0]);
-  let c = cmp_op_mask_m128d_s::<{ cmp_op!(EqualOrdered) }>(a, b).to_bits();
+  let c = cmp_op_mask_m128d_s::<{ CmpOp::EqualOrdered.imm() }>(a, b).to_bits();
   assert_eq!(c, [u64::MAX, 7_f64.to_bits()]);
 }
 
@@ -234,7 +289,7 @@ fn test_cmp_op_mask_m128d_s() {
 fn test_cmp_op_mask_m256d() {
   let a = m256d::from_array([1.0, 5.0, 0.0, 7.0]);
   let b = m256d::from_array([2.0, 1.0, 3.0, 4.0]);
-  let c = cmp_op_mask_m256d::<{ cmp_op!(LessThanOrdered) }>(a, b).to_bits();
+  let c = cmp_op_mask_m256d::<{ CmpOp::LessThanOrdered.imm() }>(a, b).to_bits();
   assert_eq!(c, [u64::MAX, 0, u64::MAX, 0]);
 }
 
@@ -301,6 +356,20 @@ fn test_convert_to_f32_from_m256_s() {
   assert_eq!(b, 4.0);
 }
 
+#[test]
+fn test_convert_saturating_to_i32_m256i_from_m256() {
+  let a = m256::from_array([1.9, -1.9, f32::NAN, 3.0e9, -3.0e9, f32::INFINITY, f32::NEG_INFINITY, 2147483520.0]);
+  let b: [i32; 8] = convert_saturating_to_i32_m256i_from_m256(a).into();
+  assert_eq!(b, [1, -1, 0, i32::MAX, i32::MIN, i32::MAX, i32::MIN, 2147483520]);
+}
+
+#[test]
+fn test_convert_saturating_to_i32_m128i_from_m256d() {
+  let a = m256d::from_array([f64::NAN, 1.0e10, -1.0e10, -7.5]);
+  let b: [i32; 4] = convert_saturating_to_i32_m128i_from_m256d(a).into();
+  assert_eq!(b, [0, i32::MAX, i32::MIN, -7]);
+}
+
 #[test]
 fn test_div_m256d() {
   let a = m256d::from([4.0, 5.0, 6.0, 7.0]);
@@ -332,7 +401,6 @@ fn test_extract_i32_from_m256i() {
 }
 
 #[test]
-#[cfg(target_arch = "x86_64")]
 fn test_extract_i64_from_m256i() {
   let a = m256i::from([9_i64, 10, 11, 12]);
   assert_eq!(extract_i64_from_m256i::<1>(a), 10_i64);
@@ -433,7 +501,6 @@ fn test_insert_i32_to_m256i() {
 }
 
 #[test]
-#[cfg(target_arch = "x86_64")]
 fn test_insert_i64_to_m256i() {
   let a = m256i::from([0_i64; 4]);
   let b: [i64; 4] = insert_i64_to_m256i::<3>(a, 5).into();
@@ -569,6 +636,73 @@ fn test_store_masked_m256() {
   assert_eq!(a.to_array(), [0.0, 17.0, 16.0, 0.0, 80.0, 1.0, 0.0, 0.0]);
 }
 
+#[test]
+fn test_load_partial_m256() {
+  let data = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+  assert_eq!(load_partial_m256(&data).to_array(), [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+  assert_eq!(load_partial_m256(&data[7..]).to_array(), [8.0, 9.0, 10.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+  assert_eq!(load_partial_m256(&[]).to_array(), [0.0; 8]);
+}
+
+#[test]
+fn test_load_partial_m256d() {
+  let data = [1.0, 2.0, 3.0, 4.0, 5.0];
+  assert_eq!(load_partial_m256d(&data).to_array(), [1.0, 2.0, 3.0, 4.0]);
+  assert_eq!(load_partial_m256d(&data[3..]).to_array(), [4.0, 5.0, 0.0, 0.0]);
+}
+
+#[test]
+fn test_simd_chunks_m256() {
+  let data: [f32; 17] = core::array::from_fn(|i| i as f32);
+  let (chunks, tail) = simd_chunks_m256(&data);
+  let chunks: Vec<[f32; 8]> = chunks.map(m256::to_array).collect();
+  assert_eq!(chunks, [[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0], [8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0]]);
+  assert_eq!(tail, &[16.0]);
+  let (mut chunks, tail) = simd_chunks_m256d(&[1.0, 2.0, 3.0]);
+  assert!(chunks.next().is_none());
+  assert_eq!(tail.len(), 3);
+
+  let mut data = [1.0_f32; 17];
+  let tail = simd_chunks_mut_m256(&mut data, |a| add_m256(a, set_splat_m256(1.0)));
+  assert_eq!(tail.len(), 1);
+  assert_eq!(data[..16], [2.0; 16]);
+  assert_eq!(data[16], 1.0);
+  let mut data = [2.0_f64; 8];
+  assert!(simd_chunks_mut_m256d(&mut data, |a| mul_m256d(a, a)).is_empty());
+  assert_eq!(data, [4.0; 8]);
+}
+
+#[test]
+fn test_store_nontemporal_m256() {
+  let mut a = zeroed_m256();
+  let mut b = zeroed_m256d();
+  let mut c = zeroed_m256i();
+  streaming_stores(|s| {
+    s.store_nontemporal_m256(&mut a, set_splat_m256(1.0));
+    s.store_nontemporal_m256d(&mut b, set_splat_m256d(2.0));
+    s.store_nontemporal_m256i(&mut c, set_splat_i32_m256i(3));
+  });
+  assert_eq!(a.to_array(), [1.0; 8]);
+  assert_eq!(b.to_array(), [2.0; 4]);
+  assert_eq!(<[i32; 8]>::from(c), [3; 8]);
+}
+
+#[test]
+fn test_store_partial_m256() {
+  let mut data = [0.0; 10];
+  store_partial_m256(&mut data[..], m256::from_array([1.0; 8]));
+  assert_eq!(data, [1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0]);
+  store_partial_m256(&mut data[7..], m256::from_array([2.0; 8]));
+  assert_eq!(data, [1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0]);
+}
+
+#[test]
+fn test_store_partial_m256d() {
+  let mut data = [0.0; 5];
+  store_partial_m256d(&mut data[3..], m256d::from_array([1.0, 2.0, 3.0, 4.0]));
+  assert_eq!(data, [0.0, 0.0, 0.0, 1.0, 2.0]);
+}
+
 #[test]
 fn test_max_m256d() {
   let a = m256d::from_array([1.0, 12.0, -1.0, 3.0]);
@@ -601,6 +735,82 @@ fn test_min_m256() {
   assert_eq!(c, [1.0, 6.0, -1.0, 2.2, 5.0, 0.0, 1.0, 2.0]);
 }
 
+#[test]
+fn test_max_nan_propagating_m256d() {
+  let a = m256d::from_array([1.0, f64::NAN, 3.0, f64::NAN]);
+  let b = m256d::from_array([5.0, 6.0, f64::NAN, f64::NAN]);
+  let c = max_nan_propagating_m256d(a, b).to_array();
+  assert_eq!(c[0], 5.0);
+  assert!(c[1].is_nan() && c[2].is_nan() && c[3].is_nan());
+}
+
+#[test]
+fn test_max_num_m256d() {
+  let a = m256d::from_array([1.0, f64::NAN, 3.0, f64::NAN]);
+  let b = m256d::from_array([5.0, 6.0, f64::NAN, f64::NAN]);
+  let c = max_num_m256d(a, b).to_array();
+  assert_eq!(&c[..3], &[5.0, 6.0, 3.0]);
+  assert!(c[3].is_nan());
+}
+
+#[test]
+fn test_max_nan_propagating_m256() {
+  let a = m256::from_array([1.0, f32::NAN, 3.0, f32::NAN, 10.0, 0.0, 1.0, 2.0]);
+  let b = m256::from_array([5.0, 6.0, f32::NAN, f32::NAN, 5.0, 6.0, 7.0, 8.0]);
+  let c = max_nan_propagating_m256(a, b).to_array();
+  assert_eq!(c[0], 5.0);
+  assert!(c[1].is_nan() && c[2].is_nan() && c[3].is_nan());
+  assert_eq!(&c[4..], &[10.0, 6.0, 7.0, 8.0]);
+}
+
+#[test]
+fn test_max_num_m256() {
+  let a = m256::from_array([1.0, f32::NAN, 3.0, f32::NAN, 10.0, 0.0, 1.0, 2.0]);
+  let b = m256::from_array([5.0, 6.0, f32::NAN, f32::NAN, 5.0, 6.0, 7.0, 8.0]);
+  let c = max_num_m256(a, b).to_array();
+  assert_eq!(&c[..3], &[5.0, 6.0, 3.0]);
+  assert!(c[3].is_nan());
+  assert_eq!(&c[4..], &[10.0, 6.0, 7.0, 8.0]);
+}
+
+#[test]
+fn test_min_nan_propagating_m256d() {
+  let a = m256d::from_array([1.0, f64::NAN, 3.0, f64::NAN]);
+  let b = m256d::from_array([5.0, 6.0, f64::NAN, f64::NAN]);
+  let c = min_nan_propagating_m256d(a, b).to_array();
+  assert_eq!(c[0], 1.0);
+  assert!(c[1].is_nan() && c[2].is_nan() && c[3].is_nan());
+}
+
+#[test]
+fn test_min_num_m256d() {
+  let a = m256d::from_array([1.0, f64::NAN, 3.0, f64::NAN]);
+  let b = m256d::from_array([5.0, 6.0, f64::NAN, f64::NAN]);
+  let c = min_num_m256d(a, b).to_array();
+  assert_eq!(&c[..3], &[1.0, 6.0, 3.0]);
+  assert!(c[3].is_nan());
+}
+
+#[test]
+fn test_min_nan_propagating_m256() {
+  let a = m256::from_array([1.0, f32::NAN, 3.0, f32::NAN, 10.0, 0.0, 1.0, 2.0]);
+  let b = m256::from_array([5.0, 6.0, f32::NAN, f32::NAN, 5.0, 6.0, 7.0, 8.0]);
+  let c = min_nan_propagating_m256(a, b).to_array();
+  assert_eq!(c[0], 1.0);
+  assert!(c[1].is_nan() && c[2].is_nan() && c[3].is_nan());
+  assert_eq!(&c[4..], &[5.0, 0.0, 1.0, 2.0]);
+}
+
+#[test]
+fn test_min_num_m256() {
+  let a = m256::from_array([1.0, f32::NAN, 3.0, f32::NAN, 10.0, 0.0, 1.0, 2.0]);
+  let b = m256::from_array([5.0, 6.0, f32::NAN, f32::NAN, 5.0, 6.0, 7.0, 8.0]);
+  let c = min_num_m256(a, b).to_array();
+  assert_eq!(&c[..3], &[1.0, 6.0, 3.0]);
+  assert!(c[3].is_nan());
+  assert_eq!(&c[4..], &[5.0, 0.0, 1.0, 2.0]);
+}
+
 #[test]
 fn test_duplicate_odd_lanes_m256d() {
   let a = m256d::from_array([1.0, 12.0, -1.0, 3.0]);
@@ -781,6 +991,27 @@ fn test_reciprocal_m256() {
 }
 
 #[test]
+fn test_reciprocal_sqrt_refined_m256() {
+  let a = m256::from_array([16.0, 2.0, 0.01, 12345.0, 1.0e-30, 3.0, 1.0e30, 0.7]);
+  let b = reciprocal_sqrt_refined_m256(a).to_array();
+  for (x, r) in a.to_array().iter().zip(b.iter()) {
+    let expected = 1.0 / x.sqrt();
+    assert!(((r - expected) / expected).abs() < 1.0e-6);
+  }
+}
+
+#[test]
+fn test_reciprocal_refined_m256() {
+  let a = m256::from_array([1.0, 3.0, -7.0, 1000.0, 0.1, 1.0e-30, 12345.0, -0.3]);
+  let b = reciprocal_refined_m256(a).to_array();
+  for (x, r) in a.to_array().iter().zip(b.iter()) {
+    let expected = 1.0 / x;
+    assert!(((r - expected) / expected).abs() < 1.0e-6);
+  }
+}
+
+#[test]
+#[allow(deprecated)]
 fn test_round_m256d() {
   let a = m256d::from_array([-0.1, 1.6, 2.5, 3.1]);
   //
@@ -794,6 +1025,7 @@ fn test_round_m256d() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_round_m256() {
   let a = m256::from_array([-0.1, 1.6, 3.3, 4.5, 5.1, 6.5, 7.2, 8.0]);
   //
@@ -816,6 +1048,50 @@ fn test_reciprocal_sqrt_m256() {
   }
 }
 
+#[test]
+fn test_dot_product_m256d() {
+  let a = m256d::from_array([1.0, 2.0, 3.0, 4.0]);
+  let b = m256d::from_array([5.0, -6.0, 7.0, 0.5]);
+  assert_eq!(dot_product_m256d(a, b), 16.0);
+}
+
+#[test]
+fn test_dot_f32() {
+  let a: Vec<f32> = (0..21).map(|i| i as f32).collect();
+  let b: Vec<f32> = (0..21).map(|i| (i % 3) as f32).collect();
+  let expected: f32 = a.iter().zip(&b).map(|(x, y)| x * y).sum();
+  assert_eq!(dot_f32(&a, &b), expected);
+  assert_eq!(dot_f32(&a[..5], &b), dot_f32(&a[..5], &b[..5]));
+  assert_eq!(dot_f32(&[], &b), 0.0);
+}
+
+#[test]
+fn test_prefix_sum_m256() {
+  let a = m256::from_array([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.5]);
+  let c = prefix_sum_m256(a).to_array();
+  assert_eq!(c, [1.0, 3.0, 6.0, 10.0, 15.0, 21.0, 28.0, 36.5]);
+}
+
+#[test]
+fn test_reduce_add_m256() {
+  let a = m256::from_array([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.5]);
+  assert_eq!(reduce_add_m256(a), 36.5);
+}
+
+#[test]
+fn test_reduce_min_max_m256() {
+  let a = m256::from_array([3.0, -2.0, 7.0, 4.0, 5.0, -6.0, 1.0, 8.5]);
+  assert_eq!(reduce_min_m256(a), -6.0);
+  assert_eq!(reduce_max_m256(a), 8.5);
+  for i in 0..8 {
+    let mut arr = [0.0; 8];
+    arr[i] = 1.0;
+    assert_eq!(reduce_max_m256(m256::from_array(arr)), 1.0);
+    arr[i] = -1.0;
+    assert_eq!(reduce_min_m256(m256::from_array(arr)), -1.0);
+  }
+}
+
 #[test]
 fn test_set_i8_m256i() {
   let a: [i8; 32] = set_i8_m256i(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31).into();
@@ -1116,6 +1392,35 @@ fn test_sub_m256() {
   assert_eq!(c, [-58.0, -59.0, -76.0, -77.5, -70.0, -70.0, -70.0, -1.0]);
 }
 
+#[test]
+fn test_transpose_8x8_m256() {
+  let mut rows = [zeroed_m256(); 8];
+  for (r, row) in rows.iter_mut().enumerate() {
+    let mut a = [0.0; 8];
+    for (c, x) in a.iter_mut().enumerate() {
+      *x = (r * 8 + c) as f32;
+    }
+    *row = m256::from_array(a);
+  }
+  transpose_8x8_m256(&mut rows);
+  for (c, col) in rows.iter().enumerate() {
+    let a = col.to_array();
+    for (r, x) in a.iter().enumerate() {
+      assert_eq!(*x, (r * 8 + c) as f32);
+    }
+  }
+}
+
+#[test]
+fn test_transpose_4x4_m256d() {
+  let mut rows = [m256d::from_array([1.0, 2.0, 3.0, 4.0]), m256d::from_array([5.0, 6.0, 7.0, 8.0]), m256d::from_array([9.0, 10.0, 11.0, 12.0]), m256d::from_array([13.0, 14.0, 15.0, 16.0])];
+  transpose_4x4_m256d(&mut rows);
+  assert_eq!(rows[0].to_array(), [1.0, 5.0, 9.0, 13.0]);
+  assert_eq!(rows[1].to_array(), [2.0, 6.0, 10.0, 14.0]);
+  assert_eq!(rows[2].to_array(), [3.0, 7.0, 11.0, 15.0]);
+  assert_eq!(rows[3].to_array(), [4.0, 8.0, 12.0, 16.0]);
+}
+
 #[test]
 fn test_unpack_hi_m256d() {
   let a = m256d::from_array([1.0, 2.0, 3.0, 4.0]);
@@ -1251,3 +1556,17 @@ fn test_ptest_i256() {
   assert_eq!(testc_m256i(a, b), 0);
   assert_eq!(testc_m256i(b, b), 1);
 }
+
+#[test]
+fn test_testnzc() {
+  let a = m256::from_array([-1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0]);
+  let b = m256::from_array([-1.0, -1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0]);
+  assert_eq!(testnzc_m256(a, b), 1);
+  assert_eq!(testnzc_m256(a, a), 0);
+  let c = m128d::from_array([-1.0, 1.0]);
+  assert_eq!(testnzc_m128d(c, set_splat_m128d(-1.0)), 1);
+  assert_eq!(testnzc_m128d(c, m128d::from_array([1.0, -1.0])), 0);
+  let i = m256i::from([1_i64, 0, 0, 0]);
+  assert_eq!(testnzc_m256i(i, m256i::from([3_i64, 0, 0, 0])), 1);
+  assert_eq!(testnzc_m256i(i, i), 0);
+}
@@ -128,3 +128,15 @@ fn test_trailing_zero_count_u64() {
   assert_eq!(trailing_zero_count_u64(0b100), 2);
   assert_eq!(trailing_zero_count_u64(0b111100), 2);
 }
+
+#[test]
+fn test_set_bits() {
+  let mask = move_mask_i8_m128i(m128i::from([-1_i8, 0, 0, -1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, -1]));
+  let mut bits = SetBits(mask as u64);
+  assert_eq!(bits.next(), Some(0));
+  assert_eq!(bits.next(), Some(3));
+  assert_eq!(bits.next(), Some(15));
+  assert_eq!(bits.next(), None);
+  assert_eq!(SetBits(u64::MAX).len(), 64);
+  assert_eq!(SetBits(1 << 63).next(), Some(63));
+}
@@ -61,3 +61,15 @@ fn test_population_extract_u64() {
   assert_eq!(population_extract_u64(0b1001, 0b1110), 0b0100);
   assert_eq!(population_extract_u64(0b1001, 0b1100), 0b0010);
 }
+
+#[test]
+fn test_interleave_bits_u32() {
+  for &(x, y) in &[(0, 0), (1, 0), (0, 1), (u32::MAX, 0), (0, u32::MAX), (0xDEAD_BEEF, 0x1234_5678)] {
+    let m = interleave_bits_u32(x, y);
+    for i in 0..32 {
+      assert_eq!((m >> (2 * i)) & 1, u64::from((x >> i) & 1));
+      assert_eq!((m >> (2 * i + 1)) & 1, u64::from((y >> i) & 1));
+    }
+    assert_eq!(deinterleave_bits_u64(m), (x, y));
+  }
+}
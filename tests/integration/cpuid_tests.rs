@@ -0,0 +1,98 @@
+use super::*;
+
+#[test]
+fn test_cpu_features_match_std_detection() {
+  let f = cpu_features();
+  assert_eq!(f.sse2, is_x86_feature_detected!("sse2"));
+  assert_eq!(f.sse4_2, is_x86_feature_detected!("sse4.2"));
+  assert_eq!(f.popcnt, is_x86_feature_detected!("popcnt"));
+  assert_eq!(f.bmi1, is_x86_feature_detected!("bmi1"));
+  assert_eq!(f.bmi2, is_x86_feature_detected!("bmi2"));
+  assert_eq!(f.lzcnt, is_x86_feature_detected!("lzcnt"));
+  assert_eq!(f.rdseed, is_x86_feature_detected!("rdseed"));
+  assert_eq!(f.movbe, is_x86_feature_detected!("movbe"));
+  assert_eq!(f.sse4a, is_x86_feature_detected!("sse4a"));
+  assert_eq!(f.sha, is_x86_feature_detected!("sha"));
+  assert_eq!(f.cmpxchg16b, is_x86_feature_detected!("cmpxchg16b"));
+}
+
+#[test]
+fn test_cpu_features_names() {
+  let f = CpuFeatures::detect();
+  assert_eq!(f, cpu_features());
+  for (name, set) in f.as_list().iter() {
+    assert_eq!(f.has(name), Some(*set));
+  }
+  let shown = format!("{}", f);
+  assert_eq!(shown.split(' ').any(|n| n == "sse2"), f.sse2);
+  assert_eq!(format!("{}", CpuFeatures::default()), "");
+}
+
+#[test]
+fn test_cpu_vendor_string() {
+  let vendor = cpu_vendor_string();
+  assert!(vendor.iter().all(|b| b.is_ascii_graphic() || *b == b' '));
+}
+
+#[test]
+fn test_cpu_brand_string() {
+  if let Some(brand) = cpu_brand_string() {
+    assert!(brand.iter().all(|b| b.is_ascii()));
+  }
+}
+
+#[test]
+fn test_cpu_version() {
+  let v = cpu_version();
+  assert!(v.stepping < 16);
+  assert!(v.family > 0);
+}
+
+#[test]
+fn test_os_supports_avx() {
+  assert_eq!(os_supports_avx(), is_x86_feature_detected!("avx"));
+  assert_eq!(os_supports_avx512(), is_x86_feature_detected!("avx512f"));
+  if os_supports_avx() {
+    assert!(read_xcr0().is_some());
+  }
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+fn test_x86_64_levels() {
+  let v2 = supports_x86_64_v2();
+  let v3 = supports_x86_64_v3();
+  let v4 = supports_x86_64_v4();
+  assert!(v2 || !v3);
+  assert!(v3 || !v4);
+  assert_eq!(v3, v2 && is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") && is_x86_feature_detected!("movbe") && is_x86_feature_detected!("bmi2") && is_x86_feature_detected!("lzcnt") && is_x86_feature_detected!("f16c") && is_x86_feature_detected!("bmi1"));
+  if COMPILED_FOR_X86_64_V2 {
+    assert!(v2);
+  }
+  if COMPILED_FOR_X86_64_V3 {
+    assert!(v3);
+  }
+  if COMPILED_FOR_X86_64_V4 {
+    assert!(v4);
+  }
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+fn test_compiled_for_x86_64_level() {
+  // CI sets this along with `-C target-cpu=x86-64-v2` (or `v3`).
+  let (v2, v3) = (COMPILED_FOR_X86_64_V2, COMPILED_FOR_X86_64_V3);
+  match option_env!("SAFE_ARCH_TEST_X86_64_LEVEL") {
+    Some("v2") => assert!(v2),
+    Some("v3") => assert!(v2 && v3),
+    _ => (),
+  }
+}
+
+#[test]
+fn test_ensure_cpu_compatible() {
+  // the tests themselves run, so this CPU must have what they were built for
+  assert_eq!(ensure_cpu_compatible(), Ok(()));
+  let e = MissingCpuFeatures { missing: CpuFeatures { avx2: true, fma: true, ..Default::default() } };
+  assert!(format!("{}", e).contains(": fma avx2 ("));
+}
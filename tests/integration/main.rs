@@ -1,49 +1,62 @@
+#![cfg_attr(feature = "portable_simd", feature(portable_simd))]
 #![allow(bad_style)]
 #![allow(unused_imports)]
 #![allow(clippy::identity_op)]
 
 use safe_arch::*;
 
-#[cfg(target_feature = "adx")]
+#[cfg(all(target_feature = "adx", feature = "adx"))]
 mod adx_tests;
 
-#[cfg(target_feature = "avx")]
+#[cfg(all(target_feature = "aes", feature = "aes"))]
+mod aes_tests;
+
+#[cfg(all(target_feature = "avx", feature = "avx"))]
 mod avx_tests;
 
-#[cfg(target_feature = "bmi1")]
+#[cfg(all(target_feature = "bmi1", feature = "bmi1"))]
 mod bmi1_tests;
 
-#[cfg(target_feature = "bmi2")]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod cpuid_tests;
+
+#[cfg(all(target_feature = "bmi2", feature = "bmi2"))]
 mod bmi2_tests;
 
-#[cfg(target_feature = "lzcnt")]
+#[cfg(all(target_feature = "lzcnt", feature = "lzcnt"))]
 mod lzcnt_tests;
 
-#[cfg(target_feature = "pclmulqdq")]
+#[cfg(all(target_feature = "avx2", feature = "math"))]
+mod math_tests;
+
+#[cfg(all(target_feature = "pclmulqdq", feature = "pclmulqdq"))]
 mod pclmulqdq_tests;
 
-#[cfg(target_feature = "popcnt")]
+#[cfg(all(target_feature = "popcnt", feature = "popcnt"))]
 mod popcnt_tests;
 
-#[cfg(target_feature = "rdrand")]
+#[cfg(all(target_feature = "rdrand", feature = "rdrand"))]
 mod rdrand_tests;
 
-#[cfg(target_feature = "rdseed")]
+#[cfg(all(target_feature = "rdseed", feature = "rdseed"))]
 mod rdseed_tests;
 
-#[cfg(target_feature = "sse2")]
+#[cfg(feature = "runtime")]
+mod runtime_tests;
+
+#[cfg(all(target_feature = "sse2", feature = "sse2"))]
 mod sse2_tests;
 
-#[cfg(target_feature = "sse3")]
+#[cfg(all(target_feature = "sse3", feature = "sse3"))]
 mod sse3_tests;
 
-#[cfg(target_feature = "ssse3")]
+#[cfg(all(target_feature = "ssse3", feature = "ssse3"))]
 mod ssse3_tests;
 
-#[cfg(target_feature = "sse4.1")]
+#[cfg(all(target_feature = "sse4.1", feature = "sse4_1"))]
 mod sse4_1_tests;
 
-#[cfg(target_feature = "sse4.2")]
+#[cfg(all(target_feature = "sse4.2", feature = "sse4_2"))]
 mod sse4_2_tests;
 
 #[test]
@@ -116,6 +129,79 @@ fn test_m128_fmt() {
   assert_eq!(&f, "(0, 0, 0, 0)");
 }
 
+#[test]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn test_const_constructors() {
+  static BITS: m128 = m128::from_bits([0x3F80_0000; 4]);
+  assert_eq!(BITS.to_array(), [1.0; 4]);
+
+  const ARR: m256d = m256d::from_array([1.0, 2.0, 3.0, 4.0]);
+  assert_eq!(ARR.to_array(), [1.0, 2.0, 3.0, 4.0]);
+
+  #[cfg(all(target_feature = "sse2", feature = "sse2"))]
+  {
+    static SPLAT: m128i = set_splat_i16_m128i(-3);
+    assert_eq!(<[i16; 8]>::from(SPLAT), [-3; 8]);
+    const ZERO: m128d = zeroed_m128d();
+    assert_eq!(ZERO.to_bits(), [0; 2]);
+  }
+
+  #[cfg(all(target_feature = "avx", feature = "avx"))]
+  {
+    static SPLAT: m256 = set_splat_m256(2.5);
+    assert_eq!(SPLAT.to_array(), [2.5; 8]);
+    const ZERO: m256i = zeroed_m256i();
+    assert_eq!(<[i64; 4]>::from(ZERO), [0; 4]);
+  }
+}
+
+#[test]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn test_as_bytes() {
+  let mut m = m128i::from(0x0F0E0D0C_0B0A0908_07060504_03020100_u128);
+  assert_eq!(m.as_bytes(), &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+  m.as_bytes_mut()[15] = 0xFF;
+  assert_eq!(u128::from(m) >> 120, 0xFF);
+
+  let mut m = m256::from_array([1.0; 8]);
+  assert_eq!(&m.as_bytes()[..4], &1.0_f32.to_ne_bytes());
+  m.as_bytes_mut()[28..].copy_from_slice(&2.0_f32.to_ne_bytes());
+  assert_eq!(m.to_array()[7], 2.0);
+}
+
+#[test]
+#[cfg(feature = "portable_simd")]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn test_portable_simd_conversions() {
+  use core::simd::Simd;
+
+  let m = m128::from(Simd::from_array([1.0_f32, 2.0, 3.0, 4.0]));
+  assert_eq!(m.to_array(), [1.0, 2.0, 3.0, 4.0]);
+  assert_eq!(Simd::<f32, 4>::from(m).to_array(), [1.0, 2.0, 3.0, 4.0]);
+
+  let m = m256i::from(Simd::<i16, 16>::splat(-2));
+  assert_eq!(<[i16; 16]>::from(m), [-2; 16]);
+  assert_eq!(Simd::<u64, 4>::from(m), Simd::splat(0xFFFE_FFFE_FFFE_FFFE));
+}
+
+#[test]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn test_core_arch_conversions() {
+  #[cfg(target_arch = "x86")]
+  use core::arch::x86::*;
+  #[cfg(target_arch = "x86_64")]
+  use core::arch::x86_64::*;
+
+  let raw: __m128i = m128i::from([1_i32, 2, 3, 4]).into();
+  assert_eq!(<[i32; 4]>::from(m128i::from(raw)), [1, 2, 3, 4]);
+
+  let raw: __m256 = m256::from_array([5.0; 8]).into();
+  assert_eq!(m256::from(raw).to_array(), [5.0; 8]);
+
+  let raw = __m128d::from(m128d::from_array([1.0, 2.0]));
+  assert_eq!(m128d::from(raw).to_array(), [1.0, 2.0]);
+}
+
 #[allow(dead_code)]
 fn approx_eq_f32(a: f32, b: f32) -> bool {
   (a - b).abs() < 0.00000001
@@ -125,3 +211,53 @@ fn approx_eq_f32(a: f32, b: f32) -> bool {
 fn approx_eq_f64(a: f64, b: f64) -> bool {
   (a - b).abs() < 0.00000000001
 }
+
+#[test]
+#[cfg(feature = "introspection")]
+fn test_introspection_table() {
+  use safe_arch::introspection::*;
+  let find = |name: &str| INTRINSIC_TABLE.iter().find(|i| i.function == name);
+  let add = find("add_m128").unwrap();
+  assert_eq!(add.intrinsic, "_mm_add_ps");
+  assert_eq!(add.target_features, &["sse"]);
+  let fma = find("fused_mul_add_m128").unwrap();
+  assert_eq!(fma.instructions.len(), 3);
+  // methods and private helpers aren't listed
+  assert!(find("partial_mask_i32_m256i").is_none());
+  assert!(find("to_array").is_none());
+}
+
+#[test]
+#[cfg(all(target_feature = "avx2", feature = "avx2"))]
+fn test_prelude_levels() {
+  use safe_arch::prelude::avx2::*;
+  let a = set_splat_i32_m256i(3);
+  let b: [i32; 8] = add_i32_m256i(a, a).into();
+  assert_eq!(b, [6; 8]);
+  let c: [i32; 4] = add_i32_m128i(m128i::from([1, 2, 3, 4]), m128i::from([1, 1, 1, 1])).into();
+  assert_eq!(c, [2, 3, 4, 5]);
+}
+
+#[test]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn test_compiled_modules() {
+  let modules = compiled_modules();
+  assert_eq!(compiled_with("sse2"), cfg!(all(target_feature = "sse2", feature = "sse2")));
+  assert_eq!(compiled_with("math"), cfg!(all(target_feature = "avx2", feature = "math")));
+  assert!(!compiled_with("not_a_module"));
+  // the prerequisites of a module are always built along with it
+  if compiled_with("avx2") {
+    assert!(modules.contains(&"avx") && modules.contains(&"sse4_2"));
+  }
+}
+
+#[test]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn test_target_feature_summary() {
+  let summary = target_feature_summary().to_string();
+  let (features, modules) = summary.split_once("; modules:").unwrap();
+  let features: Vec<&str> = features.split_whitespace().collect();
+  assert_eq!(features.contains(&"avx2"), cfg!(target_feature = "avx2"));
+  let listed: Vec<&str> = modules.split_whitespace().collect();
+  assert_eq!(listed, compiled_modules());
+}
@@ -0,0 +1,75 @@
+use super::*;
+
+/// How many `f32` values apart `a` and `b` are.
+fn ulp_diff(a: f32, b: f32) -> u32 {
+  let key = |f: f32| {
+    let i = f.to_bits() as i32;
+    if i < 0 {
+      i32::MIN.wrapping_sub(i)
+    } else {
+      i
+    }
+  };
+  (i64::from(key(a)) - i64::from(key(b))).unsigned_abs() as u32
+}
+
+/// The worst error of `f` against `reference` over a sweep of `range`, in ULP
+/// and also as an absolute difference.
+fn worst_error(range: core::ops::Range<f32>, f: impl Fn(m256) -> m256, reference: impl Fn(f64) -> f64) -> (u32, f64) {
+  let (mut ulp, mut abs) = (0, 0.0_f64);
+  let step = (range.end - range.start) / 100_000.0;
+  for i in 0..100_000 {
+    let mut lanes = [0.0; 8];
+    for (l, x) in lanes.iter_mut().enumerate() {
+      *x = range.start + step * (i as f32 + l as f32 / 8.0);
+    }
+    let out = f(m256::from_array(lanes)).to_array();
+    for (x, y) in lanes.iter().zip(out.iter()) {
+      let expected = reference(f64::from(*x));
+      ulp = ulp.max(ulp_diff(*y, expected as f32));
+      abs = abs.max((f64::from(*y) - expected).abs());
+    }
+  }
+  (ulp, abs)
+}
+
+#[test]
+fn test_exp_m256() {
+  assert!(worst_error(-87.0..88.7, exp_m256, f64::exp).0 <= 1);
+  assert!(worst_error(-1.0..1.0, exp_m256, f64::exp).0 <= 1);
+  let special = [f32::NAN, f32::INFINITY, f32::NEG_INFINITY, 89.0, -105.0, 0.0, 1.0e-30, -1.0e-30];
+  let e = exp_m256(m256::from_array(special)).to_array();
+  assert!(e[0].is_nan());
+  assert_eq!(&e[1..], &[f32::INFINITY, 0.0, f32::INFINITY, 0.0, 1.0, 1.0, 1.0]);
+}
+
+#[test]
+fn test_ln_m256() {
+  assert!(worst_error(1.0e-3..10.0, ln_m256, f64::ln).0 <= 1);
+  assert!(worst_error(0.5..2.0, ln_m256, f64::ln).0 <= 1);
+  assert!(worst_error(10.0..1.0e30, ln_m256, f64::ln).0 <= 1);
+  assert!(worst_error(1.0e-44..1.0e-38, ln_m256, f64::ln).0 <= 1);
+  let special = [f32::NAN, f32::INFINITY, 0.0, -0.0, -1.0, f32::NEG_INFINITY, 1.0, f32::MAX];
+  let l = ln_m256(m256::from_array(special)).to_array();
+  assert!(l[0].is_nan());
+  assert_eq!(&l[1..4], &[f32::INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY]);
+  assert!(l[4].is_nan() && l[5].is_nan());
+  assert_eq!(l[6], 0.0);
+  assert_eq!(l[7], f32::MAX.ln());
+}
+
+#[test]
+fn test_sin_cos_m256() {
+  let pi = core::f32::consts::PI;
+  assert!(worst_error(-pi..pi, |x| sin_cos_m256(x).0, f64::sin).0 <= 1);
+  assert!(worst_error(-pi..pi, |x| sin_cos_m256(x).1, f64::cos).0 <= 1);
+  assert!(worst_error(-8192.0..8192.0, |x| sin_cos_m256(x).0, f64::sin).1 <= 1.0e-7);
+  assert!(worst_error(-8192.0..8192.0, |x| sin_cos_m256(x).1, f64::cos).1 <= 1.0e-7);
+  let special = [f32::NAN, f32::INFINITY, f32::NEG_INFINITY, 0.0, -0.0, 0.0, 0.0, 0.0];
+  let (s, c) = sin_cos_m256(m256::from_array(special));
+  let (s, c) = (s.to_array(), c.to_array());
+  assert!(s[..3].iter().chain(&c[..3]).all(|f| f.is_nan()));
+  assert_eq!(s[3].to_bits(), 0.0_f32.to_bits());
+  assert_eq!(s[4].to_bits(), (-0.0_f32).to_bits());
+  assert_eq!(c[3], 1.0);
+}
@@ -14,3 +14,60 @@ fn test_mul_i64_carryless_m128i() {
   let c: [i64; 2] = mul_i64_carryless_m128i::<{ 1 | (1 << 4) }>(x, y).into();
   assert_eq!(c, [540_i64, 0]); // not 1500 like a normal mul would be!
 }
+
+#[test]
+fn test_mul_carryless_full_m128i() {
+  fn reference(a: u128, b: u128) -> (u128, u128) {
+    let (mut low, mut high) = (0_u128, 0_u128);
+    for i in 0..128 {
+      if (b >> i) & 1 != 0 {
+        low ^= a << i;
+        high ^= if i == 0 { 0 } else { a >> (128 - i) };
+      }
+    }
+    (low, high)
+  }
+  let mut state = 0xFEDC_BA98_7654_3210_0123_4567_89AB_CDEF_u128;
+  for _ in 0..256 {
+    state ^= state << 13;
+    state ^= state >> 7;
+    state ^= state << 17;
+    let (a, b) = (state, state.rotate_left(37) ^ 0xAAAA);
+    let (low, high) = mul_carryless_full_m128i(m128i::from(a), m128i::from(b));
+    assert_eq!((u128::from(low), u128::from(high)), reference(a, b));
+  }
+}
+
+#[test]
+fn test_ghash_multiply() {
+  // Algorithm 1 of the GCM spec, one bit at a time.
+  fn reference(x: u128, y: u128) -> u128 {
+    let mut z = 0;
+    let mut v = y;
+    for i in 0..128 {
+      if (x >> (127 - i)) & 1 != 0 {
+        z ^= v;
+      }
+      v = if v & 1 != 0 { (v >> 1) ^ (0xE1 << 120) } else { v >> 1 };
+    }
+    z
+  }
+  let mut state = 0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210_u128;
+  for _ in 0..256 {
+    // xorshift, just to get a spread of inputs
+    state ^= state << 13;
+    state ^= state >> 7;
+    state ^= state << 17;
+    let x = state;
+    let h = state.rotate_left(64) ^ 0x5555;
+    let c = u128::from(ghash_multiply(m128i::from(x), m128i::from(h)));
+    assert_eq!(c, reference(x, h), "{x:032X} * {h:032X}");
+  }
+  // GCM spec, test case 2: GHASH(H, {}, C)
+  let h = 0x66e94bd4ef8a2c3b884cfa59ca342b2e_u128;
+  let c = 0x0388dace60b6a392f328c2b971b2fe78_u128;
+  let len = 128_u128;
+  let y = ghash_multiply(m128i::from(c), m128i::from(h));
+  let y = ghash_multiply(m128i::from(u128::from(y) ^ len), m128i::from(h));
+  assert_eq!(u128::from(y), 0xf38cbb1ad69223dcc3457ae5b6b0f885);
+}
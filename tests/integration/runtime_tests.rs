@@ -0,0 +1,230 @@
+use super::*;
+
+#[test]
+fn test_tokens_match_cpu_features() {
+  let f = cpu_features();
+  assert_eq!(Ssse3Token::detect().is_some(), f.sse && f.sse2 && f.sse3 && f.ssse3);
+  assert_eq!(Sse41Token::detect().is_some(), Ssse3Token::detect().is_some() && f.sse4_1);
+  assert_eq!(Sse42Token::detect().is_some(), Sse41Token::detect().is_some() && f.sse4_2);
+  assert_eq!(AvxToken::detect().is_some(), Sse42Token::detect().is_some() && os_supports_avx());
+  assert_eq!(Avx2Token::detect().is_some(), AvxToken::detect().is_some() && f.avx2);
+  // features enabled at compile time must be there at runtime
+  if cfg!(target_feature = "avx2") {
+    assert!(Avx2Token::detect().is_some());
+  }
+}
+
+#[test]
+fn test_token_methods() {
+  if let Some(t) = Sse41Token::detect() {
+    let a = m128i::from([-5, 6, i32::MIN, 0]);
+    let b = m128i::from([3, 3, 3, 3]);
+    assert_eq!(<[i32; 4]>::from(t.max_i32_m128i(a, b)), [3, 6, 3, 3]);
+    assert_eq!(<[i32; 4]>::from(t.mul_32_m128i(a, b)), [-15, 18, i32::MIN, 0]);
+    assert_eq!(<[i32; 4]>::from(t.ssse3().abs_i32_m128i(a)), [5, 6, i32::MIN, 0]);
+  }
+  if let Some(t) = Avx2Token::detect() {
+    let a = m256i::from([1, 2, 3, 4, 5, 6, 7, i32::MAX]);
+    let b = m256i::from([1; 8]);
+    assert_eq!(<[i32; 8]>::from(t.add_i32_m256i(a, b)), [2, 3, 4, 5, 6, 7, 8, i32::MIN]);
+    assert_eq!(<[i32; 8]>::from(t.sub_i32_m256i(a, b)), [0, 1, 2, 3, 4, 5, 6, i32::MAX - 1]);
+    let x = m256::from_array([1.0, 4.0, 9.0, 16.0, 25.0, 36.0, 49.0, 64.0]);
+    let avx = t.avx();
+    assert_eq!(avx.sqrt_m256(x).to_array(), [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+  }
+}
+
+#[test]
+fn test_dynamic_functions() {
+  if let Some(t) = Sse41Token::detect() {
+    let a = m128::from_array([-0.5, 1.5, 2.5, -2.5]);
+    // toward zero, and don't raise exceptions
+    let c = unsafe { dynamic::round_m128::<0b1011>(a) };
+    assert_eq!(c.to_array(), [-0.0, 1.0, 2.0, -2.0]);
+    assert_eq!(t.round_m128::<0b1011>(a).to_array(), c.to_array());
+  }
+  if cpu_features().fma && AvxToken::detect().is_some() {
+    let a = m256d::from_array([1.0, 2.0, 3.0, 4.0]);
+    let c = unsafe { dynamic::fused_mul_add_m256d(a, a, a) };
+    assert_eq!(c.to_array(), [2.0, 6.0, 12.0, 20.0]);
+  }
+}
+
+fn abs_sse41(t: Sse41Token, data: &mut [i32]) {
+  let mut chunks = data.chunks_exact_mut(4);
+  for chunk in &mut chunks {
+    let a = m128i::from([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    let out: [i32; 4] = t.ssse3().abs_i32_m128i(a).into();
+    chunk.copy_from_slice(&out);
+  }
+  abs_scalar(chunks.into_remainder());
+}
+
+fn abs_ssse3(t: Ssse3Token, data: &mut [i32]) {
+  for x in data.iter_mut() {
+    *x = <[i32; 4]>::from(t.abs_i32_m128i(m128i::from([*x; 4])))[0];
+  }
+}
+
+fn abs_scalar(data: &mut [i32]) {
+  data.iter_mut().for_each(|x| *x = x.wrapping_abs());
+}
+
+safe_arch::simd_dispatch! {
+  fn abs_all(data: &mut [i32]);
+  Sse41Token => abs_sse41,
+  Ssse3Token => abs_ssse3,
+  else => abs_scalar,
+}
+
+#[test]
+fn test_simd_dispatch() {
+  let mut data = [-1, 2, -3, 4, -5, i32::MIN, 0];
+  for _ in 0..2 {
+    let mut d = data;
+    abs_all(&mut d);
+    assert_eq!(d, [1, 2, 3, 4, 5, i32::MIN, 0]);
+  }
+  data.reverse();
+  abs_all(&mut data[..]);
+  assert_eq!(data, [0, i32::MIN, 5, 4, 3, 2, 1]);
+}
+
+#[inline]
+fn name_avx2(_: Avx2Token, suffix: &'static str) -> (&'static str, &'static str) {
+  (Avx2Token::FEATURE, suffix)
+}
+#[inline]
+fn name_sse41(_: Sse41Token, suffix: &'static str) -> (&'static str, &'static str) {
+  (Sse41Token::FEATURE, suffix)
+}
+fn name_scalar(suffix: &'static str) -> (&'static str, &'static str) {
+  ("scalar", suffix)
+}
+
+safe_arch::simd_dispatch! {
+  fn chosen_kernel(suffix: &'static str) -> (&'static str, &'static str);
+  Avx2Token => name_avx2,
+  Sse41Token => name_sse41,
+  else => name_scalar,
+}
+
+#[test]
+fn test_simd_dispatch_picks_best_kernel() {
+  let best = if Avx2Token::detect().is_some() {
+    "avx2"
+  } else if Sse41Token::detect().is_some() {
+    "sse4.1"
+  } else {
+    "scalar"
+  };
+  // the first call picks, and later calls use the same kernel
+  assert_eq!(chosen_kernel("a"), (best, "a"));
+  assert_eq!(chosen_kernel("b"), (best, "b"));
+}
+
+#[test]
+fn test_best_available() {
+  let which = |features| {
+    best_available!(features;
+      Avx2Token => |_| Avx2Token::FEATURE,
+      Sse41Token => |_| Sse41Token::FEATURE,
+      else => || "scalar",
+    )
+  };
+  let best = if Avx2Token::detect().is_some() {
+    "avx2"
+  } else if Sse41Token::detect().is_some() {
+    "sse4.1"
+  } else {
+    "scalar"
+  };
+  assert_eq!(which(cpu_features()), best);
+  let capped = CpuFeatures { avx2: false, ..cpu_features() };
+  assert_eq!(which(capped), if best == "avx2" { "sse4.1" } else { best });
+  assert_eq!(which(CpuFeatures::default()), "scalar");
+}
+
+#[test]
+fn test_with_feature() {
+  let a = m128i::from([-9, 9, -9, 9]);
+  let max: [i32; 4] = with_feature!(sse4_1(t) => {
+    t.max_i32_m128i(a, m128i::from([0; 4])).into()
+  } else {
+    <[i32; 4]>::from(a).map(|x| x.max(0))
+  });
+  assert_eq!(max, [0, 9, 0, 9]);
+  let used_token = with_feature!(ssse3(_t) => { true } else { false });
+  assert_eq!(used_token, Ssse3Token::detect().is_some());
+}
+
+#[test]
+fn test_dispatch_table() {
+  fn one() -> u8 {
+    1
+  }
+  fn two() -> u8 {
+    2
+  }
+  fn three() -> u8 {
+    3
+  }
+  const RARE: CpuFeatures = CpuFeatures { sse4a: true, avx512vbmi2: true, ..CpuFeatures::NONE };
+  static TABLE: DispatchTable<fn() -> u8> = DispatchTable::new(&[(RARE, one), (CpuFeatures::NONE, two)], three);
+  let expected = if usable_cpu_features().contains(RARE) { 1 } else { 2 };
+  assert_eq!(TABLE.chosen_index(), Some(expected as usize - 1));
+  assert_eq!((TABLE.get())(), expected);
+
+  static EMPTY: DispatchTable<fn() -> u8> = DispatchTable::new(&[], three);
+  assert_eq!(EMPTY.chosen_index(), None);
+  assert_eq!((EMPTY.get())(), 3);
+}
+
+#[test]
+fn test_fma_token() {
+  assert_eq!(FmaToken::detect().is_some(), AvxToken::detect().is_some() && cpu_features().fma);
+  if let Some(t) = FmaToken::detect() {
+    let a = m256d::from_array([1.0, 2.0, 3.0, 4.0]);
+    let b = m256d::from_array([2.0; 4]);
+    assert_eq!(t.fused_mul_add_m256d(a, b, a).to_array(), [3.0, 6.0, 9.0, 12.0]);
+    assert_eq!(t.fused_mul_neg_sub_m256d(a, b, a).to_array(), [-3.0, -6.0, -9.0, -12.0]);
+    assert_eq!(t.fused_mul_addsub_m256d(a, b, a).to_array(), [1.0, 6.0, 3.0, 12.0]);
+    let x = m128::from_array([1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(t.fused_mul_neg_add_m128_s(x, x, x).to_array(), [0.0, 2.0, 3.0, 4.0]);
+    let c = with_feature!(fma(t) => { t.fused_mul_sub_m128(x, x, x) } else { x });
+    assert_eq!(c.to_array(), [0.0, 2.0, 6.0, 12.0]);
+  }
+}
+
+#[test]
+fn test_every_module_has_a_token() {
+  if let Some(t) = Sse2Token::detect() {
+    let a = m128i::from([1, 2, 3, i32::MAX]);
+    assert_eq!(<[i32; 4]>::from(t.add_i32_m128i(a, a)), [2, 4, 6, -2]);
+    assert_eq!(t.find_byte(b"safe_arch", b'_'), Some(4));
+    let mut text = *b"Hello, World";
+    t.to_ascii_uppercase_in_place(&mut text);
+    assert_eq!(&text, b"HELLO, WORLD");
+  }
+  if let Some(t) = Sse42Token::detect() {
+    assert_eq!(t.crc32_u8(u32::MAX, b'a') ^ u32::MAX, 0xC1D0_4330);
+  }
+  if let Some(t) = AesToken::detect() {
+    // FIPS-197, Appendix C.1
+    let k = t.aes_key_expand_128([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+    assert_eq!(<[u8; 16]>::from(k[10]), [0x13, 0x11, 0x1D, 0x7F, 0xE3, 0x94, 0x4A, 0x17, 0xF3, 0x07, 0xA7, 0x8B, 0x4D, 0x2B, 0x30, 0xC5]);
+  }
+  if let Some(t) = Bmi2Token::detect() {
+    let m = t.interleave_bits_u32(12345, 67890);
+    assert_eq!(t.deinterleave_bits_u64(m), (12345, 67890));
+  }
+  if let Some(t) = PopcntToken::detect() {
+    assert_eq!(t.population_count_i32(0b1011), 3);
+  }
+  if let Some(t) = LzcntToken::detect() {
+    assert_eq!(t.leading_zero_count_u32(1), 31);
+  }
+  let a = m128i::from([1_i32; 4]);
+  let c: [i32; 4] = with_feature!(sse2(t) => { t.add_i32_m128i(a, a).into() } else { [2; 4] });
+  assert_eq!(c, [2; 4]);
+}
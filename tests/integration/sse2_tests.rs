@@ -1 +1,84 @@
 use super::*;
+
+#[test]
+fn test_ascii_case_in_place() {
+  let all: Vec<u8> = (0..=255).collect();
+  for start in 0..40 {
+    let mut upper = all[start..].to_vec();
+    let mut lower = all[start..].to_vec();
+    to_ascii_uppercase_in_place(&mut upper);
+    to_ascii_lowercase_in_place(&mut lower);
+    assert_eq!(upper, all[start..].to_ascii_uppercase());
+    assert_eq!(lower, all[start..].to_ascii_lowercase());
+    assert_eq!(is_ascii(&all[..start + 88]), start + 88 <= 128);
+  }
+}
+
+#[test]
+fn test_partial_unaligned_load_store() {
+  use core::convert::TryInto;
+  let bytes: [u8; 12] = [9, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
+  for offset in 0..4 {
+    let chunk: &[u8; 8] = bytes[offset..offset + 8].try_into().unwrap();
+    let a = load_unaligned_i64_m128i_s(chunk);
+    let mut out = [0_u8; 12];
+    let target: &mut [u8; 8] = (&mut out[offset..offset + 8]).try_into().unwrap();
+    store_unaligned_i64_m128i_s(target, a);
+    assert_eq!(&out[offset..offset + 8], chunk);
+    assert_eq!(<[i64; 2]>::from(a)[1], 0);
+
+    let small: &[u8; 2] = bytes[offset..offset + 2].try_into().unwrap();
+    let b = load_unaligned_i16_m128i_s(small);
+    assert_eq!(<[u16; 8]>::from(b), [u16::from_le_bytes(*small), 0, 0, 0, 0, 0, 0, 0]);
+  }
+}
+
+#[test]
+fn test_any_all_lanes_true() {
+  let a = m128i::from([5_i16, 5, 5, 5, 5, 5, 5, -5]);
+  let eq = cmp_eq_mask_i16_m128i(a, a);
+  assert!(all_lanes_true_m128i(eq) && any_lane_true_m128i(eq));
+  let gt = cmp_gt_mask_i16_m128i(a, set_splat_i16_m128i(0));
+  assert!(any_lane_true_m128i(gt) && !all_lanes_true_m128i(gt));
+  let d = m128d::from_array([1.0, f64::NAN]);
+  assert!(!all_lanes_true_m128d(cmp_eq_mask_m128d(d, d)));
+  assert!(all_lanes_true_m128d(cmp_eq_mask_m128d(d, d) | cmp_unord_mask_m128d(d, d)));
+}
+
+#[test]
+fn test_convert_f32_slice_to_bf16() {
+  fn reference(f: f32) -> u16 {
+    let bits = f.to_bits();
+    if f.is_nan() {
+      ((bits >> 16) | 0x0040) as u16
+    } else {
+      (bits.wrapping_add(0x7FFF + ((bits >> 16) & 1)) >> 16) as u16
+    }
+  }
+  let src = [0.0, -0.0, 1.0, 1.0 + 1.0 / 256.0, 1.0 + 3.0 / 256.0, -3.3, f32::MAX, f32::MIN, f32::INFINITY, f32::NAN, 1.0e-40, -7.25e20, 0.1];
+  let mut dst = [0_u16; 13];
+  convert_f32_slice_to_bf16(&src, &mut dst);
+  for (s, d) in src.iter().zip(dst.iter()) {
+    assert_eq!(*d, reference(*s), "{}", s);
+  }
+  let mut back = [0.0_f32; 13];
+  convert_bf16_slice_to_f32(&dst, &mut back);
+  for (d, b) in dst.iter().zip(back.iter()) {
+    assert_eq!(b.to_bits(), u32::from(*d) << 16);
+  }
+}
+
+#[test]
+fn test_shift_all_checked_out_of_range() {
+  let a = m128i::from([1_i32, -100, i32::MAX, i32::MIN]);
+  for &count in &[32, 33, 64, 255, 1 << 31, u32::MAX] {
+    assert_eq!(<[i32; 4]>::from(shl_all_checked_u32_m128i(a, count)), [0; 4]);
+    assert_eq!(<[i32; 4]>::from(shr_all_checked_u32_m128i(a, count)), [0; 4]);
+    assert_eq!(<[i32; 4]>::from(shr_all_checked_i32_m128i(a, count)), [0, -1, 0, -1]);
+    assert_eq!(<[i16; 8]>::from(shr_all_checked_i16_m128i(a, count)), [0, 0, -1, -1, -1, 0, 0, -1]);
+  }
+  let b: [i32; 4] = shr_all_checked_i32_m128i(a, 31).into();
+  assert_eq!(b, [0, -1, 0, -1]);
+  let b: [u32; 4] = shr_all_checked_u32_m128i(a, 31).into();
+  assert_eq!(b, [0, 1, 0, 1]);
+}
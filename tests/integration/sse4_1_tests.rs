@@ -244,3 +244,27 @@ fn test_ptest_i128() {
   assert_eq!(testc_m128i(a, b), 0);
   assert_eq!(testc_m128i(b, b), 1);
 }
+
+#[test]
+fn test_widen_u8_to_m128() {
+  let mut bytes = [0_u8; 16];
+  for (i, b) in bytes.iter_mut().enumerate() {
+    *b = (i * 17) as u8;
+  }
+  let w = widen_u8_to_m128(bytes, 1.0);
+  for (i, x) in w.iter().flat_map(|m| m.to_array()).enumerate() {
+    assert_eq!(x, (i * 17) as f32);
+  }
+  let w = widen_u8_to_m128(bytes, 1.0 / 255.0);
+  assert_eq!(w[3].to_array()[3], 1.0);
+  assert_eq!(narrow_m128_to_u8_saturating(w, 255.0), bytes);
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_round_mode_enum_matches_macro() {
+  assert_eq!(RoundMode::Nearest.imm(), round_op!(Nearest));
+  assert_eq!(RoundMode::Floor.imm(), round_op!(NegInf));
+  assert_eq!(RoundMode::Ceil.imm(), round_op!(PosInf));
+  assert_eq!(RoundMode::TowardZero.imm(), round_op!(Zero));
+}
@@ -24,7 +24,6 @@ fn test_crc32_u32() {
 }
 
 #[test]
-#[cfg(target_arch = "x86_64")]
 fn test_crc32_u64() {
   assert_eq!(crc32_u64(u64::MAX, u64::MAX), 3080238136_u64);
 }
@@ -154,3 +154,25 @@ fn test_sign_apply_i32_m128i() {
   let c: [i32; 4] = sign_apply_i32_m128i(a, b).into();
   assert_eq!(c, [1, -2, -3, 0]);
 }
+
+#[test]
+fn test_hex_encode_decode() {
+  let bytes: Vec<u8> = (0..100_u32).map(|i| (i * 151 + 7) as u8).collect();
+  for n in 0..bytes.len() {
+    let mut hex = vec![0_u8; n * 2];
+    hex_encode(&bytes[..n], &mut hex);
+    let expected: String = bytes[..n].iter().map(|b| format!("{b:02x}")).collect();
+    assert_eq!(hex, expected.as_bytes());
+    let mut back = vec![0_u8; n];
+    assert_eq!(hex_decode(&hex.to_ascii_uppercase(), &mut back), Ok(()));
+    assert_eq!(back, &bytes[..n]);
+  }
+  let mut hex = vec![b'a'; 200];
+  for i in 0..hex.len() {
+    for bad in [b'/', b':', b'@', b'G', b'`', b'g', 0x80, 0xC1, 0xE1] {
+      hex[i] = bad;
+      assert_eq!(hex_decode(&hex, &mut [0; 100]), Err(i));
+    }
+    hex[i] = b'7';
+  }
+}